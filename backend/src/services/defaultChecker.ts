@@ -1,4 +1,5 @@
 import {
+  Address,
   BASE_FEE,
   Keypair,
   Operation,
@@ -118,7 +119,11 @@ export class DefaultChecker {
       );
     }
 
-    const secret = process.env.LOAN_MANAGER_ADMIN_SECRET;
+    // Defaults only need the keeper role; fall back to the admin key.
+    const secretVar = process.env.LOAN_MANAGER_KEEPER_SECRET
+      ? 'LOAN_MANAGER_KEEPER_SECRET'
+      : 'LOAN_MANAGER_ADMIN_SECRET';
+    const secret = process.env[secretVar];
     if (!secret) {
       throw AppError.internal(
         'Default checker misconfiguration: LOAN_MANAGER_ADMIN_SECRET is not set',
@@ -129,9 +134,7 @@ export class DefaultChecker {
     try {
       signer = Keypair.fromSecret(secret);
     } catch {
-      throw AppError.internal(
-        'Default checker misconfiguration: LOAN_MANAGER_ADMIN_SECRET is invalid',
-      );
+      throw AppError.internal(`Default checker misconfiguration: ${secretVar} is invalid`);
     }
 
     const server = createSorobanRpcServer();
@@ -269,7 +272,8 @@ export class DefaultChecker {
         Operation.invokeContractFunction({
           contract: this.contractId,
           function: 'check_defaults',
-          args: [loanIdsScVal],
+          // The signer is passed as caller; it must be the admin or a keeper.
+          args: [new Address(signer.publicKey()).toScVal(), loanIdsScVal],
         }),
       )
       .setTimeout(30)
@@ -689,10 +689,14 @@ export class EventIndexer {
         throw new Error(`LoanApproved event has invalid term_ledgers: ${event.id}`);
       }
     } else if (type === 'LoanRepaid') {
+      // (type, borrower, loan_id), [amount, score_delta, new_score]; older
+      // deployments emit the bare amount.
       if (!event.topic[1] || !event.topic[2]) return null;
       address = this.decodeAddress(event.topic[1]);
       loanId = this.decodeLoanId(event.topic[2]);
-      amount = this.decodeAmount(event.value);
+      amount = Array.isArray(scValToNative(event.value))
+        ? this.decodeTupleFirstNumericValue(event.value)
+        : this.decodeAmount(event.value);
     } else if (type === 'LoanDefaulted') {
       if (!event.topic[1]) return null;
       loanId = this.decodeLoanId(event.topic[1]);
@@ -7,6 +7,7 @@ import {
   Address,
   StrKey,
   Keypair,
+  xdr,
 } from '@stellar/stellar-sdk';
 import logger from '../utils/logger.js';
 import { AppError } from '../errors/AppError.js';
@@ -195,12 +196,14 @@ class SorobanService {
   }
 
   /**
-   * Builds an unsigned Soroban `request_loan(borrower, amount)` transaction.
+   * Builds an unsigned Soroban `request_loan(borrower, amount, term)` transaction,
+   * leaving memo, token, grace_ledgers and cosigner unset.
    * Returns base64 XDR for the frontend to sign with the user's wallet.
    */
   async buildRequestLoanTx(
     borrowerPublicKey: string,
     amount: number,
+    termLedgers = 17_280,
   ): Promise<{ unsignedTxXdr: string; networkPassphrase: string }> {
     const server = this.getRpcServer();
     const contractId = this.getLoanManagerContractId();
@@ -212,6 +215,7 @@ class SorobanService {
       type: 'address',
     });
     const amountScVal = nativeToScVal(BigInt(amount), { type: 'i128' });
+    const termScVal = nativeToScVal(termLedgers, { type: 'u32' });
 
     const tx = new TransactionBuilder(account, {
       fee: BASE_FEE,
@@ -221,7 +225,15 @@ class SorobanService {
         Operation.invokeContractFunction({
           contract: contractId,
           function: 'request_loan',
-          args: [borrowerScVal, amountScVal],
+          args: [
+            borrowerScVal,
+            amountScVal,
+            termScVal,
+            xdr.ScVal.scvVoid(),
+            xdr.ScVal.scvVoid(),
+            xdr.ScVal.scvVoid(),
+            xdr.ScVal.scvVoid(),
+          ],
         }),
       )
       .setTimeout(30)
@@ -267,7 +279,8 @@ class SorobanService {
         Operation.invokeContractFunction({
           contract: contractId,
           function: 'repay',
-          args: [borrowerScVal, loanIdScVal, amountScVal],
+          // No idempotency key; pass one to make retries replay-safe.
+          args: [borrowerScVal, loanIdScVal, amountScVal, nativeToScVal(null)],
         }),
       )
       .setTimeout(30)
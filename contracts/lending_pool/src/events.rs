@@ -1,4 +1,4 @@
-use crate::DataKey;
+use crate::{DataKey, EventVerbosity, LendingPool};
 use soroban_sdk::{Address, Env, Symbol};
 
 pub fn deposit(env: &Env, provider: Address, token: Address, amount: i128, shares_minted: i128) {
@@ -14,16 +14,13 @@ pub fn withdraw(env: &Env, provider: Address, token: Address, amount: i128, shar
 #[allow(dead_code)]
 pub fn yield_distributed(env: &Env, token: Address, amount: i128) {
     if amount > 0 {
-        let total = env
-            .storage()
-            .instance()
-            .get::<_, i128>(&DataKey::TotalYieldDistributed(token.clone()))
-            .unwrap_or(0)
-            .checked_add(amount)
-            .expect("total yield distributed overflow");
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalYieldDistributed(token.clone()), &total);
+        LendingPool::adjust_counter(
+            env,
+            &DataKey::TotalYieldDistributed(token.clone()),
+            amount,
+            "total yield distributed overflow",
+            "total yield distributed underflow",
+        );
     }
 
     let topics = (Symbol::new(env, "YieldDistributed"), token);
@@ -35,14 +32,88 @@ pub fn deposit_cap_updated(env: &Env, token: Address, old_cap: i128, new_cap: i1
     env.events().publish(topics, (old_cap, new_cap));
 }
 
-pub fn pool_paused(env: &Env) {
-    let topics = (Symbol::new(env, "PoolPaused"),);
-    env.events().publish(topics, ());
+pub fn water_marks_updated(env: &Env, high_bps: u32, low_bps: u32) {
+    let topics = (Symbol::new(env, "WaterMarksUpdated"),);
+    env.events().publish(topics, (high_bps, low_bps));
 }
 
-pub fn pool_unpaused(env: &Env) {
-    let topics = (Symbol::new(env, "PoolUnpaused"),);
-    env.events().publish(topics, ());
+pub fn reserve_ratio_updated(env: &Env, reserve_bps: u32) {
+    let topics = (Symbol::new(env, "ReserveRatioUpdated"),);
+    env.events().publish(topics, reserve_bps);
+}
+
+pub fn threshold_crossed(env: &Env, token: Address, direction: Symbol, utilization_bps: u32) {
+    if !LendingPool::emits(env, EventVerbosity::Normal) {
+        return;
+    }
+    let topics = (Symbol::new(env, "Threshold"), token, direction);
+    env.events().publish(topics, utilization_bps);
+}
+
+pub fn event_verbosity_updated(env: &Env, verbosity: EventVerbosity) {
+    let topics = (Symbol::new(env, "EventVerbosityUpdated"),);
+    env.events().publish(topics, verbosity);
+}
+
+pub fn provider_score_gate_updated(env: &Env, nft_contract: Address, min_score: u32) {
+    let topics = (Symbol::new(env, "ProviderScoreGateUpdated"), nft_contract);
+    env.events().publish(topics, min_score);
+}
+
+pub fn supported_token_updated(env: &Env, token: Address, supported: bool) {
+    let topics = (Symbol::new(env, "SupportedTokenUpdated"), token);
+    env.events().publish(topics, supported);
+}
+
+pub fn loan_manager_updated(env: &Env, loan_manager: Address) {
+    let topics = (Symbol::new(env, "LoanManagerUpdated"),);
+    env.events().publish(topics, loan_manager);
+}
+
+pub fn strict_collateral_lock_updated(env: &Env, enabled: bool) {
+    let topics = (Symbol::new(env, "StrictCollateralLockUpdated"),);
+    env.events().publish(topics, enabled);
+}
+
+pub fn interest_recorded(env: &Env, token: Address, amount: i128) {
+    if !LendingPool::emits(env, EventVerbosity::Normal) {
+        return;
+    }
+    let topics = (Symbol::new(env, "InterestRecorded"), token);
+    env.events().publish(topics, amount);
+}
+
+pub fn token_recovered(env: &Env, admin: Address, token: Address, to: Address, amount: i128) {
+    let topics = (Symbol::new(env, "TokenRecovered"), admin, token);
+    env.events().publish(topics, (to, amount));
+}
+
+pub fn pool_paused(env: &Env, admin: Address) {
+    let topics = (Symbol::new(env, "PoolPaused"), admin);
+    env.events().publish(topics, env.ledger().timestamp());
+}
+
+pub fn pool_unpaused(env: &Env, admin: Address) {
+    let topics = (Symbol::new(env, "PoolUnpaused"), admin);
+    env.events().publish(topics, env.ledger().timestamp());
+}
+
+pub fn early_exit_fee_updated(env: &Env, fee_bps: u32, treasury: Address) {
+    let topics = (Symbol::new(env, "EarlyExitFeeUpdated"), treasury);
+    env.events().publish(topics, fee_bps);
+}
+
+pub fn committed(env: &Env, provider: Address, token: Address, until_ts: u64) {
+    if !LendingPool::emits(env, EventVerbosity::Normal) {
+        return;
+    }
+    let topics = (Symbol::new(env, "Committed"), provider, token);
+    env.events().publish(topics, until_ts);
+}
+
+pub fn early_exit_fee_charged(env: &Env, provider: Address, token: Address, fee: i128) {
+    let topics = (Symbol::new(env, "EarlyExitFee"), provider, token);
+    env.events().publish(topics, fee);
 }
 
 pub fn withdrawal_cooldown_updated(env: &Env, old_cooldown: u32, new_cooldown: u32) {
@@ -59,3 +130,23 @@ pub fn admin_transferred(env: &Env, previous_admin: Address, new_admin: Address,
     let topics = (Symbol::new(env, "AdminTransferred"), via);
     env.events().publish(topics, (previous_admin, new_admin));
 }
+
+pub fn migration_swept(env: &Env, admin: Address, token: Address, to: Address, amount: i128) {
+    let topics = (Symbol::new(env, "MigrationSwept"), admin, token);
+    env.events().publish(topics, (to, amount));
+}
+
+pub fn token_migrated(env: &Env, admin: Address, old_token: Address, new_token: Address) {
+    let topics = (Symbol::new(env, "TokenMigrated"), admin, old_token);
+    env.events().publish(topics, new_token);
+}
+
+pub fn auto_pause_updated(env: &Env, enabled: bool) {
+    let topics = (Symbol::new(env, "AutoPauseUpdated"),);
+    env.events().publish(topics, enabled);
+}
+
+pub fn auto_paused(env: &Env, token: Address) {
+    let topics = (Symbol::new(env, "AutoPause"), token);
+    env.events().publish(topics, env.ledger().timestamp());
+}
@@ -2,12 +2,23 @@
 // Lending pool contract for RemitLend.
 use soroban_sdk::token::Client as TokenClient;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, Symbol,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    BytesN, Env, Symbol, Val, Vec,
 };
 
 mod events;
 use events::*;
 
+#[contractclient(name = "NftClient")]
+pub trait RemittanceNftInterface {
+    fn current_score(env: Env, user: Address) -> u32;
+}
+
+#[contractclient(name = "LoanManagerClient")]
+pub trait LoanManagerInterface {
+    fn has_locked_collateral(env: Env, borrower: Address) -> bool;
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum PoolError {
@@ -21,6 +32,15 @@ pub enum PoolError {
     InvalidMaxPoolSize = 9,
     NoProposedAdmin = 10,
     CooldownTooLong = 11,
+    InvalidThreshold = 12,
+    InsufficientProviderScore = 13,
+    InvalidFee = 14,
+    InvalidCommitment = 15,
+    TokenInUse = 16,
+    CollateralLocked = 17,
+    ContractNotPaused = 18,
+    OutstandingBorrows = 19,
+    BalanceNotSwept = 20,
 }
 
 /// Storage keys.
@@ -57,6 +77,54 @@ pub enum DataKey {
     TotalYieldDistributed(Address),
     ProposedAdmin,
     Version,
+    StorageVersion,
+    /// utilisation (bps) at or above which a `Threshold` "up" event fires (0 = off)
+    HighWaterBps,
+    /// utilisation (bps) below which a `Threshold` "down" event fires (0 = off)
+    LowWaterBps,
+    /// token → utilisation observed at the last threshold check; used to
+    /// debounce `Threshold` events so they only fire on a crossing
+    LastUtilizationBps(Address),
+    /// remittance NFT contract consulted by the provider score gate
+    NftContract,
+    /// minimum NFT score required to deposit (0 = gate off)
+    MinProviderScore,
+    /// token → whether the loan manager may lend it out of this pool
+    SupportedToken(Address),
+    /// (provider, token) → ledger timestamp until which the position is committed
+    Commitment(Address, Address),
+    /// fee (bps) on redemptions made before a commitment expires (0 = off)
+    EarlyExitFeeBps,
+    /// recipient of early exit fees
+    Treasury,
+    /// loan manager allowed to report repaid interest
+    LoanManager,
+    /// token → lifetime interest repaid into the pool by borrowers
+    TotalInterestEarned(Address),
+    /// token → providers currently holding shares, in first-deposit order
+    ProviderList(Address),
+    /// share of tracked deposits (bps) that must stay idle in the pool (0 = off)
+    ReserveBps,
+    /// which optional events are published (unset = `Normal`)
+    EventVerbosity,
+    /// refuse withdrawals from providers with loan collateral locked in the
+    /// loan manager
+    StrictCollateralLock,
+    /// old token → token its pool accounting was migrated to
+    MigratedToken(Address),
+    /// pause the pool when a withdrawal or loan leaves a token insolvent
+    AutoPause,
+}
+
+/// How many optional events the pool publishes. Critical events (deposits,
+/// withdrawals, admin and config changes) always fire; lower-value ones
+/// such as utilisation threshold crossings are dropped under `Minimal`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventVerbosity {
+    Minimal,
+    Normal,
+    Verbose,
 }
 
 #[contracttype]
@@ -73,6 +141,32 @@ pub struct PoolStats {
     pub utilization_bps: u32,
 }
 
+/// One-call health snapshot for a token pool, returned by `pool_status`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolStatus {
+    pub total_deposits: i128,
+    pub total_outstanding: i128,
+    pub pool_token_balance: i128,
+    pub utilization_bps: u32,
+    /// True when a high water mark is set and utilization is at or above it.
+    pub above_high_water: bool,
+    pub paused: bool,
+    /// Idle balance plus outstanding loans still cover tracked deposits.
+    pub solvent: bool,
+}
+
+/// How much can be deposited and borrowed right now, returned by `capacity`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capacity {
+    /// Deposits still accepted under the max pool size (`i128::MAX` when
+    /// uncapped).
+    pub deposit_headroom: i128,
+    /// Largest loan the pool can fund without dipping into its reserve.
+    pub borrow_headroom: i128,
+}
+
 #[contract]
 pub struct LendingPool;
 
@@ -83,9 +177,14 @@ impl LendingPool {
     const PERSISTENT_TTL_THRESHOLD: u32 = 17280;
     const PERSISTENT_TTL_BUMP: u32 = 518400;
     const CURRENT_VERSION: u32 = 3;
+    const STORAGE_VERSION: u32 = 1;
     const DEFAULT_WITHDRAWAL_COOLDOWN: u32 = 1_440;
     const SHARE_PRICE_SCALE: i128 = 1_000_000;
     const MAX_WITHDRAWAL_COOLDOWN_LEDGERS: u32 = 17_280 * 30;
+    const MAX_EARLY_EXIT_FEE_BPS: u32 = 10_000;
+    const MAX_PROVIDER_PAGE: u32 = 50;
+    const MAX_WITHDRAW_BATCH: u32 = 50;
+    const BPS_DENOMINATOR: i128 = 10_000;
 
     // ── TTL helpers ───────────────────────────────────────────────────────
 
@@ -105,12 +204,48 @@ impl LendingPool {
 
     // ── Storage accessors ─────────────────────────────────────────────────
 
+    /// Panic with the uniform "not initialized" message if `initialize`
+    /// has not run. Admin reads and the pause guard go through this.
+    fn require_initialized(env: &Env) {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            panic!("not initialized");
+        }
+    }
+
+    fn assert_no_locked_collateral(env: &Env, provider: &Address) -> Result<(), PoolError> {
+        if !Self::get_strict_collateral_lock(env.clone()) {
+            return Ok(());
+        }
+        if let Some(loan_manager) = Self::get_loan_manager(env.clone()) {
+            if LoanManagerClient::new(env, &loan_manager).has_locked_collateral(provider) {
+                return Err(PoolError::CollateralLocked);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether events tagged with `level` should be published under the
+    /// configured verbosity.
+    fn emits(env: &Env, level: EventVerbosity) -> bool {
+        fn rank(level: EventVerbosity) -> u32 {
+            match level {
+                EventVerbosity::Minimal => 0,
+                EventVerbosity::Normal => 1,
+                EventVerbosity::Verbose => 2,
+            }
+        }
+        let configured: EventVerbosity = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventVerbosity)
+            .unwrap_or(EventVerbosity::Normal);
+        rank(configured) >= rank(level)
+    }
+
     fn admin(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("not initialized")
+        env.storage().instance().get(&DataKey::Admin).unwrap()
     }
 
     fn read_pool_balance(env: &Env, token: &Address) -> i128 {
@@ -133,6 +268,25 @@ impl LendingPool {
             .expect("total assets overflow")
     }
 
+    /// Apply `delta` to an i128 counter in instance storage and return the new
+    /// value. Panics with `overflow` if the sum overflows and with `underflow`
+    /// if it would go negative, so counters never wrap or saturate silently.
+    fn adjust_counter(
+        env: &Env,
+        key: &DataKey,
+        delta: i128,
+        overflow: &'static str,
+        underflow: &'static str,
+    ) -> i128 {
+        let current: i128 = env.storage().instance().get(key).unwrap_or(0);
+        let updated = current.checked_add(delta).expect(overflow);
+        if updated < 0 {
+            panic!("{}", underflow);
+        }
+        env.storage().instance().set(key, &updated);
+        updated
+    }
+
     fn total_deposits(env: &Env, token: &Address) -> i128 {
         Self::bump_instance_ttl(env);
         env.storage()
@@ -167,6 +321,27 @@ impl LendingPool {
         deposit_ledger
     }
 
+    fn read_provider_list(env: &Env, token: &Address) -> Vec<Address> {
+        let key = DataKey::ProviderList(token.clone());
+        match env.storage().persistent().get(&key) {
+            Some(list) => {
+                Self::bump_persistent_ttl(env, &key);
+                list
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn write_provider_list(env: &Env, token: &Address, list: &Vec<Address>) {
+        let key = DataKey::ProviderList(token.clone());
+        if list.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, list);
+            Self::bump_persistent_ttl(env, &key);
+        }
+    }
+
     fn read_depositor_count(env: &Env, token: &Address) -> u32 {
         env.storage()
             .instance()
@@ -190,7 +365,70 @@ impl LendingPool {
             .unwrap_or(Self::DEFAULT_WITHDRAWAL_COOLDOWN)
     }
 
+    fn utilization_bps(env: &Env, token: &Address) -> u32 {
+        let total_deposits = Self::total_deposits(env, token);
+        let pool_token_balance = Self::read_pool_balance(env, token);
+
+        // Utilisation: portion of tracked principal currently out on loan.
+        if total_deposits > 0 && pool_token_balance < total_deposits {
+            let borrowed = total_deposits - pool_token_balance;
+            (borrowed.checked_mul(10_000).expect("utilization overflow") / total_deposits) as u32
+        } else {
+            0
+        }
+    }
+
+    fn water_marks(env: &Env) -> (u32, u32) {
+        Self::bump_instance_ttl(env);
+        let high: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::HighWaterBps)
+            .unwrap_or(0);
+        let low: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LowWaterBps)
+            .unwrap_or(0);
+        (high, low)
+    }
+
+    /// Compare current utilisation against the configured water marks and
+    /// emit a `Threshold` event only when a mark is crossed since the last
+    /// check, so a pool sitting above the high mark does not re-fire on
+    /// every transaction.
+    fn check_utilization_thresholds(env: &Env, token: &Address) {
+        let (high, low) = Self::water_marks(env);
+        if high == 0 && low == 0 {
+            return;
+        }
+
+        let key = DataKey::LastUtilizationBps(token.clone());
+        let previous: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        let current = Self::utilization_bps(env, token);
+        if previous == current {
+            return;
+        }
+
+        if high > 0 && previous < high && current >= high {
+            threshold_crossed(env, token.clone(), symbol_short!("up"), current);
+        } else if low > 0 && previous >= low && current < low {
+            threshold_crossed(env, token.clone(), symbol_short!("down"), current);
+        }
+
+        env.storage().instance().set(&key, &current);
+        Self::bump_instance_ttl(env);
+    }
+
+    fn min_provider_score(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinProviderScore)
+            .unwrap_or(0)
+    }
+
     fn assert_not_paused(env: &Env) -> Result<(), PoolError> {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         let paused: bool = env
             .storage()
@@ -282,10 +520,21 @@ impl LendingPool {
             return Err(PoolError::InsufficientLiquidity);
         }
 
-        TokenClient::new(env, token).transfer(
+        let early_exit_fee = Self::early_exit_fee(env, provider, token, assets_to_return);
+        let token_client = TokenClient::new(env, token);
+        if early_exit_fee > 0 {
+            let treasury: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Treasury)
+                .expect("treasury not set");
+            token_client.transfer(&env.current_contract_address(), &treasury, &early_exit_fee);
+            early_exit_fee_charged(env, provider.clone(), token.clone(), early_exit_fee);
+        }
+        token_client.transfer(
             &env.current_contract_address(),
             provider,
-            &assets_to_return,
+            &(assets_to_return - early_exit_fee),
         );
 
         let share_key = DataKey::Shares(provider.clone(), token.clone());
@@ -294,40 +543,125 @@ impl LendingPool {
         if remaining == 0 {
             env.storage().persistent().remove(&share_key);
             env.storage().persistent().remove(&deposit_key);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Commitment(provider.clone(), token.clone()));
             let count = Self::read_depositor_count(env, token);
             env.storage().instance().set(
                 &DataKey::DepositorCount(token.clone()),
                 &count.saturating_sub(1),
             );
+            let mut providers = Self::read_provider_list(env, token);
+            if let Some(index) = providers.first_index_of(provider) {
+                providers.remove(index);
+                Self::write_provider_list(env, token, &providers);
+            }
         } else {
             env.storage().persistent().set(&share_key, &remaining);
             Self::bump_persistent_ttl(env, &share_key);
             Self::bump_persistent_ttl(env, &deposit_key);
         }
 
-        let new_total_shares = cur_total_shares
-            .checked_sub(shares)
-            .expect("total shares underflow");
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalShares(token.clone()), &new_total_shares);
+        Self::adjust_counter(
+            env,
+            &DataKey::TotalShares(token.clone()),
+            -shares,
+            "total shares overflow",
+            "total shares underflow",
+        );
 
-        let new_total_deposits = Self::total_deposits(env, token).saturating_sub(assets_to_return);
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalDeposits(token.clone()), &new_total_deposits);
+        // Redemptions include yield, so they can exceed tracked principal;
+        // floor the principal counter at zero rather than underflowing.
+        let principal_returned = assets_to_return.min(Self::total_deposits(env, token));
+        Self::adjust_counter(
+            env,
+            &DataKey::TotalDeposits(token.clone()),
+            -principal_returned,
+            "total deposits overflow",
+            "total deposits underflow",
+        );
 
         Self::bump_instance_ttl(env);
         withdraw(
             env,
             provider.clone(),
             token.clone(),
-            assets_to_return,
+            assets_to_return - early_exit_fee,
             shares,
         );
+        Self::check_utilization_thresholds(env, token);
+        Self::auto_pause_if_insolvent(env, token);
         Ok(())
     }
 
+    fn solvent(env: &Env, token: &Address) -> bool {
+        Self::total_pool_assets(env, token) >= Self::total_deposits(env, token)
+    }
+
+    /// Circuit breaker: with auto-pause on, pause the pool as soon as a
+    /// mutation leaves `token` insolvent.
+    fn auto_pause_if_insolvent(env: &Env, token: &Address) {
+        if !Self::get_auto_pause(env.clone())
+            || Self::is_paused(env.clone())
+            || Self::solvent(env, token)
+        {
+            return;
+        }
+        env.storage().instance().set(&DataKey::Paused, &true);
+        auto_paused(env, token.clone());
+    }
+
+    /// `value * bps / 10_000`, rounded down, without overflowing on the
+    /// intermediate product.
+    fn mul_bps(value: i128, bps: u32) -> i128 {
+        let bps = bps as i128;
+        (value / Self::BPS_DENOMINATOR)
+            .checked_mul(bps)
+            .and_then(|whole| {
+                whole.checked_add(value % Self::BPS_DENOMINATOR * bps / Self::BPS_DENOMINATOR)
+            })
+            .expect("bps overflow")
+    }
+
+    /// Fee owed on redeeming `assets` while `provider`'s commitment in
+    /// `token` is still running; zero once it lapses or with no commitment.
+    fn early_exit_fee(env: &Env, provider: &Address, token: &Address, assets: i128) -> i128 {
+        let committed_until = Self::read_commitment(env, provider, token);
+        if env.ledger().timestamp() >= committed_until {
+            return 0;
+        }
+        let fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EarlyExitFeeBps)
+            .unwrap_or(0);
+        Self::mul_bps(assets, fee_bps)
+    }
+
+    fn move_instance(env: &Env, from: DataKey, to: DataKey) {
+        if let Some(value) = env.storage().instance().get::<_, Val>(&from) {
+            env.storage().instance().set(&to, &value);
+            env.storage().instance().remove(&from);
+        }
+    }
+
+    fn move_persistent(env: &Env, from: DataKey, to: DataKey) {
+        if let Some(value) = env.storage().persistent().get::<_, Val>(&from) {
+            env.storage().persistent().set(&to, &value);
+            env.storage().persistent().remove(&from);
+            Self::bump_persistent_ttl(env, &to);
+        }
+    }
+
+    fn read_commitment(env: &Env, provider: &Address, token: &Address) -> u64 {
+        let key = DataKey::Commitment(provider.clone(), token.clone());
+        let committed_until = env.storage().persistent().get(&key).unwrap_or(0);
+        if committed_until > 0 {
+            Self::bump_persistent_ttl(env, &key);
+        }
+        committed_until
+    }
+
     // ── Admin / lifecycle ─────────────────────────────────────────────────
 
     pub fn initialize(env: Env, admin: Address) -> Result<(), PoolError> {
@@ -343,6 +677,9 @@ impl LendingPool {
         env.storage()
             .instance()
             .set(&DataKey::Version, &Self::CURRENT_VERSION);
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &Self::STORAGE_VERSION);
         Self::bump_instance_ttl(&env);
         Ok(())
     }
@@ -352,6 +689,16 @@ impl LendingPool {
         env.storage().instance().get(&DataKey::Version).unwrap_or(0)
     }
 
+    /// Storage layout version, advanced by future migrations. Contracts
+    /// initialized before the marker existed report 0.
+    pub fn storage_version(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(0)
+    }
+
     pub fn get_admin(env: Env) -> Address {
         Self::admin(&env)
     }
@@ -392,6 +739,33 @@ impl LendingPool {
         Ok(())
     }
 
+    /// Charge `fee_bps` of the redeemed assets, paid to `treasury`, when a
+    /// provider withdraws before their commitment expires.
+    pub fn set_early_exit_fee(env: Env, fee_bps: u32, treasury: Address) -> Result<(), PoolError> {
+        Self::admin(&env).require_auth();
+        if fee_bps > Self::MAX_EARLY_EXIT_FEE_BPS {
+            return Err(PoolError::InvalidFee);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyExitFeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Self::bump_instance_ttl(&env);
+        early_exit_fee_updated(&env, fee_bps, treasury);
+        Ok(())
+    }
+
+    /// Returns `(fee_bps, treasury)`.
+    pub fn get_early_exit_fee(env: Env) -> (u32, Option<Address>) {
+        Self::bump_instance_ttl(&env);
+        let fee_bps = env
+            .storage()
+            .instance()
+            .get(&DataKey::EarlyExitFeeBps)
+            .unwrap_or(0);
+        (fee_bps, env.storage().instance().get(&DataKey::Treasury))
+    }
+
     pub fn set_withdrawal_cooldown(env: Env, ledgers: u32) -> Result<(), PoolError> {
         Self::admin(&env).require_auth();
         if ledgers > Self::MAX_WITHDRAWAL_COOLDOWN_LEDGERS {
@@ -409,6 +783,329 @@ impl LendingPool {
         Ok(())
     }
 
+    /// Configure the utilisation water marks (in bps) used for `Threshold`
+    /// events. A zero mark disables that direction.
+    pub fn set_utilization_water_marks(
+        env: Env,
+        high_bps: u32,
+        low_bps: u32,
+    ) -> Result<(), PoolError> {
+        Self::admin(&env).require_auth();
+        if high_bps > 10_000 || low_bps > 10_000 || (high_bps > 0 && low_bps > high_bps) {
+            return Err(PoolError::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HighWaterBps, &high_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::LowWaterBps, &low_bps);
+        Self::bump_instance_ttl(&env);
+
+        water_marks_updated(&env, high_bps, low_bps);
+        Ok(())
+    }
+
+    /// Returns `(high_water_bps, low_water_bps)`.
+    pub fn get_utilization_water_marks(env: Env) -> (u32, u32) {
+        Self::water_marks(&env)
+    }
+
+    /// Re-evaluate the utilisation water marks for `token`.
+    ///
+    /// Deposits and withdrawals check automatically; the loan manager calls
+    /// this after moving funds in or out of the pool for borrows and repays.
+    pub fn sync_utilization(env: Env, token: Address) {
+        Self::check_utilization_thresholds(&env, &token);
+        Self::auto_pause_if_insolvent(&env, &token);
+    }
+
+    /// Only accept deposits from providers whose remittance NFT score in
+    /// `nft_contract` is at least `min_score`. A `min_score` of 0 lets anyone
+    /// deposit.
+    pub fn set_provider_score_gate(env: Env, nft_contract: Address, min_score: u32) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::NftContract, &nft_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::MinProviderScore, &min_score);
+        Self::bump_instance_ttl(&env);
+        provider_score_gate_updated(&env, nft_contract, min_score);
+    }
+
+    /// Send `amount` of a token that was transferred to the pool by mistake
+    /// to `to`. Tokens the pool accounts for — any with LP shares, tracked
+    /// deposits or loans outstanding, or allowlisted for lending — are
+    /// rejected with `TokenInUse` so LP funds cannot be swept.
+    pub fn recover_token(
+        env: Env,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), PoolError> {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        if amount <= 0 {
+            return Err(PoolError::InvalidAmount);
+        }
+        if Self::total_shares(&env, &token) > 0
+            || Self::total_deposits(&env, &token) > 0
+            || Self::read_total_outstanding(&env, &token) > 0
+            || Self::is_supported_token(env.clone(), token.clone())
+        {
+            return Err(PoolError::TokenInUse);
+        }
+        if Self::read_pool_balance(&env, &token) < amount {
+            return Err(PoolError::InsufficientBalance);
+        }
+
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+        token_recovered(&env, admin, token, to, amount);
+        Ok(())
+    }
+
+    /// Transfer the pool's whole `token` balance to `to` ahead of
+    /// `migrate_token`, e.g. to redeem it for the reissued asset. Unlike
+    /// `recover_token` this works on a token in use, but only while the pool
+    /// is paused and nothing in `token` is lent out. Returns the amount
+    /// swept.
+    pub fn sweep_for_migration(env: Env, token: Address, to: Address) -> Result<i128, PoolError> {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        if !Self::is_paused(env.clone()) {
+            return Err(PoolError::ContractNotPaused);
+        }
+        if Self::read_total_outstanding(&env, &token) > 0 {
+            return Err(PoolError::OutstandingBorrows);
+        }
+        let amount = Self::read_pool_balance(&env, &token);
+        if amount > 0 {
+            TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+        }
+        migration_swept(&env, admin, token, to, amount);
+        Ok(amount)
+    }
+
+    /// Move the pool accounting for `old_token` — shares, deposits, provider
+    /// positions and per-token config — onto `new_token`, for when the
+    /// underlying asset is reissued under a new contract. The pool must be
+    /// paused, `old_token` must have no loans outstanding and its balance
+    /// must already have been swept out with `sweep_for_migration`, and the
+    /// pool must hold at least the tracked deposits in `new_token`.
+    pub fn migrate_token(
+        env: Env,
+        old_token: Address,
+        new_token: Address,
+    ) -> Result<(), PoolError> {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        if !Self::is_paused(env.clone()) {
+            return Err(PoolError::ContractNotPaused);
+        }
+        if old_token == new_token
+            || Self::total_shares(&env, &new_token) > 0
+            || Self::total_deposits(&env, &new_token) > 0
+            || Self::read_total_outstanding(&env, &new_token) > 0
+        {
+            return Err(PoolError::TokenInUse);
+        }
+        if Self::read_total_outstanding(&env, &old_token) > 0 {
+            return Err(PoolError::OutstandingBorrows);
+        }
+        if Self::read_pool_balance(&env, &old_token) > 0 {
+            return Err(PoolError::BalanceNotSwept);
+        }
+        if Self::read_pool_balance(&env, &new_token) < Self::total_deposits(&env, &old_token) {
+            return Err(PoolError::InsufficientBalance);
+        }
+
+        let providers = Self::read_provider_list(&env, &old_token);
+        for provider in providers.iter() {
+            Self::move_persistent(
+                &env,
+                DataKey::Shares(provider.clone(), old_token.clone()),
+                DataKey::Shares(provider.clone(), new_token.clone()),
+            );
+            Self::move_persistent(
+                &env,
+                DataKey::DepositTimestamp(provider.clone(), old_token.clone()),
+                DataKey::DepositTimestamp(provider.clone(), new_token.clone()),
+            );
+            Self::move_persistent(
+                &env,
+                DataKey::Commitment(provider.clone(), old_token.clone()),
+                DataKey::Commitment(provider, new_token.clone()),
+            );
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ProviderList(old_token.clone()));
+        Self::write_provider_list(&env, &new_token, &providers);
+
+        for (from, to) in [
+            (
+                DataKey::MaxPoolSize(old_token.clone()),
+                DataKey::MaxPoolSize(new_token.clone()),
+            ),
+            (
+                DataKey::TotalShares(old_token.clone()),
+                DataKey::TotalShares(new_token.clone()),
+            ),
+            (
+                DataKey::TotalDeposits(old_token.clone()),
+                DataKey::TotalDeposits(new_token.clone()),
+            ),
+            (
+                DataKey::DepositorCount(old_token.clone()),
+                DataKey::DepositorCount(new_token.clone()),
+            ),
+            (
+                DataKey::TotalYieldDistributed(old_token.clone()),
+                DataKey::TotalYieldDistributed(new_token.clone()),
+            ),
+            (
+                DataKey::TotalInterestEarned(old_token.clone()),
+                DataKey::TotalInterestEarned(new_token.clone()),
+            ),
+            (
+                DataKey::SupportedToken(old_token.clone()),
+                DataKey::SupportedToken(new_token.clone()),
+            ),
+        ] {
+            Self::move_instance(&env, from, to);
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::TotalOutstanding(old_token.clone()));
+        env.storage()
+            .instance()
+            .remove(&DataKey::LastUtilizationBps(old_token.clone()));
+        env.storage()
+            .instance()
+            .set(&DataKey::MigratedToken(old_token.clone()), &new_token);
+        Self::bump_instance_ttl(&env);
+
+        token_migrated(&env, admin, old_token, new_token);
+        Ok(())
+    }
+
+    /// The token `old_token`'s pool accounting was migrated to, if any.
+    pub fn get_migrated_token(env: Env, old_token: Address) -> Option<Address> {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::MigratedToken(old_token))
+    }
+
+    /// Set the loan manager that reports repaid interest via
+    /// `record_interest`.
+    pub fn set_loan_manager(env: Env, loan_manager: Address) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::LoanManager, &loan_manager);
+        Self::bump_instance_ttl(&env);
+        loan_manager_updated(&env, loan_manager);
+    }
+
+    pub fn get_loan_manager(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::LoanManager)
+    }
+
+    /// When enabled, `withdraw` rejects any provider who has collateral
+    /// posted on a loan in the configured loan manager. Off by default.
+    pub fn set_strict_collateral_lock(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::StrictCollateralLock, &enabled);
+        Self::bump_instance_ttl(&env);
+        strict_collateral_lock_updated(&env, enabled);
+    }
+
+    pub fn get_strict_collateral_lock(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::StrictCollateralLock)
+            .unwrap_or(false)
+    }
+
+    /// When enabled, a withdrawal or loan that leaves a token pool unable
+    /// to cover its tracked deposits pauses the whole pool and emits
+    /// `AutoPause`. Off by default.
+    pub fn set_auto_pause(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::AutoPause, &enabled);
+        Self::bump_instance_ttl(&env);
+        auto_pause_updated(&env, enabled);
+    }
+
+    pub fn get_auto_pause(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoPause)
+            .unwrap_or(false)
+    }
+
+    /// Whether idle balance plus outstanding loans still cover the tracked
+    /// deposits of `token`.
+    pub fn is_solvent(env: Env, token: Address) -> bool {
+        Self::solvent(&env, &token)
+    }
+
+    /// Add `amount` of interest repaid in `token` to the lifetime total.
+    /// Only the configured loan manager may call this; it is a no-op until
+    /// one is set.
+    pub fn record_interest(env: Env, token: Address, amount: i128) -> Result<(), PoolError> {
+        let Some(loan_manager) = Self::get_loan_manager(env.clone()) else {
+            return Ok(());
+        };
+        loan_manager.require_auth();
+        if amount <= 0 {
+            return Err(PoolError::InvalidAmount);
+        }
+
+        Self::adjust_counter(
+            &env,
+            &DataKey::TotalInterestEarned(token.clone()),
+            amount,
+            "total interest earned overflow",
+            "total interest earned underflow",
+        );
+        Self::bump_instance_ttl(&env);
+        interest_recorded(&env, token, amount);
+        Ok(())
+    }
+
+    /// Allow or disallow loans disbursed in `token`. The loan manager checks
+    /// this before lending any token other than its own configured one.
+    pub fn set_supported_token(env: Env, token: Address, supported: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::SupportedToken(token.clone()), &supported);
+        Self::bump_instance_ttl(&env);
+        supported_token_updated(&env, token, supported);
+    }
+
+    pub fn is_supported_token(env: Env, token: Address) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::SupportedToken(token))
+            .unwrap_or(false)
+    }
+
+    pub fn get_min_provider_score(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        Self::min_provider_score(&env)
+    }
+
     pub fn get_max_pool_size(env: Env, token: Address) -> i128 {
         Self::bump_instance_ttl(&env);
         env.storage()
@@ -417,6 +1114,76 @@ impl LendingPool {
             .unwrap_or(0)
     }
 
+    /// Keep `reserve_bps` of tracked deposits idle; the loan manager will not
+    /// lend into the reserve.
+    pub fn set_reserve_ratio(env: Env, reserve_bps: u32) -> Result<(), PoolError> {
+        Self::admin(&env).require_auth();
+        if reserve_bps > 10_000 {
+            return Err(PoolError::InvalidThreshold);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReserveBps, &reserve_bps);
+        Self::bump_instance_ttl(&env);
+        reserve_ratio_updated(&env, reserve_bps);
+        Ok(())
+    }
+
+    pub fn get_reserve_ratio(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ReserveBps)
+            .unwrap_or(0)
+    }
+
+    /// Choose which optional events are published. `Normal` (the default)
+    /// and `Verbose` publish everything the pool emits today; `Minimal`
+    /// keeps only critical events.
+    pub fn set_event_verbosity(env: Env, verbosity: EventVerbosity) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::EventVerbosity, &verbosity);
+        Self::bump_instance_ttl(&env);
+        event_verbosity_updated(&env, verbosity);
+    }
+
+    pub fn get_event_verbosity(env: Env) -> EventVerbosity {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::EventVerbosity)
+            .unwrap_or(EventVerbosity::Normal)
+    }
+
+    /// Deposit and borrow headroom for `token` under the max pool size and
+    /// the reserve ratio.
+    pub fn capacity(env: Env, token: Address) -> Capacity {
+        let reserve = Self::mul_bps(
+            Self::total_deposits(&env, &token),
+            Self::get_reserve_ratio(env.clone()),
+        );
+        Capacity {
+            deposit_headroom: Self::remaining_capacity(env.clone(), token.clone()),
+            borrow_headroom: Self::read_pool_balance(&env, &token)
+                .saturating_sub(reserve)
+                .max(0),
+        }
+    }
+
+    /// Headroom left under the `token` pool's MaxPoolSize cap, i.e. the
+    /// largest deposit that would currently be accepted. Returns `i128::MAX`
+    /// when the pool is uncapped.
+    pub fn remaining_capacity(env: Env, token: Address) -> i128 {
+        let max = Self::get_max_pool_size(env.clone(), token.clone());
+        if max == 0 {
+            return i128::MAX;
+        }
+        max.saturating_sub(Self::total_deposits(&env, &token))
+            .max(0)
+    }
+
     pub fn get_total_deposits(env: Env, token: Address) -> i128 {
         Self::total_deposits(&env, &token)
     }
@@ -425,6 +1192,19 @@ impl LendingPool {
         Self::total_shares(&env, &token)
     }
 
+    /// Page through providers currently holding `token` shares, in the order
+    /// they first deposited. At most 50 are returned per call.
+    pub fn list_providers(env: Env, token: Address, start: u32, limit: u32) -> Vec<Address> {
+        let providers = Self::read_provider_list(&env, &token);
+        let end = start
+            .saturating_add(limit.min(Self::MAX_PROVIDER_PAGE))
+            .min(providers.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        providers.slice(start..end)
+    }
+
     pub fn get_depositor_count(env: Env, token: Address) -> u32 {
         Self::read_depositor_count(&env, &token)
     }
@@ -433,6 +1213,16 @@ impl LendingPool {
         Self::total_yield_distributed(&env, &token)
     }
 
+    /// Lifetime interest borrowers have repaid into the `token` pool. Unlike
+    /// the share price, this never goes down when LPs withdraw.
+    pub fn total_interest_earned(env: Env, token: Address) -> i128 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalInterestEarned(token))
+            .unwrap_or(0)
+    }
+
     pub fn get_withdrawal_cooldown(env: Env) -> u32 {
         Self::withdrawal_cooldown(&env)
     }
@@ -458,19 +1248,23 @@ impl LendingPool {
             return Err(PoolError::InvalidAmount);
         }
 
-        // MaxPoolSize cap uses tracked principal, not pool balance.
-        let max: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxPoolSize(token.clone()))
-            .unwrap_or(0);
-        if max > 0 {
-            let total = Self::total_deposits(&env, &token);
-            if total.checked_add(amount).expect("overflow") > max {
-                return Err(PoolError::PoolSizeExceeded);
+        let min_score = Self::min_provider_score(&env);
+        if min_score > 0 {
+            let nft_contract: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::NftContract)
+                .ok_or(PoolError::NotInitialized)?;
+            if NftClient::new(&env, &nft_contract).current_score(&provider) < min_score {
+                return Err(PoolError::InsufficientProviderScore);
             }
         }
 
+        // MaxPoolSize cap uses tracked principal, not pool balance.
+        if amount > Self::remaining_capacity(env.clone(), token.clone()) {
+            return Err(PoolError::PoolSizeExceeded);
+        }
+
         // Snapshot pool state *before* the transfer so the share price
         // reflects the pre-deposit pool composition.
         let total_assets_before = Self::total_pool_assets(&env, &token);
@@ -492,9 +1286,15 @@ impl LendingPool {
         let existing_shares = Self::read_shares(&env, &provider, &token);
         if existing_shares == 0 {
             let count = Self::read_depositor_count(&env, &token);
-            env.storage()
-                .instance()
-                .set(&DataKey::DepositorCount(token.clone()), &(count + 1));
+            env.storage().instance().set(
+                &DataKey::DepositorCount(token.clone()),
+                &count.checked_add(1).expect("depositor count overflow"),
+            );
+            let mut providers = Self::read_provider_list(&env, &token);
+            if !providers.contains(&provider) {
+                providers.push_back(provider.clone());
+                Self::write_provider_list(&env, &token, &providers);
+            }
         }
 
         let new_shares = existing_shares
@@ -510,19 +1310,20 @@ impl LendingPool {
             .set(&deposit_key, &current_ledger);
         Self::bump_persistent_ttl(&env, &deposit_key);
 
-        let new_total_shares = cur_total_shares
-            .checked_add(shares_to_mint)
-            .expect("total shares overflow");
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalShares(token.clone()), &new_total_shares);
-
-        let new_total_deposits = Self::total_deposits(&env, &token)
-            .checked_add(amount)
-            .expect("total deposits overflow");
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalDeposits(token.clone()), &new_total_deposits);
+        Self::adjust_counter(
+            &env,
+            &DataKey::TotalShares(token.clone()),
+            shares_to_mint,
+            "total shares overflow",
+            "total shares underflow",
+        );
+        Self::adjust_counter(
+            &env,
+            &DataKey::TotalDeposits(token.clone()),
+            amount,
+            "total deposits overflow",
+            "total deposits underflow",
+        );
 
         Self::bump_instance_ttl(&env);
         deposit(
@@ -532,6 +1333,7 @@ impl LendingPool {
             amount,
             shares_to_mint,
         );
+        Self::check_utilization_thresholds(&env, &token);
         Ok(())
     }
 
@@ -581,6 +1383,20 @@ impl LendingPool {
         Self::read_shares(&env, &provider, &token)
     }
 
+    /// `provider`'s fraction of the `token` pool in basis points, rounding
+    /// down. Returns 0 when the pool has no shares.
+    pub fn provider_share_bps(env: Env, provider: Address, token: Address) -> u32 {
+        let total_shares = Self::total_shares(&env, &token);
+        if total_shares <= 0 {
+            return 0;
+        }
+        let shares = Self::read_shares(&env, &provider, &token);
+        shares
+            .checked_mul(10_000)
+            .map(|scaled| scaled / total_shares)
+            .expect("share bps overflow") as u32
+    }
+
     /// Current LP share price scaled by `SHARE_PRICE_SCALE`.
     /// `1_000_000` means 1.0 underlying asset per share.
     /// Price includes proportional value of outstanding loans.
@@ -596,6 +1412,34 @@ impl LendingPool {
             .expect("share price overflow")
     }
 
+    /// Shares `deposit` would mint for `amount` of `token` at the current
+    /// share price. Returns 0 for non-positive amounts.
+    pub fn preview_deposit(env: Env, token: Address, amount: i128) -> i128 {
+        if amount <= 0 {
+            return 0;
+        }
+        Self::calc_shares_to_mint(
+            amount,
+            Self::total_pool_assets(&env, &token),
+            Self::total_shares(&env, &token),
+        )
+    }
+
+    /// Tokens `provider` would receive for burning `shares` of `token` now,
+    /// net of any early exit fee. Returns 0 when there is nothing to redeem.
+    pub fn preview_withdraw(env: Env, provider: Address, token: Address, shares: i128) -> i128 {
+        let total_shares = Self::total_shares(&env, &token);
+        if shares <= 0 || total_shares <= 0 {
+            return 0;
+        }
+        let assets = Self::calc_assets_to_redeem(
+            shares,
+            Self::total_pool_assets(&env, &token),
+            total_shares,
+        );
+        assets - Self::early_exit_fee(&env, &provider, &token, assets)
+    }
+
     /// Burn `shares` LP tokens and receive the proportional underlying assets.
     ///
     /// The redemption value is `shares * pool_balance / total_shares`, which
@@ -610,9 +1454,66 @@ impl LendingPool {
         provider.require_auth();
         Self::assert_not_paused(&env)?;
         Self::assert_withdrawal_cooldown_elapsed(&env, &provider, &token);
+        Self::assert_no_locked_collateral(&env, &provider)?;
         Self::redeem_shares(&env, &provider, &token, shares)
     }
 
+    /// Redeem `(provider, shares)` pairs of `token` in one call, e.g. for a
+    /// custodian managing sub-accounts. Every provider must authorize, and
+    /// each redemption runs the same checks as `withdraw`; if any fails the
+    /// whole batch is reverted. At most 50 entries.
+    pub fn batch_withdraw(
+        env: Env,
+        token: Address,
+        requests: Vec<(Address, i128)>,
+    ) -> Result<(), PoolError> {
+        if requests.is_empty() || requests.len() > Self::MAX_WITHDRAW_BATCH {
+            return Err(PoolError::InvalidAmount);
+        }
+        Self::assert_not_paused(&env)?;
+        for (provider, shares) in requests.iter() {
+            provider.require_auth();
+            Self::assert_withdrawal_cooldown_elapsed(&env, &provider, &token);
+            Self::assert_no_locked_collateral(&env, &provider)?;
+            Self::redeem_shares(&env, &provider, &token, shares)?;
+        }
+        Ok(())
+    }
+
+    /// Commit `provider`'s position in `token` until ledger timestamp
+    /// `until_ts`. Redemptions before then pay the early exit fee. A
+    /// commitment can be extended but not shortened, and ends when the
+    /// position is fully withdrawn.
+    pub fn commit(
+        env: Env,
+        provider: Address,
+        token: Address,
+        until_ts: u64,
+    ) -> Result<(), PoolError> {
+        provider.require_auth();
+        Self::assert_not_paused(&env)?;
+        if Self::read_shares(&env, &provider, &token) <= 0 {
+            return Err(PoolError::InsufficientBalance);
+        }
+        if until_ts <= env.ledger().timestamp()
+            || until_ts < Self::read_commitment(&env, &provider, &token)
+        {
+            return Err(PoolError::InvalidCommitment);
+        }
+
+        let key = DataKey::Commitment(provider.clone(), token.clone());
+        env.storage().persistent().set(&key, &until_ts);
+        Self::bump_persistent_ttl(&env, &key);
+        committed(&env, provider, token, until_ts);
+        Ok(())
+    }
+
+    /// Ledger timestamp until which `provider`'s position in `token` is
+    /// committed, or 0 if it never was.
+    pub fn get_commitment(env: Env, provider: Address, token: Address) -> u64 {
+        Self::read_commitment(&env, &provider, &token)
+    }
+
     pub fn emergency_withdraw(
         env: Env,
         provider: Address,
@@ -666,14 +1567,7 @@ impl LendingPool {
         let total_deposits = Self::total_deposits(&env, &token);
         let total_shares = Self::total_shares(&env, &token);
         let pool_token_balance = Self::read_pool_balance(&env, &token);
-
-        // Utilisation: portion of tracked principal currently out on loan.
-        let utilization_bps = if total_deposits > 0 && pool_token_balance < total_deposits {
-            let borrowed = total_deposits - pool_token_balance;
-            ((borrowed * 10_000) / total_deposits) as u32
-        } else {
-            0
-        };
+        let utilization_bps = Self::utilization_bps(&env, &token);
 
         PoolStats {
             total_deposits,
@@ -685,6 +1579,24 @@ impl LendingPool {
         }
     }
 
+    pub fn pool_status(env: Env, token: Address) -> PoolStatus {
+        let total_deposits = Self::total_deposits(&env, &token);
+        let total_outstanding = Self::read_total_outstanding(&env, &token);
+        let pool_token_balance = Self::read_pool_balance(&env, &token);
+        let utilization_bps = Self::utilization_bps(&env, &token);
+        let (high, _) = Self::water_marks(&env);
+
+        PoolStatus {
+            total_deposits,
+            total_outstanding,
+            pool_token_balance,
+            utilization_bps,
+            above_high_water: high > 0 && utilization_bps >= high,
+            paused: Self::is_paused(env.clone()),
+            solvent: Self::solvent(&env, &token),
+        }
+    }
+
     // ── Admin governance ──────────────────────────────────────────────────
 
     pub fn propose_admin(env: Env, new_admin: Address) {
@@ -735,19 +1647,21 @@ impl LendingPool {
     }
 
     pub fn pause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         env.storage().instance().set(&DataKey::Paused, &true);
         Self::bump_instance_ttl(&env);
 
-        pool_paused(&env);
+        pool_paused(&env, admin);
     }
 
     pub fn unpause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         env.storage().instance().set(&DataKey::Paused, &false);
         Self::bump_instance_ttl(&env);
 
-        pool_unpaused(&env);
+        pool_unpaused(&env, admin);
     }
 
     pub fn is_paused(env: Env) -> bool {
@@ -770,18 +1684,15 @@ impl LendingPool {
             return;
         }
 
-        let key = DataKey::TotalOutstanding(token.clone());
-        let current = Self::read_total_outstanding(&env, &token);
-        let updated = current
-            .checked_add(delta)
-            .expect("total outstanding overflow");
-
-        if updated < 0 {
-            panic!("total outstanding underflow");
-        }
-
-        env.storage().instance().set(&key, &updated);
+        Self::adjust_counter(
+            &env,
+            &DataKey::TotalOutstanding(token.clone()),
+            delta,
+            "total outstanding overflow",
+            "total outstanding underflow",
+        );
         Self::bump_instance_ttl(&env);
+        Self::auto_pause_if_insolvent(&env, &token);
     }
 
     pub fn pool_balance(env: Env, token: Address) -> i128 {
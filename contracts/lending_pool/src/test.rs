@@ -1,4 +1,4 @@
-use crate::{events, LendingPool, LendingPoolClient};
+use crate::{events, EventVerbosity, LendingPool, LendingPoolClient};
 use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
 use soroban_sdk::token::Client as TokenClient;
 use soroban_sdk::token::StellarAssetClient;
@@ -29,6 +29,7 @@ fn test_version_is_initialized() {
 
     pool_client.initialize(&admin);
     assert_eq!(pool_client.version(), 3);
+    assert_eq!(pool_client.storage_version(), 1);
 }
 
 #[test]
@@ -1556,3 +1557,873 @@ fn test_multiple_depositors_share_yield_proportionally_and_total_shares_track_co
     assert_eq!(token_client.balance(&pool_id), 0);
     assert_eq!(pool_client.get_total_shares(&token_id), 0);
 }
+
+// ── Utilisation water marks ──────────────────────────────────────────────────
+
+fn threshold_events(env: &Env) -> soroban_sdk::Vec<(soroban_sdk::Symbol, u32)> {
+    let mut found = soroban_sdk::Vec::new(env);
+    for event in env.events().all().iter() {
+        let topic = soroban_sdk::Symbol::try_from_val(env, &event.1.get(0).unwrap());
+        if topic == Ok(soroban_sdk::Symbol::new(env, "Threshold")) {
+            let direction = soroban_sdk::Symbol::from_val(env, &event.1.get(2).unwrap());
+            found.push_back((direction, u32::from_val(env, &event.2)));
+        }
+    }
+    found
+}
+
+#[test]
+fn test_threshold_event_fires_on_crossing_up_and_back_down() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+    pool_client.set_withdrawal_cooldown(&0);
+    pool_client.set_utilization_water_marks(&8_000, &2_000);
+    assert_eq!(pool_client.get_utilization_water_marks(), (8_000, 2_000));
+
+    let provider = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+    assert_eq!(threshold_events(&env).len(), 0);
+
+    // Borrow 50% — between the marks, nothing fires.
+    token_client.transfer(&pool_id, &borrower, &5_000);
+    pool_client.sync_utilization(&token_id);
+    assert_eq!(threshold_events(&env).len(), 0);
+
+    // Borrow up to 90% — crosses the high mark.
+    token_client.transfer(&pool_id, &borrower, &4_000);
+    pool_client.sync_utilization(&token_id);
+    let events = threshold_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get(0).unwrap(),
+        (soroban_sdk::symbol_short!("up"), 9_000)
+    );
+
+    // Still above the mark on the next tx — debounced.
+    token_client.transfer(&pool_id, &borrower, &500);
+    pool_client.sync_utilization(&token_id);
+    assert_eq!(threshold_events(&env).len(), 0);
+
+    // Repay down to 10% — crosses below the low mark.
+    token_client.transfer(&borrower, &pool_id, &8_500);
+    pool_client.sync_utilization(&token_id);
+    let events = threshold_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get(0).unwrap(),
+        (soroban_sdk::symbol_short!("down"), 1_000)
+    );
+
+    pool_client.sync_utilization(&token_id);
+    assert_eq!(threshold_events(&env).len(), 0);
+}
+
+#[test]
+fn test_threshold_event_disabled_without_water_marks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+
+    let provider = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+
+    token_client.transfer(&pool_id, &Address::generate(&env), &9_500);
+    pool_client.sync_utilization(&token_id);
+    assert_eq!(threshold_events(&env).len(), 0);
+}
+
+#[test]
+fn test_set_utilization_water_marks_rejects_inverted_marks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let result = pool_client.try_set_utilization_water_marks(&2_000, &8_000);
+    assert_eq!(result, Err(Ok(crate::PoolError::InvalidThreshold)));
+}
+
+#[test]
+fn test_remaining_capacity_tracks_cap_headroom() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+    assert_eq!(pool_client.remaining_capacity(&token_id), i128::MAX);
+
+    pool_client.set_max_pool_size(&token_id, &5_000);
+    assert_eq!(pool_client.remaining_capacity(&token_id), 5_000);
+
+    let provider = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+
+    pool_client.deposit(&provider, &token_id, &3_000);
+    assert_eq!(pool_client.remaining_capacity(&token_id), 2_000);
+
+    let result = pool_client.try_deposit(&provider, &token_id, &2_001);
+    assert_eq!(result, Err(Ok(crate::PoolError::PoolSizeExceeded)));
+
+    // A deposit exactly filling the headroom is accepted.
+    pool_client.deposit(&provider, &token_id, &2_000);
+    assert_eq!(pool_client.remaining_capacity(&token_id), 0);
+    assert_eq!(pool_client.get_total_deposits(&token_id), 5_000);
+}
+
+#[test]
+fn test_provider_score_gate_only_applies_when_enabled() {
+    use remittance_nft::{RemittanceNFT, RemittanceNFTClient};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+
+    let nft_id = env.register(RemittanceNFT, ());
+    let nft_client = RemittanceNFTClient::new(&env, &nft_id);
+    nft_client.initialize(&admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let low = Address::generate(&env);
+    let high = Address::generate(&env);
+    let no_nft = Address::generate(&env);
+    let uri = soroban_sdk::String::from_str(&env, "ipfs://QmTest");
+    nft_client.mint(
+        &low,
+        &450,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &uri,
+        &None,
+    );
+    nft_client.mint(
+        &high,
+        &700,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &uri,
+        &None,
+    );
+    for provider in [&low, &high, &no_nft] {
+        stellar_asset_client.mint(provider, &2_000);
+    }
+
+    // Gate off: anyone can deposit.
+    assert_eq!(pool_client.get_min_provider_score(), 0);
+    pool_client.deposit(&no_nft, &token_id, &1_000);
+
+    pool_client.set_provider_score_gate(&nft_id, &600);
+    assert_eq!(pool_client.get_min_provider_score(), 600);
+    assert_eq!(
+        pool_client.try_deposit(&low, &token_id, &1_000),
+        Err(Ok(crate::PoolError::InsufficientProviderScore))
+    );
+    assert_eq!(
+        pool_client.try_deposit(&no_nft, &token_id, &1_000),
+        Err(Ok(crate::PoolError::InsufficientProviderScore))
+    );
+    pool_client.deposit(&high, &token_id, &1_000);
+
+    pool_client.set_provider_score_gate(&nft_id, &0);
+    pool_client.deposit(&low, &token_id, &1_000);
+    assert_eq!(pool_client.get_total_deposits(&token_id), 3_000);
+}
+
+#[test]
+fn test_provider_share_bps_tracks_ownership() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &token_admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+
+    let major = Address::generate(&env);
+    let minor = Address::generate(&env);
+    assert_eq!(pool_client.provider_share_bps(&major, &token_id), 0);
+
+    stellar_asset_client.mint(&major, &3_000);
+    stellar_asset_client.mint(&minor, &1_000);
+    pool_client.deposit(&major, &token_id, &3_000);
+    assert_eq!(pool_client.provider_share_bps(&major, &token_id), 10_000);
+
+    pool_client.deposit(&minor, &token_id, &1_000);
+    assert_eq!(pool_client.provider_share_bps(&major, &token_id), 7_500);
+    assert_eq!(pool_client.provider_share_bps(&minor, &token_id), 2_500);
+    assert_eq!(
+        pool_client.provider_share_bps(&Address::generate(&env), &token_id),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "total deposits overflow")]
+fn test_total_deposits_overflow_panics_with_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    env.as_contract(&pool_id, || {
+        env.storage().instance().set(
+            &crate::DataKey::TotalDeposits(token_id.clone()),
+            &(i128::MAX - 10),
+        );
+    });
+
+    let provider = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &100);
+    pool_client.deposit(&provider, &token_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "total outstanding underflow")]
+fn test_total_outstanding_underflow_panics_with_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, _stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    pool_client.adjust_outstanding(&token_id, &i128::MAX);
+    assert_eq!(pool_client.get_total_outstanding(&token_id), i128::MAX);
+    pool_client.adjust_outstanding(&token_id, &-i128::MAX);
+    pool_client.adjust_outstanding(&token_id, &-1);
+}
+
+#[test]
+fn test_pool_status_bundles_health_fields() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+    pool_client.set_utilization_water_marks(&8_000, &2_000);
+
+    let provider = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+
+    // Lend out 9_000 and record it as outstanding.
+    token_client.transfer(&pool_id, &borrower, &9_000);
+    pool_client.adjust_outstanding(&token_id, &9_000);
+
+    let status = pool_client.pool_status(&token_id);
+    let stats = pool_client.get_pool_stats(&token_id);
+    assert_eq!(
+        status.total_deposits,
+        pool_client.get_total_deposits(&token_id)
+    );
+    assert_eq!(
+        status.total_outstanding,
+        pool_client.get_total_outstanding(&token_id)
+    );
+    assert_eq!(
+        status.pool_token_balance,
+        pool_client.pool_balance(&token_id)
+    );
+    assert_eq!(status.utilization_bps, stats.utilization_bps);
+    assert_eq!(status.utilization_bps, 9_000);
+    assert!(status.above_high_water);
+    assert_eq!(status.paused, pool_client.is_paused());
+    assert!(status.solvent);
+
+    // Writing the loan down without the funds returning breaks solvency.
+    pool_client.adjust_outstanding(&token_id, &-9_000);
+    pool_client.pause();
+    let status = pool_client.pool_status(&token_id);
+    assert!(!status.solvent);
+    assert!(status.paused);
+}
+
+#[test]
+#[should_panic(expected = "not initialized")]
+fn test_deposit_before_initialize_panics_uniformly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+
+    let provider = Address::generate(&env);
+    let token = Address::generate(&env);
+    pool_client.deposit(&provider, &token, &100);
+}
+
+#[test]
+fn test_supported_token_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let token = Address::generate(&env);
+    assert!(!pool_client.is_supported_token(&token));
+    pool_client.set_supported_token(&token, &true);
+    assert!(pool_client.is_supported_token(&token));
+    pool_client.set_supported_token(&token, &false);
+    assert!(!pool_client.is_supported_token(&token));
+}
+
+#[test]
+fn test_pause_and_unpause_events_carry_admin_and_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_234);
+
+    for (paused, name) in [(true, "PoolPaused"), (false, "PoolUnpaused")] {
+        if paused {
+            pool_client.pause();
+        } else {
+            pool_client.unpause();
+        }
+        let events = env.events().all();
+        let event = events.get(events.len() - 1).unwrap();
+        assert_eq!(
+            soroban_sdk::Symbol::from_val(&env, &event.1.get(0).unwrap()),
+            soroban_sdk::Symbol::new(&env, name)
+        );
+        assert_eq!(Address::from_val(&env, &event.1.get(1).unwrap()), admin);
+        assert_eq!(u64::from_val(&env, &event.2), 1_234);
+    }
+}
+
+fn committed_pool_setup(
+    env: &Env,
+) -> (
+    LendingPoolClient<'_>,
+    Address,
+    TokenClient<'_>,
+    Address,
+    Address,
+) {
+    let token_admin = Address::generate(env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(env, &token_admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(env, &pool_id);
+    pool_client.initialize(&token_admin);
+    pool_client.set_withdrawal_cooldown(&0);
+
+    let treasury = Address::generate(env);
+    pool_client.set_early_exit_fee(&500, &treasury);
+
+    let provider = Address::generate(env);
+    stellar_asset_client.mint(&provider, &1_000);
+    pool_client.deposit(&provider, &token_id, &1_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    pool_client.commit(&provider, &token_id, &5_000);
+    assert_eq!(pool_client.get_commitment(&provider, &token_id), 5_000);
+    (pool_client, token_id, token_client, provider, treasury)
+}
+
+#[test]
+fn test_early_withdrawal_of_committed_liquidity_pays_fee_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (pool_client, token_id, token_client, provider, treasury) = committed_pool_setup(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 4_999);
+    pool_client.withdraw(&provider, &token_id, &1_000);
+
+    assert_eq!(token_client.balance(&treasury), 50);
+    assert_eq!(token_client.balance(&provider), 950);
+    assert_eq!(pool_client.get_commitment(&provider, &token_id), 0);
+}
+
+#[test]
+fn test_withdrawal_after_commitment_lapses_is_fee_free() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (pool_client, token_id, token_client, provider, treasury) = committed_pool_setup(&env);
+
+    // Commitments can only be extended.
+    let result = pool_client.try_commit(&provider, &token_id, &4_000);
+    assert_eq!(result, Err(Ok(crate::PoolError::InvalidCommitment)));
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    pool_client.withdraw(&provider, &token_id, &1_000);
+
+    assert_eq!(token_client.balance(&treasury), 0);
+    assert_eq!(token_client.balance(&provider), 1_000);
+}
+
+#[test]
+fn test_recover_foreign_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let (stray_id, stray_admin, stray_client) = create_token_contract(&env, &admin);
+    stray_admin.mint(&pool_id, &300);
+    let to = Address::generate(&env);
+
+    pool_client.recover_token(&stray_id, &to, &300);
+    assert_eq!(stray_client.balance(&to), 300);
+    assert_eq!(stray_client.balance(&pool_id), 0);
+}
+
+#[test]
+fn test_recover_token_rejects_pool_accounting_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let provider = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &1_000);
+    pool_client.deposit(&provider, &token_id, &1_000);
+
+    let result = pool_client.try_recover_token(&token_id, &admin, &1_000);
+    assert_eq!(result, Err(Ok(crate::PoolError::TokenInUse)));
+
+    // A token allowlisted for lending is off-limits even before deposits.
+    let (listed_id, listed_admin, _) = create_token_contract(&env, &admin);
+    listed_admin.mint(&pool_id, &10);
+    pool_client.set_supported_token(&listed_id, &true);
+    let result = pool_client.try_recover_token(&listed_id, &admin, &10);
+    assert_eq!(result, Err(Ok(crate::PoolError::TokenInUse)));
+}
+
+#[test]
+fn test_record_interest_accumulates_for_loan_manager() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, _stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    // No loan manager configured: reports are ignored.
+    pool_client.record_interest(&token_id, &50);
+    assert_eq!(pool_client.total_interest_earned(&token_id), 0);
+
+    let loan_manager = Address::generate(&env);
+    pool_client.set_loan_manager(&loan_manager);
+    pool_client.record_interest(&token_id, &50);
+    pool_client.record_interest(&token_id, &25);
+    assert_eq!(pool_client.total_interest_earned(&token_id), 75);
+    assert_eq!(
+        pool_client.try_record_interest(&token_id, &0),
+        Err(Ok(crate::PoolError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_list_providers_tracks_deposits_and_full_withdrawals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, _token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+    pool_client.set_withdrawal_cooldown(&0);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    stellar_asset_client.mint(&alice, &5_000);
+    stellar_asset_client.mint(&bob, &5_000);
+    assert_eq!(pool_client.list_providers(&token_id, &0, &10).len(), 0);
+
+    pool_client.deposit(&alice, &token_id, &1_000);
+    pool_client.deposit(&bob, &token_id, &1_000);
+    // A repeat deposit does not duplicate the entry.
+    pool_client.deposit(&alice, &token_id, &500);
+    assert_eq!(
+        pool_client.list_providers(&token_id, &0, &10),
+        soroban_sdk::vec![&env, alice.clone(), bob.clone()]
+    );
+    assert_eq!(
+        pool_client.list_providers(&token_id, &1, &10),
+        soroban_sdk::vec![&env, bob.clone()]
+    );
+
+    // A partial withdrawal keeps the provider listed; a full one removes it.
+    pool_client.withdraw(&alice, &token_id, &500);
+    assert_eq!(pool_client.list_providers(&token_id, &0, &10).len(), 2);
+    pool_client.withdraw(&alice, &token_id, &1_000);
+    assert_eq!(
+        pool_client.list_providers(&token_id, &0, &10),
+        soroban_sdk::vec![&env, bob]
+    );
+    assert_eq!(pool_client.list_providers(&token_id, &5, &10).len(), 0);
+}
+
+#[test]
+fn test_capacity_reports_deposit_and_borrow_headroom() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let provider = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &20_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+
+    // No cap, no reserve: everything idle can be lent.
+    let capacity = pool_client.capacity(&token_id);
+    assert_eq!(capacity.deposit_headroom, i128::MAX);
+    assert_eq!(capacity.borrow_headroom, 10_000);
+
+    // TVL cap only limits deposits.
+    pool_client.set_max_pool_size(&token_id, &15_000);
+    let capacity = pool_client.capacity(&token_id);
+    assert_eq!(capacity.deposit_headroom, 5_000);
+    assert_eq!(capacity.borrow_headroom, 10_000);
+
+    // A 20% reserve holds back 2_000 of the 10_000 deposited.
+    pool_client.set_reserve_ratio(&2_000);
+    assert_eq!(pool_client.capacity(&token_id).borrow_headroom, 8_000);
+
+    // Once lending has drawn the idle balance below the reserve, nothing
+    // more can be borrowed.
+    token_client.transfer(&pool_id, &Address::generate(&env), &8_500);
+    let capacity = pool_client.capacity(&token_id);
+    assert_eq!(capacity.deposit_headroom, 5_000);
+    assert_eq!(capacity.borrow_headroom, 0);
+
+    assert_eq!(
+        pool_client.try_set_reserve_ratio(&10_001),
+        Err(Ok(crate::PoolError::InvalidThreshold))
+    );
+}
+
+#[test]
+fn test_previews_match_executed_deposit_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+    pool_client.set_withdrawal_cooldown(&0);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    stellar_asset_client.mint(&alice, &10_000);
+    stellar_asset_client.mint(&bob, &10_000);
+
+    assert_eq!(pool_client.preview_deposit(&token_id, &3_000), 3_000);
+    pool_client.deposit(&alice, &token_id, &3_000);
+
+    // Repaid interest lifts the share price; later deposits mint fewer shares.
+    stellar_asset_client.mint(&pool_id, &1_000);
+    let expected_shares = pool_client.preview_deposit(&token_id, &2_000);
+    assert_eq!(expected_shares, 1_500);
+    pool_client.deposit(&bob, &token_id, &2_000);
+    assert_eq!(pool_client.get_shares(&bob, &token_id), expected_shares);
+
+    let expected_assets = pool_client.preview_withdraw(&alice, &token_id, &1_200);
+    assert_eq!(expected_assets, 1_600);
+    let before = token_client.balance(&alice);
+    pool_client.withdraw(&alice, &token_id, &1_200);
+    assert_eq!(token_client.balance(&alice) - before, expected_assets);
+
+    // The preview reflects the early exit fee on committed positions.
+    let treasury = Address::generate(&env);
+    pool_client.set_early_exit_fee(&1_000, &treasury);
+    pool_client.commit(&bob, &token_id, &(env.ledger().timestamp() + 100));
+    let expected_assets = pool_client.preview_withdraw(&bob, &token_id, &1_500);
+    let before = token_client.balance(&bob);
+    pool_client.withdraw(&bob, &token_id, &1_500);
+    assert_eq!(token_client.balance(&bob) - before, expected_assets);
+    assert_eq!(expected_assets, 1_800);
+
+    assert_eq!(pool_client.preview_deposit(&token_id, &0), 0);
+    assert_eq!(pool_client.preview_withdraw(&bob, &token_id, &0), 0);
+}
+
+#[test]
+fn test_minimal_event_verbosity_drops_threshold_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+    pool_client.set_withdrawal_cooldown(&0);
+    pool_client.set_utilization_water_marks(&8_000, &2_000);
+    assert_eq!(pool_client.get_event_verbosity(), EventVerbosity::Normal);
+
+    pool_client.set_event_verbosity(&EventVerbosity::Minimal);
+    assert_eq!(pool_client.get_event_verbosity(), EventVerbosity::Minimal);
+
+    // Critical events still fire.
+    let provider = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+    let events = env.events().all();
+    let event = events.get(events.len() - 1).unwrap();
+    let topic = soroban_sdk::Symbol::from_val(&env, &event.1.get(0).unwrap());
+    assert_eq!(topic, soroban_sdk::Symbol::new(&env, "Deposit"));
+
+    token_client.transfer(&pool_id, &borrower, &9_000);
+    pool_client.sync_utilization(&token_id);
+    assert_eq!(threshold_events(&env).len(), 0);
+
+    pool_client.set_event_verbosity(&EventVerbosity::Verbose);
+    token_client.transfer(&borrower, &pool_id, &8_000);
+    pool_client.sync_utilization(&token_id);
+    let events = threshold_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get(0).unwrap(),
+        (soroban_sdk::symbol_short!("down"), 1_000)
+    );
+}
+
+#[test]
+fn test_batch_withdraw_redeems_for_each_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+    pool_client.set_withdrawal_cooldown(&0);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    stellar_asset_client.mint(&alice, &3_000);
+    stellar_asset_client.mint(&bob, &2_000);
+    pool_client.deposit(&alice, &token_id, &3_000);
+    pool_client.deposit(&bob, &token_id, &2_000);
+
+    pool_client.batch_withdraw(
+        &token_id,
+        &soroban_sdk::vec![&env, (alice.clone(), 1_000i128), (bob.clone(), 2_000i128)],
+    );
+    assert_eq!(token_client.balance(&alice), 1_000);
+    assert_eq!(token_client.balance(&bob), 2_000);
+    assert_eq!(pool_client.get_shares(&alice, &token_id), 2_000);
+    assert_eq!(pool_client.get_shares(&bob, &token_id), 0);
+    assert_eq!(pool_client.get_total_shares(&token_id), 2_000);
+}
+
+#[test]
+fn test_batch_withdraw_reverts_when_one_provider_lacks_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &token_admin);
+
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&token_admin);
+    pool_client.set_withdrawal_cooldown(&0);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    stellar_asset_client.mint(&alice, &3_000);
+    stellar_asset_client.mint(&bob, &2_000);
+    pool_client.deposit(&alice, &token_id, &3_000);
+    pool_client.deposit(&bob, &token_id, &2_000);
+
+    assert_eq!(
+        pool_client.try_batch_withdraw(
+            &token_id,
+            &soroban_sdk::vec![&env, (alice.clone(), 1_000i128), (bob.clone(), 2_500i128)],
+        ),
+        Err(Ok(crate::PoolError::InsufficientBalance))
+    );
+    // Alice's redemption earlier in the batch was rolled back too.
+    assert_eq!(token_client.balance(&alice), 0);
+    assert_eq!(pool_client.get_shares(&alice, &token_id), 3_000);
+    assert_eq!(pool_client.get_total_shares(&token_id), 5_000);
+    assert_eq!(token_client.balance(&pool_id), 5_000);
+
+    assert_eq!(
+        pool_client.try_batch_withdraw(&token_id, &soroban_sdk::vec![&env]),
+        Err(Ok(crate::PoolError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_migrate_token_moves_pool_accounting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (old_id, old_admin, old_client) = create_token_contract(&env, &admin);
+    let (new_id, new_admin, new_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+    pool_client.set_withdrawal_cooldown(&0);
+    pool_client.set_supported_token(&old_id, &true);
+
+    let provider = Address::generate(&env);
+    old_admin.mint(&provider, &1_000);
+    pool_client.deposit(&provider, &old_id, &1_000);
+    pool_client.adjust_outstanding(&old_id, &400);
+
+    // Only while paused.
+    assert_eq!(
+        pool_client.try_migrate_token(&old_id, &new_id),
+        Err(Ok(crate::PoolError::ContractNotPaused))
+    );
+    assert_eq!(
+        pool_client.try_sweep_for_migration(&old_id, &admin),
+        Err(Ok(crate::PoolError::ContractNotPaused))
+    );
+    pool_client.pause();
+
+    // Not while the old token is lent out.
+    assert_eq!(
+        pool_client.try_migrate_token(&old_id, &new_id),
+        Err(Ok(crate::PoolError::OutstandingBorrows))
+    );
+    assert_eq!(
+        pool_client.try_sweep_for_migration(&old_id, &admin),
+        Err(Ok(crate::PoolError::OutstandingBorrows))
+    );
+    pool_client.adjust_outstanding(&old_id, &-400);
+
+    // Not until the old balance has been swept. `recover_token` refuses a
+    // token in use, so the sweep goes through `sweep_for_migration`.
+    assert_eq!(
+        pool_client.try_migrate_token(&old_id, &new_id),
+        Err(Ok(crate::PoolError::BalanceNotSwept))
+    );
+    assert_eq!(
+        pool_client.try_recover_token(&old_id, &admin, &1_000),
+        Err(Ok(crate::PoolError::TokenInUse))
+    );
+    assert_eq!(pool_client.sweep_for_migration(&old_id, &admin), 1_000);
+    assert_eq!(old_client.balance(&admin), 1_000);
+
+    // Not until the pool holds the deposits in the new token.
+    assert_eq!(
+        pool_client.try_migrate_token(&old_id, &new_id),
+        Err(Ok(crate::PoolError::InsufficientBalance))
+    );
+    new_admin.mint(&pool_id, &1_000);
+
+    pool_client.migrate_token(&old_id, &new_id);
+    assert_eq!(
+        pool_client.get_migrated_token(&old_id),
+        Some(new_id.clone())
+    );
+    assert_eq!(pool_client.get_shares(&provider, &old_id), 0);
+    assert_eq!(pool_client.get_total_shares(&old_id), 0);
+    assert!(!pool_client.is_supported_token(&old_id));
+    assert_eq!(pool_client.get_shares(&provider, &new_id), 1_000);
+    assert_eq!(pool_client.get_total_deposits(&new_id), 1_000);
+    assert_eq!(pool_client.get_depositor_count(&new_id), 1);
+    assert!(pool_client.is_supported_token(&new_id));
+
+    pool_client.unpause();
+    pool_client.withdraw(&provider, &new_id, &1_000);
+    assert_eq!(new_client.balance(&provider), 1_000);
+}
+
+#[test]
+fn test_auto_pause_trips_on_insolvency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_id, stellar_asset_client, token_client) = create_token_contract(&env, &admin);
+    let pool_id = env.register(LendingPool, ());
+    let pool_client = LendingPoolClient::new(&env, &pool_id);
+    pool_client.initialize(&admin);
+
+    let provider = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    stellar_asset_client.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &10_000);
+    token_client.transfer(&pool_id, &borrower, &4_000);
+    pool_client.adjust_outstanding(&token_id, &4_000);
+
+    // Off by default: writing part of the loan down leaves the pool running.
+    assert!(!pool_client.get_auto_pause());
+    pool_client.adjust_outstanding(&token_id, &-1_000);
+    assert!(!pool_client.is_solvent(&token_id));
+    assert!(!pool_client.is_paused());
+    pool_client.adjust_outstanding(&token_id, &1_000);
+    assert!(pool_client.is_solvent(&token_id));
+
+    pool_client.set_auto_pause(&true);
+    pool_client.adjust_outstanding(&token_id, &-1_000);
+    let events = env.events().all();
+    let last = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &last.1.get(0).unwrap()),
+        soroban_sdk::Symbol::new(&env, "AutoPause")
+    );
+    assert_eq!(Address::from_val(&env, &last.1.get(1).unwrap()), token_id);
+    assert!(pool_client.is_paused());
+    assert_eq!(
+        pool_client.try_deposit(&provider, &token_id, &1),
+        Err(Ok(crate::PoolError::ContractPaused))
+    );
+}
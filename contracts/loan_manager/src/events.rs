@@ -1,8 +1,33 @@
-use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{
+    events::Topics, symbol_short, vec, Address, Env, IntoVal, Map, String, Symbol, Val, Vec,
+};
+
+use crate::{DataKey, LoanStatus, RoundingMode};
+
+/// Publish an event, prefixing its topics with the deployment's event
+/// namespace when one was configured at initialize.
+pub fn publish<T, D>(env: &Env, topics: T, data: D)
+where
+    T: Topics,
+    D: IntoVal<Env, Val>,
+{
+    match env
+        .storage()
+        .instance()
+        .get::<_, Symbol>(&DataKey::EventNamespace)
+    {
+        Some(namespace) => {
+            let mut namespaced: Vec<Val> = vec![env, namespace.into_val(env)];
+            namespaced.append(&topics.into_val(env));
+            env.events().publish(namespaced, data);
+        }
+        None => env.events().publish(topics, data),
+    }
+}
 
 pub fn loan_requested(env: &Env, loan_id: u32, borrower: Address, amount: i128) {
     let topics = (Symbol::new(env, "LoanRequested"), loan_id, borrower);
-    env.events().publish(topics, amount);
+    publish(env, topics, amount);
 }
 
 pub fn loan_approved(
@@ -13,8 +38,34 @@ pub fn loan_approved(
     term_ledgers: u32,
 ) {
     let topics = (Symbol::new(env, "LoanApproved"), loan_id, borrower);
-    env.events()
-        .publish(topics, (interest_rate_bps, term_ledgers));
+    publish(env, topics, (interest_rate_bps, term_ledgers));
+}
+
+/// Final terms locked in when principal leaves the pool. `due_date` is a
+/// ledger sequence number.
+#[allow(clippy::too_many_arguments)]
+pub fn loan_disbursed(
+    env: &Env,
+    loan_id: u32,
+    borrower: Address,
+    recipient: Address,
+    principal: i128,
+    interest_rate_bps: u32,
+    due_date: u32,
+    collateral: i128,
+) {
+    let topics = (Symbol::new(env, "LoanDisbursed"), loan_id, borrower);
+    publish(
+        env,
+        topics,
+        (
+            recipient,
+            principal,
+            interest_rate_bps,
+            due_date,
+            collateral,
+        ),
+    );
 }
 
 pub fn loan_refinanced(
@@ -25,7 +76,12 @@ pub fn loan_refinanced(
     new_term: u32,
 ) {
     let topics = (Symbol::new(env, "LoanRefinanced"), loan_id, borrower);
-    env.events().publish(topics, (new_amount, new_term));
+    publish(env, topics, (new_amount, new_term));
+}
+
+pub fn loan_repriced(env: &Env, loan_id: u32, old_rate_bps: u32, new_rate_bps: u32) {
+    let topics = (Symbol::new(env, "LoanRepriced"), loan_id);
+    publish(env, topics, (old_rate_bps, new_rate_bps));
 }
 
 pub fn loan_extended(
@@ -37,128 +93,217 @@ pub fn loan_extended(
     extension_count: u32,
 ) {
     let topics = (Symbol::new(env, "LoanExtended"), loan_id, borrower);
-    env.events()
-        .publish(topics, (new_due_ledger, fee_amount, extension_count));
+    publish(env, topics, (new_due_ledger, fee_amount, extension_count));
 }
 
-pub fn loan_repaid(env: &Env, borrower: Address, loan_id: u32, amount: i128) {
+/// Carries the borrower's score change from this repayment and the score it
+/// left them at, so indexers need not correlate the NFT's score events.
+pub fn loan_repaid(
+    env: &Env,
+    borrower: Address,
+    loan_id: u32,
+    amount: i128,
+    score_delta: i32,
+    new_score: u32,
+) {
     let topics = (Symbol::new(env, "LoanRepaid"), borrower, loan_id);
-    env.events().publish(topics, amount);
+    publish(env, topics, (amount, score_delta, new_score));
 }
 
 pub fn loan_cancelled(env: &Env, borrower: Address, loan_id: u32) {
     let topics = (Symbol::new(env, "LoanCancelled"), borrower);
-    env.events().publish(topics, loan_id);
+    publish(env, topics, loan_id);
 }
 
 pub fn loan_rejected(env: &Env, loan_id: u32, reason: String) {
     let topics = (Symbol::new(env, "LoanRejected"), loan_id);
-    env.events().publish(topics, reason);
+    publish(env, topics, reason);
 }
 
 pub fn late_fee_charged(env: &Env, loan_id: u32, fee_amount: i128) {
     let topics = (Symbol::new(env, "LateFeeCharged"), loan_id);
-    env.events().publish(topics, fee_amount);
+    publish(env, topics, fee_amount);
 }
 
 pub fn min_score_updated(env: &Env, admin: Address, old_score: u32, new_score: u32) {
     let topics = (Symbol::new(env, "MinScoreUpdated"), admin);
-    env.events().publish(topics, (old_score, new_score));
+    publish(env, topics, (old_score, new_score));
 }
 
-pub fn paused(env: &Env, paused_at_ledger: u32) {
-    let topics = (Symbol::new(env, "Paused"),);
-    env.events().publish(topics, paused_at_ledger);
+pub fn paused(env: &Env, admin: Address, paused_at_ledger: u32) {
+    let topics = (Symbol::new(env, "Paused"), admin);
+    publish(env, topics, (paused_at_ledger, env.ledger().timestamp()));
 }
 
-pub fn unpaused(env: &Env, unpaused_at_ledger: u32) {
-    let topics = (Symbol::new(env, "Unpaused"),);
-    env.events().publish(topics, unpaused_at_ledger);
+pub fn unpaused(env: &Env, admin: Address, unpaused_at_ledger: u32) {
+    let topics = (Symbol::new(env, "Unpaused"), admin);
+    publish(env, topics, (unpaused_at_ledger, env.ledger().timestamp()));
 }
 
 // pub fn min_score_updated(env: &Env, old_score: u32, new_score: u32) {
 //     let topics = (Symbol::new(env, "MinScoreUpdated"),);
-//     env.events().publish(topics, (old_score, new_score));
+//     publish(env, topics, (old_score, new_score));
 // }
 
 pub fn interest_rate_updated(env: &Env, old_rate: u32, new_rate: u32) {
     let topics = (Symbol::new(env, "InterestRateUpdated"),);
-    env.events().publish(topics, (old_rate, new_rate));
+    publish(env, topics, (old_rate, new_rate));
 }
 
 pub fn default_term_updated(env: &Env, old_term: u32, new_term: u32) {
     let topics = (Symbol::new(env, "DefaultTermUpdated"),);
-    env.events().publish(topics, (old_term, new_term));
+    publish(env, topics, (old_term, new_term));
 }
 
 pub fn loan_defaulted(env: &Env, loan_id: u32, borrower: Address) {
     let topics = (Symbol::new(env, "LoanDefaulted"), loan_id);
-    env.events().publish(topics, borrower);
+    publish(env, topics, borrower);
 }
 
 pub fn term_limits_updated(env: &Env, min_term: u32, max_term: u32) {
     let topics = (Symbol::new(env, "TermLimitsUpdated"),);
-    env.events().publish(topics, (min_term, max_term));
+    publish(env, topics, (min_term, max_term));
+}
+
+pub fn rate_discounts_updated(env: &Env, discounts: Map<u32, u32>) {
+    let topics = (Symbol::new(env, "RateDiscountsUpdated"),);
+    publish(env, topics, discounts);
 }
 
 pub fn rate_oracle_updated(env: &Env, old_oracle: Option<Address>, new_oracle: Address) {
     let topics = (Symbol::new(env, "RateOracleUpdated"),);
-    env.events().publish(topics, (old_oracle, new_oracle));
+    publish(env, topics, (old_oracle, new_oracle));
 }
 
 pub fn collateral_returned(env: &Env, borrower: Address, loan_id: u32, amount: i128) {
     let topics = (Symbol::new(env, "CollateralReturned"), borrower, loan_id);
-    env.events().publish(topics, amount);
+    publish(env, topics, amount);
 }
 
 pub fn collateral_deposited(env: &Env, borrower: Address, loan_id: u32, amount: i128) {
     let topics = (Symbol::new(env, "CollateralDeposited"), borrower, loan_id);
-    env.events().publish(topics, amount);
+    publish(env, topics, amount);
 }
 
 pub fn collateral_released(env: &Env, borrower: Address, loan_id: u32) {
     let topics = (Symbol::new(env, "CollateralReleased"), borrower, loan_id);
-    env.events().publish(topics, ());
+    publish(env, topics, ());
 }
 
 pub fn late_fee_rate_updated(env: &Env, admin: Address, old_rate: u32, new_rate: u32) {
     let topics = (Symbol::new(env, "LateFeeRateUpdated"), admin);
-    env.events().publish(topics, (old_rate, new_rate));
+    publish(env, topics, (old_rate, new_rate));
+}
+
+pub fn compound_period_updated(env: &Env, admin: Address, old_ledgers: u32, new_ledgers: u32) {
+    let topics = (Symbol::new(env, "CompoundPeriodUpdated"), admin);
+    publish(env, topics, (old_ledgers, new_ledgers));
 }
 
 pub fn grace_period_updated(env: &Env, admin: Address, old_ledgers: u32, new_ledgers: u32) {
     let topics = (Symbol::new(env, "GracePeriodUpdated"), admin);
-    env.events().publish(topics, (old_ledgers, new_ledgers));
+    publish(env, topics, (old_ledgers, new_ledgers));
 }
 
 pub fn default_window_updated(env: &Env, admin: Address, old_ledgers: u32, new_ledgers: u32) {
     let topics = (Symbol::new(env, "DefaultWindowUpdated"), admin);
-    env.events().publish(topics, (old_ledgers, new_ledgers));
+    publish(env, topics, (old_ledgers, new_ledgers));
 }
 
 pub fn max_loan_amount_updated(env: &Env, admin: Address, old_amount: i128, new_amount: i128) {
     let topics = (Symbol::new(env, "MaxLoanAmountUpdated"), admin);
-    env.events().publish(topics, (old_amount, new_amount));
+    publish(env, topics, (old_amount, new_amount));
 }
 
 pub fn min_repayment_updated(env: &Env, admin: Address, old_amount: i128, new_amount: i128) {
     let topics = (Symbol::new(env, "MinRepaymentUpdated"), admin);
-    env.events().publish(topics, (old_amount, new_amount));
+    publish(env, topics, (old_amount, new_amount));
+}
+
+pub fn refund_overpayment_updated(env: &Env, enabled: bool) {
+    let topics = (Symbol::new(env, "RefundOverpaymentUpdated"),);
+    publish(env, topics, enabled);
+}
+
+pub fn threshold_inclusive_updated(env: &Env, inclusive: bool) {
+    let topics = (Symbol::new(env, "ThresholdInclusiveUpdated"),);
+    publish(env, topics, inclusive);
+}
+
+pub fn default_ban_updated(env: &Env, admin: Address, max_defaults: u32, ban_ledgers: u32) {
+    let topics = (Symbol::new(env, "DefaultBanUpdated"), admin);
+    publish(env, topics, (max_defaults, ban_ledgers));
+}
+
+pub fn disbursement_delay_updated(env: &Env, admin: Address, delay_secs: u64) {
+    let topics = (Symbol::new(env, "DisbursementDelayUpdated"), admin);
+    publish(env, topics, delay_secs);
+}
+
+pub fn borrow_blackout_updated(env: &Env, admin: Address, from_ts: u64, to_ts: u64) {
+    let topics = (Symbol::new(env, "BorrowBlackoutUpdated"), admin);
+    publish(env, topics, (from_ts, to_ts));
+}
+
+pub fn disbursement_claim_window_updated(env: &Env, admin: Address, window_secs: u64) {
+    let topics = (Symbol::new(env, "DisbursementClaimWindowUpdated"), admin);
+    publish(env, topics, window_secs);
+}
+
+pub fn approval_expired(env: &Env, loan_id: u32, borrower: Address) {
+    let topics = (Symbol::new(env, "ApprovalExpired"), loan_id, borrower);
+    publish(env, topics, ());
+}
+
+pub fn disbursement_escrowed(
+    env: &Env,
+    loan_id: u32,
+    borrower: Address,
+    amount: i128,
+    release_at: u64,
+) {
+    let topics = (Symbol::new(env, "DisbursementEscrowed"), loan_id, borrower);
+    publish(env, topics, (amount, release_at));
+}
+
+pub fn borrowing_threshold_updated(env: &Env, threshold: i128) {
+    let topics = (Symbol::new(env, "BorrowingThresholdUpdated"),);
+    publish(env, topics, threshold);
+}
+
+pub fn max_loan_grace_updated(env: &Env, admin: Address, ledgers: u32) {
+    let topics = (Symbol::new(env, "MaxLoanGraceUpdated"), admin);
+    publish(env, topics, ledgers);
+}
+
+pub fn max_outstanding_per_borrower_updated(env: &Env, admin: Address, max_outstanding: i128) {
+    let topics = (Symbol::new(env, "MaxBorrowerOutstandingUpdated"), admin);
+    publish(env, topics, max_outstanding);
+}
+
+pub fn counters_reindexed(env: &Env, start_id: u32, end_id: u32, loans_found: u32) {
+    let topics = (Symbol::new(env, "CountersReindexed"),);
+    publish(env, topics, (start_id, end_id, loans_found));
+}
+
+pub fn rounding_mode_updated(env: &Env, mode: RoundingMode) {
+    let topics = (Symbol::new(env, "RoundingModeUpdated"),);
+    publish(env, topics, mode);
 }
 
 pub fn max_loans_per_borrower_updated(env: &Env, admin: Address, old_max: u32, new_max: u32) {
     let topics = (Symbol::new(env, "MaxLoansPerBorrower"), admin);
-    env.events().publish(topics, (old_max, new_max));
+    publish(env, topics, (old_max, new_max));
 }
 
 pub fn loan_approved_by_admin(env: &Env, admin: Address, loan_id: u32, borrower: Address) {
     let topics = (symbol_short!("LoanApprv"), admin);
-    env.events().publish(topics, (loan_id, borrower));
+    publish(env, topics, (loan_id, borrower));
 }
 
 pub fn collateral_liquidated(env: &Env, loan_id: u32, amount: i128) {
     let topics = (Symbol::new(env, "CollateralLiquidated"), loan_id);
-    env.events().publish(topics, amount);
+    publish(env, topics, amount);
 }
 
 pub fn loan_liquidated(
@@ -176,21 +321,85 @@ pub fn loan_liquidated(
         borrower,
         liquidator,
     );
-    env.events()
-        .publish(topics, (debt_repaid, liquidator_bonus, borrower_refund));
+    publish(
+        env,
+        topics,
+        (debt_repaid, liquidator_bonus, borrower_refund),
+    );
 }
 
 pub fn min_rate_bps_updated(env: &Env, admin: Address, old_rate: u32, new_rate: u32) {
     let topics = (Symbol::new(env, "MinRateBpsUpdated"), admin);
-    env.events().publish(topics, (old_rate, new_rate));
+    publish(env, topics, (old_rate, new_rate));
 }
 
 pub fn max_rate_bps_updated(env: &Env, admin: Address, old_rate: u32, new_rate: u32) {
     let topics = (Symbol::new(env, "MaxRateBpsUpdated"), admin);
-    env.events().publish(topics, (old_rate, new_rate));
+    publish(env, topics, (old_rate, new_rate));
+}
+
+pub fn loan_written_off(env: &Env, loan_id: u32, borrower: Address, amount: i128) {
+    let topics = (Symbol::new(env, "LoanWrittenOff"), loan_id, borrower);
+    publish(env, topics, amount);
 }
 
 pub fn loan_purged(env: &Env, loan_id: u32) {
     let topics = (Symbol::new(env, "LoanPurged"),);
-    env.events().publish(topics, loan_id);
+    publish(env, topics, loan_id);
+}
+
+pub fn whitelist_mode_updated(env: &Env, enabled: bool) {
+    let topics = (Symbol::new(env, "WhitelistModeUpdated"),);
+    publish(env, topics, enabled);
+}
+
+pub fn borrower_whitelist_updated(env: &Env, borrower: Address, whitelisted: bool) {
+    let topics = (Symbol::new(env, "BorrowerWhitelistUpdated"), borrower);
+    publish(env, topics, whitelisted);
+}
+
+pub fn keeper_updated(env: &Env, keeper: Address, enabled: bool) {
+    let topics = (Symbol::new(env, "KeeperUpdated"), keeper);
+    publish(env, topics, enabled);
+}
+
+pub fn loan_migrated(env: &Env, loan_id: u32, schema: u32) {
+    let topics = (Symbol::new(env, "LoanMigrated"), loan_id);
+    publish(env, topics, schema);
+}
+
+pub fn tranche_added(env: &Env, loan_id: u32, index: u32, amount: i128, release_ts: u64) {
+    let topics = (Symbol::new(env, "TrancheAdded"), loan_id);
+    publish(env, topics, (index, amount, release_ts));
+}
+
+pub fn tranche_released(env: &Env, loan_id: u32, borrower: Address, index: u32, amount: i128) {
+    let topics = (Symbol::new(env, "TrancheReleased"), loan_id, borrower);
+    publish(env, topics, (index, amount));
+}
+
+pub fn loan_status_changed(env: &Env, loan_id: u32, status: LoanStatus) {
+    let topics = (Symbol::new(env, "LoanStatusChanged"), loan_id);
+    publish(env, topics, status);
+}
+
+pub fn tranches_cancelled(env: &Env, loan_id: u32, amount: i128) {
+    let topics = (Symbol::new(env, "TranchesCancelled"), loan_id);
+    publish(env, topics, amount);
+}
+
+pub fn admin_transferred(env: &Env, new_admin: Address) {
+    publish(env, (Symbol::new(env, "AdminTransferred"),), new_admin);
+}
+
+pub fn contract_paused(env: &Env, paused_at_ledger: u32) {
+    publish(env, (Symbol::new(env, "ContractPaused"),), paused_at_ledger);
+}
+
+pub fn contract_unpaused(env: &Env, unpaused_at_ledger: u32) {
+    publish(
+        env,
+        (Symbol::new(env, "ContractUnpaused"),),
+        unpaused_at_ledger,
+    );
 }
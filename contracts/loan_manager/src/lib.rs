@@ -1,20 +1,26 @@
 #![no_std]
 use soroban_sdk::{
     contract, contractclient, contracterror, contractimpl, contracttype, Address, BytesN, Env,
-    String, Symbol, Vec,
+    FromVal, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 #[contractclient(name = "NftClient")]
 pub trait RemittanceNftInterface {
     fn get_score(env: Env, user: Address) -> u32;
+    fn current_score(env: Env, user: Address) -> u32;
     fn update_score(env: Env, user: Address, repayment_amount: i128, minter: Option<Address>);
     fn apply_score_delta(env: Env, user: Address, delta: i32, minter: Option<Address>);
     fn decrease_score(env: Env, user: Address, penalty_points: u32, minter: Option<Address>);
     fn seize_collateral(env: Env, user: Address, minter: Option<Address>);
     fn is_seized(env: Env, user: Address) -> bool;
-    fn record_default(env: Env, user: Address, minter: Option<Address>);
+    fn record_default(env: Env, user: Address, penalty_points: u32, minter: Option<Address>);
+    fn get_default_count(env: Env, user: Address) -> u32;
+    fn eligibility(env: Env, user: Address) -> NftEligibility;
+    fn set_outstanding(env: Env, user: Address, amount: i128, minter: Option<Address>);
     fn is_authorized_minter(env: Env, minter: Address) -> bool;
+    fn get_authorized_minters(env: Env) -> Vec<Address>;
     fn is_paused(env: Env) -> bool;
+    fn version(env: Env) -> u32;
 }
 
 #[contractclient(name = "RateOracleClient")]
@@ -27,6 +33,29 @@ pub trait LendingPoolInterface {
     fn is_paused(env: Env) -> bool;
     fn pool_balance(env: Env, token: Address) -> i128;
     fn get_total_outstanding(env: Env, token: Address) -> i128;
+    fn sync_utilization(env: Env, token: Address);
+    fn is_supported_token(env: Env, token: Address) -> bool;
+    fn get_total_deposits(env: Env, token: Address) -> i128;
+    fn record_interest(env: Env, token: Address, amount: i128);
+    fn capacity(env: Env, token: Address) -> PoolCapacity;
+}
+
+/// Mirror of the NFT contract's `Eligibility`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NftEligibility {
+    pub has_nft: bool,
+    pub score: u32,
+    pub seized: bool,
+    pub default_count: u32,
+}
+
+/// Mirror of the lending pool's `Capacity`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolCapacity {
+    pub deposit_headroom: i128,
+    pub borrow_headroom: i128,
 }
 
 mod events;
@@ -62,6 +91,23 @@ pub enum LoanError {
     InsufficientCollateral = 26,
     LoanNotLiquidatable = 27,
     LoanNotPurgable = 28,
+    BorrowerNotWhitelisted = 29,
+    Unauthorized = 30,
+    LoanNotDefaulted = 31,
+    TooManyDefaults = 32,
+    NoPendingDisbursement = 33,
+    DisbursementLocked = 34,
+    UnsupportedToken = 35,
+    BorrowingNotEnabled = 36,
+    InvalidGracePeriod = 37,
+    ApprovalNotExpired = 38,
+    ApprovalExpired = 39,
+    BorrowerDebtCapExceeded = 40,
+    BorrowBlackout = 41,
+    TimestampInPast = 42,
+    TrancheNotFound = 43,
+    TrancheAlreadyReleased = 44,
+    IdempotencyKeyReused = 45,
 }
 
 #[contracttype]
@@ -74,6 +120,17 @@ pub enum LoanStatus {
     Liquidated,
     Cancelled,
     Rejected,
+    WrittenOff,
+}
+
+/// How interest is rounded to whole token units as it accrues.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RoundingMode {
+    /// Carry the fractional remainder; accrue only whole units (the default).
+    Floor,
+    /// Round the accrued amount half up, carrying a signed remainder.
+    Round,
 }
 
 #[contracttype]
@@ -97,8 +154,102 @@ pub struct Loan {
     // Capped at MaxExtensions to prevent indefinite deferral.
     pub extension_count: u32,
     pub term_ledgers: u32,
+    /// Layout version of this record; see `LoanManager::LOAN_SCHEMA`.
+    /// Records written before versioning have no `schema` entry and are
+    /// upgraded on first read.
+    pub schema: u32,
+    /// Operator-supplied reference for reconciliation. Descriptive only.
+    pub memo: BytesN<32>,
+    /// Ledger timestamp at `request_loan`. Zero for records created before
+    /// schema 4.
+    pub created_at: u64,
+    /// Token the loan is disbursed, repaid and collateralised in.
+    pub token: Address,
+    /// Ledgers after the due date before this loan can be defaulted,
+    /// overriding the global default window. `None` uses the global value.
+    pub grace_ledgers: Option<u32>,
+    /// Optional co-signer whose score backs the request and who shares the
+    /// consequences of a default.
+    pub cosigner: Option<Address>,
+}
+
+/// Terms assigned to a new loan request, returned by `request_loan`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoanReceipt {
+    pub loan_id: u32,
+    pub interest_bps: u32,
+    /// The due date is fixed at approval as approval ledger + `term_ledgers`.
+    pub term_ledgers: u32,
+    /// Amount the borrower receives on approval.
+    pub net_disbursement: i128,
+}
+
+/// One page of `check_borrow_invariant`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BorrowInvariant {
+    /// Principal of active loans in the token summed so far, including the
+    /// `carried` amount passed in.
+    pub summed: i128,
+    /// Index to pass as `start` for the next page; `None` after the last.
+    pub next_start: Option<u32>,
+    /// On the last page, whether `summed` equals the `TotalOutstanding`
+    /// counter; on earlier pages, whether it has not yet exceeded it.
+    pub holds: bool,
+}
+
+/// Everyone holding a privileged role, returned by `roles`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoleSet {
+    pub admin: Address,
+    pub pending_admin: Option<Address>,
+    pub keepers: Vec<Address>,
+    /// Minters authorized on the NFT contract.
+    pub minters: Vec<Address>,
+}
+
+/// A scheduled slice of a loan's principal, paid out by `release_tranche`
+/// once `release_ts` has passed. Tranches still unreleased when the loan
+/// closes are cancelled and never paid out.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tranche {
+    pub amount: i128,
+    pub release_ts: u64,
+    pub released: bool,
+    pub cancelled: bool,
+}
+
+/// Read-only snapshot of a loan's repayment position, returned by
+/// `loan_summary`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoanSummary {
+    pub loan_id: u32,
+    pub borrower: Address,
+    pub status: LoanStatus,
+    pub principal: i128,
+    pub principal_remaining: i128,
+    pub interest_due: i128,
+    pub late_fee_due: i128,
+    /// Zero unless the loan is `Approved`.
+    pub total_owed: i128,
+    pub total_paid: i128,
+    pub principal_paid: i128,
+    pub interest_paid: i128,
+    pub late_fee_paid: i128,
+    pub interest_rate_bps: u32,
+    pub due_date: u32,
+    pub is_overdue: bool,
+    pub memo: BytesN<32>,
 }
 
+/// Storage keys. Contract-wide singletons and configuration live in instance
+/// storage and share its TTL; anything keyed by a loan or an address (loans,
+/// borrower loan lists, keepers, counters per borrower) lives in persistent
+/// storage and is bumped individually, so the instance entry stays small.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -132,7 +283,71 @@ pub enum DataKey {
     LiquidationBonusBps,
     MinRateBps,
     MaxRateBps,
-    MigratedVersion,
+    StorageVersion,
+    /// Legacy single-Vec active-loan index, superseded by
+    /// `DataKeyExt::ActiveLoanAt`; dropped by `index_active_loans_range`.
+    ActiveLoans,
+    CompoundPeriodLedgers,
+    WhitelistEnabled,
+    BorrowerWhitelist(Address),
+    TokenDecimals,
+    StatusCount(LoanStatus),
+    Keeper(Address),
+    /// (borrower, loan id, idempotency key) → amount of the applied repay
+    SeenPayment(Address, u32, BytesN<32>),
+    TotalWrittenOff,
+    BorrowerOutstanding(Address),
+    RefundOverpayment,
+    ThresholdInclusive,
+    LastDefault(Address),
+    MaxDefaults,
+    DefaultBanLedgers,
+    EventNamespace,
+    RoundingMode,
+    RateDiscounts,
+    DisbursementDelaySecs,
+}
+
+/// Storage keys added after `DataKey` reached the 50-variant limit of
+/// `#[contracttype]` enums. Same instance/persistent split as `DataKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyExt {
+    /// Ledger timestamp from which an approved loan's escrowed principal
+    /// may be claimed.
+    PendingDisbursement(u32),
+    /// Offset set at initialize; the first loan id is `LoanIdBase + 1`.
+    LoanIdBase,
+    /// Pool deposits of the configured token required before the first loan.
+    BorrowingEnabledThreshold,
+    /// Set once the threshold has been met; borrowing then stays enabled.
+    BorrowingEnabled,
+    /// Longest per-loan grace a borrower may request.
+    MaxLoanGraceLedgers,
+    /// Seconds after release an escrowed disbursement stays claimable.
+    DisbursementClaimWindow,
+    /// Ceiling on a borrower's open principal across all loans (0 = off).
+    MaxOutstandingPerBorrower,
+    /// Keepers in the order they were added.
+    Keepers,
+    /// `(from_ts, to_ts)` ledger timestamps during which new borrows and
+    /// disbursements are refused.
+    BorrowBlackout,
+    /// Principal of a loan held back for scheduled release, in the order
+    /// the tranches were added.
+    Tranches(u32),
+    /// token → unreleased tranche principal of approved loans, kept out of
+    /// the liquidity available to new loans.
+    TrancheReserve(Address),
+    /// Number of loans in the active-loan index.
+    ActiveLoanCount,
+    /// index position → loan id. Positions stay dense: removal moves the
+    /// last entry into the freed slot.
+    ActiveLoanAt(u32),
+    /// loan id → its position in the active-loan index.
+    ActiveLoanSlot(u32),
+    /// Index position `sweep_defaults` resumes scanning from.
+    SweepCursor,
 }
 
 #[contract]
@@ -146,7 +361,11 @@ impl LoanManager {
     const PERSISTENT_TTL_BUMP: u32 = 518400;
     const DEFAULT_INTEREST_RATE_BPS: u32 = 1200;
     const DEFAULT_TERM_LEDGERS: u32 = 17280;
+    // ~5s ledgers: DEFAULT_TERM_LEDGERS is one day.
+    const LEDGERS_PER_YEAR: u32 = Self::DEFAULT_TERM_LEDGERS * 365;
+    const SECONDS_PER_LEDGER: u64 = 5;
     const CURRENT_VERSION: u32 = 4;
+    const STORAGE_VERSION: u32 = 1;
     const DEFAULT_LATE_FEE_RATE_BPS: u32 = 500;
     const MAX_LATE_FEE_CAP_BPS: u32 = 2500;
     const DEFAULT_MAX_LOAN_AMOUNT: i128 = 50_000;
@@ -169,6 +388,19 @@ impl LoanManager {
     /// Default maximum interest rate (configurable via set_rate_bounds). #631
     const MAX_RATE_BPS: u32 = 100_000; // Maximum 1000% interest rate
     const MAX_PENALTY_MULTIPLIER: i128 = 2; // Total debt cannot exceed 2x original principal
+    const MAX_SWEEP_BATCH: u32 = 25; // Upper bound on loans defaulted per sweep_defaults call
+    const MAX_SWEEP_SCAN: u32 = 100; // Upper bound on index entries visited per sweep_defaults call
+    const MAX_COMPOUND_PERIODS: u32 = 365; // Periods compounded per accrual; the rest carry over
+    const COMPOUND_SCALE: i128 = 1_000_000_000;
+    // 1 = unversioned records predating the `schema` field; 3 adds `memo`;
+    // 4 adds `created_at`; 5 adds `token`; 6 adds `grace_ledgers`;
+    // 7 adds `cosigner`.
+    const LOAN_SCHEMA: u32 = 7;
+    const MAX_MIGRATION_BATCH: u32 = 50;
+    // Amount defaults below are denominated for a 7-decimal (Stellar asset) token
+    // and rescaled to the configured token's decimals.
+    const REFERENCE_DECIMALS: u32 = 7;
+    const SCORE_POINT_UNITS: i128 = 100; // Repayment units per score point
 
     fn bump_instance_ttl(env: &Env) {
         env.storage()
@@ -176,7 +408,7 @@ impl LoanManager {
             .extend_ttl(Self::INSTANCE_TTL_THRESHOLD, Self::INSTANCE_TTL_BUMP);
     }
 
-    fn bump_persistent_ttl(env: &Env, key: &DataKey) {
+    fn bump_persistent_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
         env.storage().persistent().extend_ttl(
             key,
             Self::PERSISTENT_TTL_THRESHOLD,
@@ -186,14 +418,17 @@ impl LoanManager {
 
     // ── Private helpers ───────────────────────────────────────────────────────
 
-    // fn get_admin(env: &Env) -> Address {
-    //     env.storage()
-    //         .instance()
-    //         .get(&DataKey::Admin)
-    //         .expect("not initialized")
-    // }
+    /// Panic with the uniform "not initialized" message if `initialize`
+    /// has not run. Every accessor for initialize-time config goes
+    /// through this.
+    fn require_initialized(env: &Env) {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            panic!("not initialized");
+        }
+    }
 
     fn nft_contract(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
@@ -202,6 +437,7 @@ impl LoanManager {
     }
 
     fn admin(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
@@ -210,6 +446,7 @@ impl LoanManager {
     }
 
     fn lending_pool(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
@@ -217,6 +454,106 @@ impl LoanManager {
             .expect("not initialized")
     }
 
+    fn read_tranches(env: &Env, loan_id: u32) -> Vec<Tranche> {
+        let key = DataKeyExt::Tranches(loan_id);
+        match env.storage().persistent().get(&key) {
+            Some(tranches) => {
+                Self::bump_persistent_ttl(env, &key);
+                tranches
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn write_tranches(env: &Env, loan_id: u32, tranches: &Vec<Tranche>) {
+        let key = DataKeyExt::Tranches(loan_id);
+        env.storage().persistent().set(&key, tranches);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    /// Principal of tranches neither released nor cancelled.
+    fn open_tranche_total(tranches: &Vec<Tranche>) -> i128 {
+        tranches
+            .iter()
+            .filter(|tranche| !tranche.released && !tranche.cancelled)
+            .map(|tranche| tranche.amount)
+            .sum()
+    }
+
+    fn tranche_reserve(env: &Env, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::TrancheReserve(token.clone()))
+            .unwrap_or(0)
+    }
+
+    fn adjust_tranche_reserve(env: &Env, token: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let updated = Self::tranche_reserve(env, token)
+            .checked_add(delta)
+            .expect("tranche reserve overflow");
+        if updated < 0 {
+            panic!("tranche reserve underflow");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::TrancheReserve(token.clone()), &updated);
+    }
+
+    /// Cancel `loan_id`'s unreleased tranches once it closes, returning any
+    /// liquidity reserved for them while it was approved.
+    fn cancel_open_tranches(env: &Env, loan_id: u32, loan: &Loan) {
+        let mut tranches = Self::read_tranches(env, loan_id);
+        let open = Self::open_tranche_total(&tranches);
+        if open == 0 {
+            return;
+        }
+        for index in 0..tranches.len() {
+            let mut tranche = tranches.get(index).unwrap();
+            if !tranche.released && !tranche.cancelled {
+                tranche.cancelled = true;
+                tranches.set(index, tranche);
+            }
+        }
+        Self::write_tranches(env, loan_id, &tranches);
+        if loan.status == LoanStatus::Approved {
+            Self::adjust_tranche_reserve(env, &loan.token, -open);
+        }
+        events::tranches_cancelled(env, loan_id, open);
+    }
+
+    fn disbursement_delay(env: &Env) -> u64 {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::DisbursementDelaySecs)
+            .unwrap_or(0)
+    }
+
+    fn disbursement_claim_window(env: &Env) -> u64 {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::DisbursementClaimWindow)
+            .unwrap_or(0)
+    }
+
+    /// Whether an escrowed disbursement released at `release_at` has gone
+    /// unclaimed past the claim window.
+    fn approval_expired(env: &Env, release_at: u64) -> bool {
+        let window = Self::disbursement_claim_window(env);
+        window > 0 && env.ledger().timestamp() >= release_at.saturating_add(window)
+    }
+
+    fn loan_id_base(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::LoanIdBase)
+            .unwrap_or(0)
+    }
+
     fn loan_counter(env: &Env) -> u32 {
         Self::bump_instance_ttl(env);
         env.storage()
@@ -240,7 +577,31 @@ impl LoanManager {
         }
     }
 
+    /// Rate locked into a loan: the oracle or configured base rate, less
+    /// the discount for the borrower's score tier, floored at zero.
     fn compute_interest_rate(env: &Env, borrower: &Address, amount: i128, score: u32) -> u32 {
+        Self::base_interest_rate(env, borrower, amount, score)
+            .saturating_sub(Self::rate_discount_bps(env, score))
+    }
+
+    /// Discount of the highest tier whose minimum score `score` reaches.
+    fn rate_discount_bps(env: &Env, score: u32) -> u32 {
+        let discounts: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RateDiscounts)
+            .unwrap_or(Map::new(env));
+        let mut discount = 0;
+        for (min_score, tier_discount) in discounts.iter() {
+            if score < min_score {
+                break;
+            }
+            discount = tier_discount;
+        }
+        discount
+    }
+
+    fn base_interest_rate(env: &Env, borrower: &Address, amount: i128, score: u32) -> u32 {
         if let Some(oracle_addr) = env
             .storage()
             .instance()
@@ -290,6 +651,7 @@ impl LoanManager {
     }
 
     fn require_not_paused(env: &Env) -> Result<(), LoanError> {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         let paused: bool = env
             .storage()
@@ -324,18 +686,49 @@ impl LoanManager {
         Ok(())
     }
 
+    /// Principal still owed on `loan`; none once it has been written off.
     fn remaining_principal(loan: &Loan) -> i128 {
+        if loan.status == LoanStatus::WrittenOff {
+            return 0;
+        }
         loan.amount
             .checked_sub(loan.principal_paid)
             .expect("principal paid exceeds amount")
     }
 
+    fn rounding_mode(env: &Env) -> RoundingMode {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundingMode)
+            .unwrap_or(RoundingMode::Floor)
+    }
+
+    /// `numerator / denominator` (denominator positive) rounded per the
+    /// configured rounding mode. Floor rounds toward negative infinity so a
+    /// negative carried remainder stays in range.
+    fn div_rounded(env: &Env, numerator: i128, denominator: i128) -> i128 {
+        match Self::rounding_mode(env) {
+            RoundingMode::Floor => numerator.div_euclid(denominator),
+            RoundingMode::Round => (numerator + denominator / 2).div_euclid(denominator),
+        }
+    }
+
     fn accrue_interest(env: &Env, loan: &mut Loan) -> Result<(), LoanError> {
+        Self::accrue_interest_to(env, loan, env.ledger().sequence())
+    }
+
+    /// Accrue interest on `loan` up to `current_ledger`, which may lie ahead
+    /// of the live ledger when projecting debt.
+    fn accrue_interest_to(
+        env: &Env,
+        loan: &mut Loan,
+        current_ledger: u32,
+    ) -> Result<(), LoanError> {
         if loan.status != LoanStatus::Approved {
             return Ok(());
         }
 
-        let current_ledger = env.ledger().sequence();
         if loan.last_interest_ledger == 0 || current_ledger <= loan.last_interest_ledger {
             return Ok(());
         }
@@ -347,6 +740,10 @@ impl LoanManager {
         }
 
         let elapsed_ledgers = current_ledger - loan.last_interest_ledger;
+        let compound_period = Self::compound_period_ledgers(env);
+        if compound_period > 0 {
+            return Self::accrue_compound_interest(env, loan, elapsed_ledgers, compound_period);
+        }
         const PRECISION: i128 = 1_000_000;
 
         // Calculate interest with high precision. Intermediate values are checked for overflow.
@@ -366,8 +763,8 @@ impl LoanManager {
 
         // Add the previous residual to the new calculation
         let combined_residual = loan.interest_residual + new_residual;
-        let additional_interest = combined_residual / PRECISION;
-        let final_residual = combined_residual % PRECISION;
+        let additional_interest = Self::div_rounded(env, combined_residual, PRECISION);
+        let final_residual = combined_residual - additional_interest * PRECISION;
 
         let total_accrued_delta = interest_delta
             .checked_add(additional_interest)
@@ -383,6 +780,80 @@ impl LoanManager {
         Ok(())
     }
 
+    fn whitelist_enabled(env: &Env) -> bool {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::WhitelistEnabled)
+            .unwrap_or(false)
+    }
+
+    fn compound_period_ledgers(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CompoundPeriodLedgers)
+            .unwrap_or(0)
+    }
+
+    /// Compound interest on the outstanding principal and interest for each
+    /// full period elapsed. Ledgers short of a full period are left for the
+    /// next accrual, as are periods beyond `MAX_COMPOUND_PERIODS`.
+    fn accrue_compound_interest(
+        env: &Env,
+        loan: &mut Loan,
+        elapsed_ledgers: u32,
+        period_ledgers: u32,
+    ) -> Result<(), LoanError> {
+        let periods = (elapsed_ledgers / period_ledgers).min(Self::MAX_COMPOUND_PERIODS);
+        if periods == 0 {
+            return Ok(());
+        }
+
+        // Per-period rate, scaled: rate_bps applies over DEFAULT_TERM_LEDGERS.
+        let period_rate = (loan.interest_rate_bps as i128)
+            .checked_mul(period_ledgers as i128)
+            .and_then(|v| v.checked_mul(Self::COMPOUND_SCALE))
+            .ok_or(LoanError::AmountTooLarge)?
+            / (10_000i128 * Self::DEFAULT_TERM_LEDGERS as i128);
+
+        // (1 + rate)^periods by binary exponentiation in fixed point.
+        let mut base = Self::COMPOUND_SCALE
+            .checked_add(period_rate)
+            .ok_or(LoanError::AmountTooLarge)?;
+        let mut factor = Self::COMPOUND_SCALE;
+        let mut exp = periods;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                factor = factor.checked_mul(base).ok_or(LoanError::AmountTooLarge)?
+                    / Self::COMPOUND_SCALE;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base =
+                    base.checked_mul(base).ok_or(LoanError::AmountTooLarge)? / Self::COMPOUND_SCALE;
+            }
+        }
+
+        let balance = Self::remaining_principal(loan)
+            .checked_add(loan.accrued_interest)
+            .ok_or(LoanError::AmountTooLarge)?;
+        let compounded = Self::div_rounded(
+            env,
+            balance
+                .checked_mul(factor)
+                .ok_or(LoanError::AmountTooLarge)?,
+            Self::COMPOUND_SCALE,
+        );
+
+        loan.accrued_interest = loan
+            .accrued_interest
+            .checked_add(compounded - balance)
+            .ok_or(LoanError::AmountTooLarge)?;
+        loan.last_interest_ledger += periods * period_ledgers;
+
+        Ok(())
+    }
+
     fn late_fee_rate_bps(env: &Env) -> u32 {
         Self::bump_instance_ttl(env);
         env.storage()
@@ -415,6 +886,25 @@ impl LoanManager {
         Ok(())
     }
 
+    /// Check `term` against the admin-configured term limits. Unset limits
+    /// leave that side unbounded.
+    fn validate_term(env: &Env, term: u32) -> Result<(), LoanError> {
+        let min_term: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinTermLedgers)
+            .unwrap_or(0);
+        let max_term: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxTermLedgers)
+            .unwrap_or(u32::MAX);
+        if term < min_term || term > max_term {
+            return Err(LoanError::InvalidTerm);
+        }
+        Ok(())
+    }
+
     fn validate_liquidation_threshold(ratio_bps: u32) -> Result<(), LoanError> {
         if (ratio_bps as i128) < Self::MIN_COLLATERAL_RATIO_BPS {
             return Err(LoanError::InvalidConfiguration);
@@ -450,6 +940,34 @@ impl LoanManager {
                 .expect("collateral ratio overflow")
     }
 
+    /// `value * bps / 10_000`, truncated toward zero. Splits `value` on the
+    /// bps denominator first so the product never needs more than i128,
+    /// and panics only when the result itself overflows.
+    fn mul_bps(value: i128, bps: u32) -> i128 {
+        let denominator = Self::MAX_RATIO_BPS as i128;
+        let bps = bps as i128;
+        (value / denominator)
+            .checked_mul(bps)
+            .and_then(|whole| whole.checked_add(value % denominator * bps / denominator))
+            .expect("bps overflow")
+    }
+
+    /// Collateral held above what `remaining_debt` needs at `threshold_bps`.
+    /// The requirement rounds up so a release never dips below the ratio.
+    fn excess_collateral(
+        collateral_amount: i128,
+        remaining_debt: i128,
+        threshold_bps: u32,
+    ) -> i128 {
+        let required = remaining_debt
+            .checked_mul(threshold_bps as i128)
+            .expect("collateral ratio overflow")
+            .checked_add(Self::MAX_RATIO_BPS as i128 - 1)
+            .expect("collateral ratio overflow")
+            / Self::MAX_RATIO_BPS as i128;
+        (collateral_amount - required).max(0)
+    }
+
     fn current_ratio_bps(collateral_amount: i128, total_debt: i128) -> u32 {
         if total_debt <= 0 || collateral_amount <= 0 {
             return 0;
@@ -464,6 +982,7 @@ impl LoanManager {
     }
 
     fn token(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
@@ -471,6 +990,49 @@ impl LoanManager {
             .expect("not initialized")
     }
 
+    /// The configured token is always lendable; any other token must be
+    /// allowlisted on the lending pool.
+    fn check_supported_token(env: &Env, token: &Address) -> Result<(), LoanError> {
+        if *token == Self::token(env) {
+            return Ok(());
+        }
+        if !PoolClient::new(env, &Self::lending_pool(env)).is_supported_token(token) {
+            return Err(LoanError::UnsupportedToken);
+        }
+        Ok(())
+    }
+
+    fn borrowing_threshold(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::BorrowingEnabledThreshold)
+            .unwrap_or(0)
+    }
+
+    /// Reject new borrows and disbursements inside the borrow blackout window.
+    fn require_no_borrow_blackout(env: &Env) -> Result<(), LoanError> {
+        if Self::is_borrow_blackout(env.clone()) {
+            return Err(LoanError::BorrowBlackout);
+        }
+        Ok(())
+    }
+
+    /// Reject borrowing until the pool's deposits of the configured token
+    /// first reach the bootstrap threshold, then latch it on for good.
+    fn require_borrowing_enabled(env: &Env) -> Result<(), LoanError> {
+        if !Self::is_borrowing_enabled(env.clone()) {
+            return Err(LoanError::BorrowingNotEnabled);
+        }
+        if Self::borrowing_threshold(env) > 0
+            && !env.storage().instance().has(&DataKeyExt::BorrowingEnabled)
+        {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::BorrowingEnabled, &true);
+        }
+        Ok(())
+    }
+
     fn grace_period_ledgers(env: &Env) -> u32 {
         Self::bump_instance_ttl(env);
         env.storage()
@@ -487,12 +1049,34 @@ impl LoanManager {
             .unwrap_or(Self::DEFAULT_DEFAULT_WINDOW_LEDGERS)
     }
 
+    fn token_decimals(env: &Env) -> u32 {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenDecimals)
+            .unwrap_or(Self::REFERENCE_DECIMALS)
+    }
+
+    /// Rescale an amount expressed in `REFERENCE_DECIMALS` units to the
+    /// configured token's decimals. Down-scaling never yields less than 1.
+    fn scale_amount(env: &Env, amount: i128) -> i128 {
+        let decimals = Self::token_decimals(env);
+        if decimals >= Self::REFERENCE_DECIMALS {
+            10i128
+                .checked_pow(decimals - Self::REFERENCE_DECIMALS)
+                .map_or(i128::MAX, |factor| amount.saturating_mul(factor))
+        } else {
+            let factor = 10i128.pow(Self::REFERENCE_DECIMALS - decimals);
+            (amount / factor).max(1)
+        }
+    }
+
     fn max_loan_amount(env: &Env) -> i128 {
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
             .get(&DataKey::MaxLoanAmount)
-            .unwrap_or(Self::DEFAULT_MAX_LOAN_AMOUNT)
+            .unwrap_or_else(|| Self::scale_amount(env, Self::DEFAULT_MAX_LOAN_AMOUNT))
     }
 
     fn max_loans_per_borrower(env: &Env) -> u32 {
@@ -503,65 +1087,254 @@ impl LoanManager {
             .unwrap_or(Self::DEFAULT_MAX_LOANS_PER_BORROWER)
     }
 
-    fn min_repayment_amount(env: &Env) -> i128 {
+    fn refund_overpayment(env: &Env) -> bool {
         Self::bump_instance_ttl(env);
         env.storage()
             .instance()
-            .get(&DataKey::MinRepaymentAmount)
-            .unwrap_or(Self::DEFAULT_MIN_REPAYMENT_AMOUNT)
+            .get(&DataKey::RefundOverpayment)
+            .unwrap_or(false)
     }
 
-    fn total_outstanding(env: &Env, token: &Address) -> i128 {
-        Self::bump_instance_ttl(env);
-        env.storage()
-            .instance()
-            .get(&DataKey::TotalOutstanding(token.clone()))
-            .unwrap_or(0)
-    }
+    /// Validation shared by `request_loan` and `simulate_request`. Returns
+    /// the interest rate the loan would be assigned.
+    fn check_loan_request(
+        env: &Env,
+        borrower: &Address,
+        amount: i128,
+        term: u32,
+        token: &Address,
+        grace_ledgers: Option<u32>,
+        cosigner: Option<&Address>,
+    ) -> Result<u32, LoanError> {
+        Self::require_not_paused(env)?;
+        Self::require_no_borrow_blackout(env)?;
 
-    fn adjust_total_outstanding(env: &Env, token: &Address, delta: i128) {
-        if delta == 0 {
-            return;
+        if amount <= 0 {
+            return Err(LoanError::InvalidAmount);
         }
 
-        let key = DataKey::TotalOutstanding(token.clone());
-        let current = Self::total_outstanding(env, token);
-        let updated = current
-            .checked_add(delta)
-            .expect("total outstanding overflow");
-
-        if updated < 0 {
-            panic!("total outstanding underflow");
+        Self::check_supported_token(env, token)?;
+        if !Self::is_borrowing_enabled(env.clone()) {
+            return Err(LoanError::BorrowingNotEnabled);
         }
 
-        env.storage().instance().set(&key, &updated);
-        Self::bump_instance_ttl(env);
-    }
+        let max_loan_amount = Self::max_loan_amount(env);
+        if amount > max_loan_amount {
+            return Err(LoanError::InvalidAmount);
+        }
 
-    fn borrower_loan_count(env: &Env, borrower: &Address) -> u32 {
-        let key = DataKey::BorrowerLoanCount(borrower.clone());
-        if env.storage().persistent().has(&key) {
-            let count = env.storage().persistent().get(&key).unwrap_or(0u32);
-            Self::bump_persistent_ttl(env, &key);
-            count
-        } else {
-            0
+        if term == 0 {
+            return Err(LoanError::InvalidTerm);
+        }
+        Self::validate_term(env, term)?;
+        if grace_ledgers.is_some_and(|grace| grace > Self::max_loan_grace_ledgers(env)) {
+            return Err(LoanError::InvalidGracePeriod);
         }
-    }
 
-    fn increment_borrower_loan_count(env: &Env, borrower: &Address) {
-        let key = DataKey::BorrowerLoanCount(borrower.clone());
-        let current_count = Self::borrower_loan_count(env, borrower);
-        let next_count = current_count
-            .checked_add(1)
-            .expect("borrower loan count overflow");
-        env.storage().persistent().set(&key, &next_count);
-        Self::bump_persistent_ttl(env, &key);
-    }
+        if Self::whitelist_enabled(env) && !Self::is_whitelisted(env.clone(), borrower.clone()) {
+            return Err(LoanError::BorrowerNotWhitelisted);
+        }
 
-    fn decrement_borrower_loan_count(env: &Env, borrower: &Address) {
-        let key = DataKey::BorrowerLoanCount(borrower.clone());
-        let current_count = Self::borrower_loan_count(env, borrower);
+        let nft_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::NftContract)
+            .ok_or(LoanError::NotInitialized)?;
+        let nft_client = NftClient::new(env, &nft_contract);
+
+        let eligibility = nft_client.eligibility(borrower);
+
+        // A co-signer's score can carry a thin-file borrower.
+        let mut score = eligibility.score;
+        if let Some(cosigner) = cosigner {
+            let cosigner_eligibility = nft_client.eligibility(cosigner);
+            if cosigner_eligibility.seized {
+                return Err(LoanError::SeizedBorrower);
+            }
+            score = score.max(cosigner_eligibility.score);
+        }
+        Self::check_min_score(env, score)?;
+        if Self::ban_ends(env, borrower, eligibility.default_count) > env.ledger().sequence() as u64
+        {
+            return Err(LoanError::TooManyDefaults);
+        }
+        if eligibility.seized {
+            return Err(LoanError::SeizedBorrower);
+        }
+
+        let active_loan_count = Self::borrower_loan_count(env, borrower);
+        let max_loans_per_borrower = Self::max_loans_per_borrower(env);
+        if active_loan_count >= max_loans_per_borrower {
+            return Err(LoanError::MaxLoansReached);
+        }
+
+        let max_outstanding = Self::max_outstanding_per_borrower(env);
+        if max_outstanding > 0
+            && Self::borrower_outstanding(env, borrower)
+                .checked_add(amount)
+                .is_none_or(|total| total > max_outstanding)
+        {
+            return Err(LoanError::BorrowerDebtCapExceeded);
+        }
+
+        Ok(Self::compute_interest_rate(env, borrower, amount, score))
+    }
+
+    /// `borrow_banned_until` for a borrower with `default_count` defaults.
+    fn ban_ends(env: &Env, borrower: &Address, default_count: u32) -> u64 {
+        let (max_defaults, ban_ledgers) = Self::get_default_ban(env.clone());
+        if max_defaults == 0 || default_count < max_defaults {
+            return 0;
+        }
+        let last_default: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastDefault(borrower.clone()))
+            .unwrap_or(0);
+        last_default as u64 + ban_ledgers as u64
+    }
+
+    /// Reject `score` against the configured minimum. With an inclusive
+    /// threshold a score equal to the minimum is rejected as well.
+    fn check_min_score(env: &Env, score: u32) -> Result<(), LoanError> {
+        let min_score: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinScore)
+            .unwrap_or(500);
+        let inclusive: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ThresholdInclusive)
+            .unwrap_or(false);
+        if score < min_score || (inclusive && score == min_score) {
+            return Err(LoanError::InsufficientScore);
+        }
+        Ok(())
+    }
+
+    fn min_repayment_amount(env: &Env) -> i128 {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::MinRepaymentAmount)
+            .unwrap_or_else(|| Self::scale_amount(env, Self::DEFAULT_MIN_REPAYMENT_AMOUNT))
+    }
+
+    fn total_outstanding(env: &Env, token: &Address) -> i128 {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalOutstanding(token.clone()))
+            .unwrap_or(0)
+    }
+
+    fn adjust_total_outstanding(env: &Env, token: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+
+        let key = DataKey::TotalOutstanding(token.clone());
+        let current = Self::total_outstanding(env, token);
+        let updated = current
+            .checked_add(delta)
+            .expect("total outstanding overflow");
+
+        if updated < 0 {
+            panic!("total outstanding underflow");
+        }
+
+        env.storage().instance().set(&key, &updated);
+        Self::bump_instance_ttl(env);
+    }
+
+    fn borrower_outstanding(env: &Env, borrower: &Address) -> i128 {
+        let key = DataKey::BorrowerOutstanding(borrower.clone());
+        let outstanding: Option<i128> = env.storage().persistent().get(&key);
+        if outstanding.is_some() {
+            Self::bump_persistent_ttl(env, &key);
+        }
+        outstanding.unwrap_or(0)
+    }
+
+    fn max_outstanding_per_borrower(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::MaxOutstandingPerBorrower)
+            .unwrap_or(0)
+    }
+
+    /// Track `borrower`'s open principal across approved loans and mirror it
+    /// to the NFT contract. Floors at zero for loans approved before tracking.
+    fn adjust_borrower_outstanding(env: &Env, borrower: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+
+        let key = DataKey::BorrowerOutstanding(borrower.clone());
+        let current = Self::borrower_outstanding(env, borrower);
+        let updated = current
+            .checked_add(delta)
+            .expect("borrower outstanding overflow")
+            .max(0);
+        env.storage().persistent().set(&key, &updated);
+        Self::bump_persistent_ttl(env, &key);
+
+        NftClient::new(env, &Self::nft_contract(env)).set_outstanding(
+            borrower,
+            &updated,
+            &Some(env.current_contract_address()),
+        );
+    }
+
+    /// Loan ids requested by `borrower`. Lists written by older versions
+    /// to instance storage are moved to persistent storage on first read.
+    fn borrower_loans(env: &Env, borrower: &Address) -> Vec<u32> {
+        let key = DataKey::BorrowerLoans(borrower.clone());
+        if let Some(loans) = env.storage().persistent().get::<_, Vec<u32>>(&key) {
+            Self::bump_persistent_ttl(env, &key);
+            return loans;
+        }
+        match env.storage().instance().get::<_, Vec<u32>>(&key) {
+            Some(loans) => {
+                env.storage().instance().remove(&key);
+                Self::write_borrower_loans(env, borrower, &loans);
+                loans
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn write_borrower_loans(env: &Env, borrower: &Address, loans: &Vec<u32>) {
+        let key = DataKey::BorrowerLoans(borrower.clone());
+        env.storage().persistent().set(&key, loans);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    fn borrower_loan_count(env: &Env, borrower: &Address) -> u32 {
+        let key = DataKey::BorrowerLoanCount(borrower.clone());
+        if env.storage().persistent().has(&key) {
+            let count = env.storage().persistent().get(&key).unwrap_or(0u32);
+            Self::bump_persistent_ttl(env, &key);
+            count
+        } else {
+            0
+        }
+    }
+
+    fn increment_borrower_loan_count(env: &Env, borrower: &Address) {
+        let key = DataKey::BorrowerLoanCount(borrower.clone());
+        let current_count = Self::borrower_loan_count(env, borrower);
+        let next_count = current_count
+            .checked_add(1)
+            .expect("borrower loan count overflow");
+        env.storage().persistent().set(&key, &next_count);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    fn decrement_borrower_loan_count(env: &Env, borrower: &Address) {
+        let key = DataKey::BorrowerLoanCount(borrower.clone());
+        let current_count = Self::borrower_loan_count(env, borrower);
         if current_count == 0 {
             return;
         }
@@ -570,19 +1343,242 @@ impl LoanManager {
         Self::bump_persistent_ttl(env, &key);
     }
 
+    fn active_loan_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::ActiveLoanCount)
+            .unwrap_or(0)
+    }
+
+    fn active_loan_at(env: &Env, position: u32) -> Option<u32> {
+        let key = DataKeyExt::ActiveLoanAt(position);
+        let loan_id = env.storage().persistent().get(&key);
+        if loan_id.is_some() {
+            Self::bump_persistent_ttl(env, &key);
+        }
+        loan_id
+    }
+
+    fn set_active_loan_at(env: &Env, position: u32, loan_id: u32) {
+        let at_key = DataKeyExt::ActiveLoanAt(position);
+        env.storage().persistent().set(&at_key, &loan_id);
+        Self::bump_persistent_ttl(env, &at_key);
+        let slot_key = DataKeyExt::ActiveLoanSlot(loan_id);
+        env.storage().persistent().set(&slot_key, &position);
+        Self::bump_persistent_ttl(env, &slot_key);
+    }
+
+    /// Append `loan_id` to the active-loan index. No-op if already indexed.
+    fn add_active_loan(env: &Env, loan_id: u32) {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKeyExt::ActiveLoanSlot(loan_id))
+        {
+            return;
+        }
+        let count = Self::active_loan_count(env);
+        Self::set_active_loan_at(env, count, loan_id);
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::ActiveLoanCount, &(count + 1));
+    }
+
+    /// Drop `loan_id` from the active-loan index, filling its slot with the
+    /// last entry.
+    fn remove_active_loan(env: &Env, loan_id: u32) {
+        let slot_key = DataKeyExt::ActiveLoanSlot(loan_id);
+        let Some(position) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return;
+        };
+        env.storage().persistent().remove(&slot_key);
+
+        let last = Self::active_loan_count(env) - 1;
+        if position != last {
+            let moved = Self::active_loan_at(env, last).expect("active loan index corrupt");
+            Self::set_active_loan_at(env, position, moved);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKeyExt::ActiveLoanAt(last));
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::ActiveLoanCount, &last);
+    }
+
+    /// Authorize an operational call: `caller` must be the admin or a keeper.
+    fn require_operator(env: &Env, caller: &Address) -> Result<(), LoanError> {
+        caller.require_auth();
+        if *caller == Self::admin(env) {
+            return Ok(());
+        }
+        let key = DataKey::Keeper(caller.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(LoanError::Unauthorized);
+        }
+        Self::bump_persistent_ttl(env, &key);
+        Ok(())
+    }
+
+    fn status_count(env: &Env, status: LoanStatus) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StatusCount(status))
+            .unwrap_or(0)
+    }
+
+    fn adjust_status_count(env: &Env, status: LoanStatus, delta: i32) {
+        let key = DataKey::StatusCount(status.clone());
+        let count = Self::status_count(env, status).saturating_add_signed(delta);
+        env.storage().instance().set(&key, &count);
+    }
+
+    const ALL_STATUSES: [LoanStatus; 8] = [
+        LoanStatus::Pending,
+        LoanStatus::Approved,
+        LoanStatus::Repaid,
+        LoanStatus::Defaulted,
+        LoanStatus::Liquidated,
+        LoanStatus::Cancelled,
+        LoanStatus::Rejected,
+        LoanStatus::WrittenOff,
+    ];
+
+    /// Statuses that keep a loan counted against its borrower's loan cap:
+    /// open loans, plus cancelled and rejected ones until they are purged.
+    fn holds_borrower_slot(status: &LoanStatus) -> bool {
+        matches!(
+            status,
+            LoanStatus::Pending
+                | LoanStatus::Approved
+                | LoanStatus::Cancelled
+                | LoanStatus::Rejected
+        )
+    }
+
+    /// Move `loan` to `to`, keeping the status counts in step. Every status
+    /// transition goes through here so each one emits `LoanStatusChanged`.
+    fn set_status(env: &Env, loan_id: u32, loan: &mut Loan, to: LoanStatus) {
+        if !matches!(to, LoanStatus::Pending | LoanStatus::Approved) {
+            Self::cancel_open_tranches(env, loan_id, loan);
+        }
+        Self::adjust_status_count(env, loan.status.clone(), -1);
+        Self::adjust_status_count(env, to.clone(), 1);
+        loan.status = to.clone();
+        events::loan_status_changed(env, loan_id, to);
+    }
+
+    fn is_overdue(env: &Env, loan: &Loan) -> bool {
+        loan.status == LoanStatus::Approved
+            && loan.due_date != 0
+            && env.ledger().sequence() > loan.due_date
+    }
+
+    /// Start `loan`'s term, interest and late-fee clocks at the current
+    /// ledger. Runs when the principal reaches the borrower: on approval, or
+    /// on `claim_disbursement` for an escrowed loan.
+    fn start_loan_clock(env: &Env, loan: &mut Loan) {
+        loan.due_date = env.ledger().sequence() + loan.term_ledgers;
+        loan.last_interest_ledger = env.ledger().sequence();
+        loan.last_late_fee_ledger = loan
+            .due_date
+            .checked_add(Self::loan_grace_period(env, loan))
+            .expect("grace period overflow");
+    }
+
+    /// Whether `loan_id`'s principal is still escrowed awaiting
+    /// `claim_disbursement`.
+    fn awaiting_disbursement(env: &Env, loan_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKeyExt::PendingDisbursement(loan_id))
+    }
+
+    /// Ledgers after the due date before `loan` can be defaulted.
+    fn loan_default_window(env: &Env, loan: &Loan) -> u32 {
+        loan.grace_ledgers
+            .unwrap_or_else(|| Self::default_window_ledgers(env))
+    }
+
+    /// Ledgers after the due date before late fees start on `loan`; never
+    /// longer than its default window.
+    fn loan_grace_period(env: &Env, loan: &Loan) -> u32 {
+        Self::grace_period_ledgers(env).min(Self::loan_default_window(env, loan))
+    }
+
+    fn max_loan_grace_ledgers(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::MaxLoanGraceLedgers)
+            .unwrap_or_else(|| Self::default_window_ledgers(env))
+    }
+
+    fn is_default_eligible(env: &Env, loan: &Loan) -> bool {
+        // An escrowed loan's term has not started yet.
+        if loan.due_date == 0 {
+            return false;
+        }
+        let default_eligible_after = loan
+            .due_date
+            .checked_add(Self::loan_default_window(env, loan))
+            .expect("default window overflow");
+        env.ledger().sequence() > default_eligible_after
+    }
+
+    /// Transition an approved, overdue loan to Defaulted: release its
+    /// outstanding balance, seize collateral and penalize the borrower's score.
+    fn mark_defaulted(env: &Env, loan_id: u32, mut loan: Loan) {
+        let loan_key = DataKey::Loan(loan_id);
+        let open_principal = Self::remaining_principal(&loan);
+        Self::set_status(env, loan_id, &mut loan, LoanStatus::Defaulted);
+        let token = loan.token.clone();
+        Self::adjust_total_outstanding(env, &token, -loan.amount);
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(env, &loan_key);
+        Self::decrement_borrower_loan_count(env, &loan.borrower);
+        Self::remove_active_loan(env, loan_id);
+        Self::seize_collateral_internal(env, loan_id);
+
+        let nft_contract = Self::nft_contract(env);
+        let nft_client = NftClient::new(env, &nft_contract);
+        nft_client.record_default(
+            &loan.borrower,
+            &Self::DEFAULT_SCORE_PENALTY_POINTS,
+            &Some(env.current_contract_address()),
+        );
+        if let Some(cosigner) = &loan.cosigner {
+            nft_client.record_default(
+                cosigner,
+                &Self::DEFAULT_SCORE_PENALTY_POINTS,
+                &Some(env.current_contract_address()),
+            );
+        }
+        let last_default_key = DataKey::LastDefault(loan.borrower.clone());
+        env.storage()
+            .persistent()
+            .set(&last_default_key, &env.ledger().sequence());
+        Self::bump_persistent_ttl(env, &last_default_key);
+        Self::adjust_borrower_outstanding(env, &loan.borrower, -open_principal);
+
+        events::loan_defaulted(env, loan_id, loan.borrower.clone());
+    }
+
     fn accrue_late_fee(env: &Env, loan: &mut Loan) -> i128 {
+        Self::accrue_late_fee_to(env, loan, env.ledger().sequence())
+    }
+
+    fn accrue_late_fee_to(env: &Env, loan: &mut Loan, current_ledger: u32) -> i128 {
         if loan.status != LoanStatus::Approved {
             return 0;
         }
 
-        let current_ledger = env.ledger().sequence();
         if loan.due_date == 0 {
             return 0;
         }
 
         let grace_ends = loan
             .due_date
-            .checked_add(Self::grace_period_ledgers(env))
+            .checked_add(Self::loan_grace_period(env, loan))
             .expect("grace period overflow");
         if current_ledger <= grace_ends {
             return 0;
@@ -644,8 +1640,16 @@ impl LoanManager {
     }
 
     fn current_total_debt(env: &Env, loan: &mut Loan) -> Result<(i128, i128), LoanError> {
-        Self::accrue_interest(env, loan)?;
-        let late_fee_delta = Self::accrue_late_fee(env, loan);
+        Self::total_debt_at_ledger(env, loan, env.ledger().sequence())
+    }
+
+    fn total_debt_at_ledger(
+        env: &Env,
+        loan: &mut Loan,
+        ledger: u32,
+    ) -> Result<(i128, i128), LoanError> {
+        Self::accrue_interest_to(env, loan, ledger)?;
+        let late_fee_delta = Self::accrue_late_fee_to(env, loan, ledger);
         let total_debt = Self::remaining_principal(loan)
             .checked_add(loan.accrued_interest)
             .and_then(|value| value.checked_add(loan.accrued_late_fee))
@@ -653,10 +1657,21 @@ impl LoanManager {
         Ok((total_debt, late_fee_delta))
     }
 
-    /// Split a repayment across principal, interest, and late fees based on
-    /// each component's share of the current total debt. This avoids a strict
-    /// waterfall where a borrower can repeatedly clear one bucket first while
-    /// delaying principal reduction.
+    /// Split a repayment across principal, interest, and late fees in a
+    /// fixed order: accrued late fees first, then interest, then principal.
+    /// Returns `(principal, interest, late_fee)` like the proportional split.
+    fn waterfall_repayment_split(loan: &Loan, amount: i128) -> (i128, i128, i128) {
+        let late_fee_payment = amount.min(loan.accrued_late_fee).max(0);
+        let mut unallocated = amount - late_fee_payment;
+        let interest_payment = unallocated.min(loan.accrued_interest).max(0);
+        unallocated -= interest_payment;
+        let principal_payment = unallocated.min(Self::remaining_principal(loan)).max(0);
+
+        (principal_payment, interest_payment, late_fee_payment)
+    }
+
+    /// Split a recovery across principal, interest, and late fees based on
+    /// each component's share of the current total debt.
     fn proportional_repayment_split(loan: &Loan, amount: i128) -> (i128, i128, i128) {
         let principal_due = Self::remaining_principal(loan);
         let total_debt = principal_due
@@ -762,9 +1777,77 @@ impl LoanManager {
             .expect("principal paid overflow");
     }
 
+    /// Read a loan record, upgrading it in place if it was stored under an
+    /// older schema.
+    fn read_loan(env: &Env, loan_id: u32) -> Option<Loan> {
+        Self::read_loan_migrating(env, loan_id).map(|(loan, _)| loan)
+    }
+
+    /// Like `read_loan`, additionally reporting whether the record was
+    /// upgraded by this read.
+    fn read_loan_migrating(env: &Env, loan_id: u32) -> Option<(Loan, bool)> {
+        let loan_key = DataKey::Loan(loan_id);
+        let raw: Map<Symbol, Val> = env.storage().persistent().get(&loan_key)?;
+        if raw
+            .get(Symbol::new(env, "schema"))
+            .is_some_and(|schema| u32::from_val(env, &schema) == Self::LOAN_SCHEMA)
+        {
+            return Some((Loan::from_val(env, &raw.to_val()), false));
+        }
+
+        let loan = Self::upgrade_legacy_loan(env, &raw);
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(env, &loan_key);
+        events::loan_migrated(env, loan_id, Self::LOAN_SCHEMA);
+        Some((loan, true))
+    }
+
+    /// Build a current-schema loan from a record stored under an older
+    /// schema. Fields added since default to their value for a fresh loan.
+    fn upgrade_legacy_loan(env: &Env, raw: &Map<Symbol, Val>) -> Loan {
+        fn field<T: TryFromVal<Env, Val>>(
+            env: &Env,
+            raw: &Map<Symbol, Val>,
+            name: &str,
+        ) -> Option<T> {
+            raw.get(Symbol::new(env, name)).map(|v| {
+                T::try_from_val(env, &v).unwrap_or_else(|_| panic!("malformed legacy loan"))
+            })
+        }
+        fn required<T: TryFromVal<Env, Val>>(env: &Env, raw: &Map<Symbol, Val>, name: &str) -> T {
+            field(env, raw, name).unwrap_or_else(|| panic!("malformed legacy loan"))
+        }
+
+        Loan {
+            borrower: required(env, raw, "borrower"),
+            amount: required(env, raw, "amount"),
+            collateral_amount: field(env, raw, "collateral_amount").unwrap_or(0),
+            principal_paid: required(env, raw, "principal_paid"),
+            interest_paid: required(env, raw, "interest_paid"),
+            accrued_interest: required(env, raw, "accrued_interest"),
+            late_fee_paid: field(env, raw, "late_fee_paid").unwrap_or(0),
+            accrued_late_fee: field(env, raw, "accrued_late_fee").unwrap_or(0),
+            interest_rate_bps: required(env, raw, "interest_rate_bps"),
+            due_date: required(env, raw, "due_date"),
+            last_interest_ledger: required(env, raw, "last_interest_ledger"),
+            last_late_fee_ledger: field(env, raw, "last_late_fee_ledger").unwrap_or(0),
+            status: required(env, raw, "status"),
+            interest_residual: field(env, raw, "interest_residual").unwrap_or(0),
+            extension_count: field(env, raw, "extension_count").unwrap_or(0),
+            term_ledgers: field(env, raw, "term_ledgers")
+                .unwrap_or_else(|| Self::read_default_term(env)),
+            schema: Self::LOAN_SCHEMA,
+            memo: field(env, raw, "memo").unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32])),
+            created_at: field(env, raw, "created_at").unwrap_or(0),
+            token: field(env, raw, "token").unwrap_or_else(|| Self::token(env)),
+            grace_ledgers: field(env, raw, "grace_ledgers").unwrap_or(None),
+            cosigner: field(env, raw, "cosigner").unwrap_or(None),
+        }
+    }
+
     fn collateral_amount(env: &Env, loan_id: u32) -> i128 {
         let loan_key = DataKey::Loan(loan_id);
-        if let Some(loan) = env.storage().persistent().get::<DataKey, Loan>(&loan_key) {
+        if let Some(loan) = Self::read_loan(env, loan_id) {
             Self::bump_persistent_ttl(env, &loan_key);
             loan.collateral_amount
         } else {
@@ -776,11 +1859,8 @@ impl LoanManager {
         use soroban_sdk::token::TokenClient;
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .unwrap_or_else(|| panic!("loan not found"));
+        let mut loan: Loan =
+            Self::read_loan(env, loan_id).unwrap_or_else(|| panic!("loan not found"));
 
         let collateral = loan.collateral_amount;
         if collateral <= 0 {
@@ -791,11 +1871,7 @@ impl LoanManager {
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(env, &loan_key);
 
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
+        let token = loan.token.clone();
         let token_client = TokenClient::new(env, &token);
         token_client.transfer(&env.current_contract_address(), recipient, &collateral);
 
@@ -807,11 +1883,8 @@ impl LoanManager {
         use soroban_sdk::token::TokenClient;
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .unwrap_or_else(|| panic!("loan not found"));
+        let mut loan: Loan =
+            Self::read_loan(env, loan_id).unwrap_or_else(|| panic!("loan not found"));
 
         let collateral = loan.collateral_amount;
         if collateral <= 0 {
@@ -822,16 +1895,8 @@ impl LoanManager {
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(env, &loan_key);
 
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
-        let lending_pool: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LendingPool)
-            .expect("lending pool not set");
+        let token = loan.token.clone();
+        let lending_pool = Self::lending_pool(env);
         let token_client = TokenClient::new(env, &token);
         token_client.transfer(&env.current_contract_address(), &lending_pool, &collateral);
 
@@ -844,10 +1909,17 @@ impl LoanManager {
         lending_pool: Address,
         token: Address,
         admin: Address,
+        event_namespace: Option<Symbol>,
+        loan_id_base: Option<u32>,
     ) -> Result<(), LoanError> {
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(LoanError::AlreadyInitialized);
         }
+        if let Some(namespace) = event_namespace {
+            env.storage()
+                .instance()
+                .set(&DataKey::EventNamespace, &namespace);
+        }
         Self::validate_late_fee_rate(Self::DEFAULT_LATE_FEE_RATE_BPS)?;
         Self::validate_liquidation_threshold(Self::DEFAULT_LIQUIDATION_THRESHOLD_BPS)?;
         Self::validate_liquidation_bonus_bps(Self::DEFAULT_LIQUIDATION_BONUS_BPS)?;
@@ -858,8 +1930,21 @@ impl LoanManager {
             .instance()
             .set(&DataKey::LendingPool, &lending_pool);
         env.storage().instance().set(&DataKey::Token, &token);
+        use soroban_sdk::token::TokenClient;
+        let decimals = TokenClient::new(&env, &token).decimals();
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &decimals);
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::LoanCounter, &0u32);
+        let loan_id_base = loan_id_base.unwrap_or(0);
+        if loan_id_base > 0 {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::LoanIdBase, &loan_id_base);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LoanCounter, &loan_id_base);
         env.storage().instance().set(&DataKey::Paused, &false);
 
         let nft_client = NftClient::new(&env, &nft_contract);
@@ -871,7 +1956,11 @@ impl LoanManager {
             .set(&DataKey::Version, &Self::CURRENT_VERSION);
         env.storage()
             .instance()
-            .set(&DataKey::MaxLoanAmount, &Self::DEFAULT_MAX_LOAN_AMOUNT);
+            .set(&DataKey::StorageVersion, &Self::STORAGE_VERSION);
+        env.storage().instance().set(
+            &DataKey::MaxLoanAmount,
+            &Self::scale_amount(&env, Self::DEFAULT_MAX_LOAN_AMOUNT),
+        );
         env.storage().instance().set(
             &DataKey::MaxLoansPerBorrower,
             &Self::DEFAULT_MAX_LOANS_PER_BORROWER,
@@ -905,11 +1994,49 @@ impl LoanManager {
         Ok(())
     }
 
+    /// Leading topic prepended to every event, if set at initialize.
+    pub fn get_event_namespace(env: Env) -> Option<Symbol> {
+        Self::bump_instance_ttl(&env);
+        env.storage().instance().get(&DataKey::EventNamespace)
+    }
+
     pub fn version(env: Env) -> u32 {
         Self::bump_instance_ttl(&env);
         env.storage().instance().get(&DataKey::Version).unwrap_or(0)
     }
 
+    /// Smoke-test the configured NFT contract: it must answer `version()`
+    /// with an initialized (non-zero) version and still list this contract
+    /// as an authorized minter. Never panics on a broken address.
+    pub fn check_nft(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        let Some(nft_addr) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+        else {
+            return false;
+        };
+        let nft_client = NftClient::new(&env, &nft_addr);
+        if !matches!(nft_client.try_version(), Ok(Ok(version)) if version > 0) {
+            return false;
+        }
+        matches!(
+            nft_client.try_is_authorized_minter(&env.current_contract_address()),
+            Ok(Ok(true))
+        )
+    }
+
+    /// Storage layout version, advanced only by `migrate`. Contracts
+    /// initialized before the marker existed report 0.
+    pub fn storage_version(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(0)
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
         Self::admin(&env).require_auth();
         let old_version = Self::version(env.clone());
@@ -917,27 +2044,27 @@ impl LoanManager {
         env.storage()
             .instance()
             .set(&DataKey::Version, &new_version);
-        env.events().publish(
+        events::publish(
+            &env,
             (Symbol::new(&env, "ContractUpgraded"),),
             (old_version, new_version),
         );
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
+    /// Bring storage up to `STORAGE_VERSION`. A no-op when already current;
+    /// panics on a layout newer than this code understands.
     pub fn migrate(env: Env) {
         Self::admin(&env).require_auth();
 
-        // Migration guard: prevent double-execution for the same version
-        let last_migrated_version: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MigratedVersion)
-            .unwrap_or(0);
-
-        // If already migrated to this version or later, skip
-        if last_migrated_version >= Self::CURRENT_VERSION {
+        // Migration guard: prevent double-execution against the same layout
+        let storage_version = Self::storage_version(env.clone());
+        if storage_version == Self::STORAGE_VERSION {
             return;
         }
+        if storage_version > Self::STORAGE_VERSION {
+            panic!("unexpected storage version");
+        }
 
         // Initialize new storage keys with defaults if they don't exist
         if !env.storage().instance().has(&DataKey::LateFeeRateBps) {
@@ -985,72 +2112,69 @@ impl LoanManager {
             .set(&DataKey::Version, &Self::CURRENT_VERSION);
         env.storage()
             .instance()
-            .set(&DataKey::MigratedVersion, &Self::CURRENT_VERSION);
-        Self::bump_instance_ttl(&env);
-    }
-
-    /// Request a new loan for `borrower`.
-    ///
-    /// Requires `borrower` authorization and the loan manager, lending pool,
-    /// and NFT contract to be unpaused. The request starts in
-    /// [`LoanStatus::Pending`] and counts toward the borrower's active-loan cap.
-    /// Returns the new loan id.
-    ///
-    /// Returns [`LoanError::ContractPaused`], [`LoanError::PoolPaused`], or
-    /// [`LoanError::NftPaused`] when pause checks fail; [`LoanError::InvalidAmount`]
-    /// for non-positive amounts or amounts over the configured maximum;
-    /// [`LoanError::InvalidTerm`] for a zero term; [`LoanError::NotInitialized`]
-    /// when the NFT contract is missing; [`LoanError::InsufficientScore`] when
-    /// the borrower's NFT score is too low; [`LoanError::SeizedBorrower`] when
-    /// the borrower is flagged as seized; and [`LoanError::MaxLoansReached`]
-    /// when the borrower is already at the loan limit.
-    pub fn request_loan(
-        env: Env,
-        borrower: Address,
-        amount: i128,
-        term: u32,
-    ) -> Result<u32, LoanError> {
-        borrower.require_auth();
-        Self::require_not_paused(&env)?;
-
-        if amount <= 0 {
-            return Err(LoanError::InvalidAmount);
-        }
-
-        let max_loan_amount = Self::max_loan_amount(&env);
-        if amount > max_loan_amount {
-            return Err(LoanError::InvalidAmount);
-        }
-
-        if term == 0 {
-            return Err(LoanError::InvalidTerm);
-        }
-
-        let nft_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::NftContract)
-            .ok_or(LoanError::NotInitialized)?;
-        let nft_client = NftClient::new(&env, &nft_contract);
-
-        let score = nft_client.get_score(&borrower);
-        let min_score: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinScore)
-            .unwrap_or(500);
-        if score < min_score {
-            return Err(LoanError::InsufficientScore);
-        }
-        if nft_client.is_seized(&borrower) {
-            return Err(LoanError::SeizedBorrower);
-        }
+            .set(&DataKey::StorageVersion, &Self::STORAGE_VERSION);
+        Self::bump_instance_ttl(&env);
+    }
 
-        let active_loan_count = Self::borrower_loan_count(&env, &borrower);
-        let max_loans_per_borrower = Self::max_loans_per_borrower(&env);
-        if active_loan_count >= max_loans_per_borrower {
-            return Err(LoanError::MaxLoansReached);
+    /// Request a new loan for `borrower`.
+    ///
+    /// Requires `borrower` authorization and the loan manager, lending pool,
+    /// and NFT contract to be unpaused. The request starts in
+    /// [`LoanStatus::Pending`] and counts toward the borrower's active-loan cap.
+    /// Returns a [`LoanReceipt`] with the new loan id and its assigned terms.
+    /// `memo` is an optional operator reference
+    /// (e.g. a corridor code) stored on the loan verbatim; it defaults to
+    /// zeros and has no effect on loan logic. `token` selects the token to
+    /// borrow; it defaults to the configured token, and any other token must
+    /// be allowlisted on the lending pool. `grace_ledgers` replaces the
+    /// global default window for this loan, up to `get_max_loan_grace`.
+    /// `cosigner`, who must also authorize, lends their score to the request
+    /// (the higher of the two scores is used) and is also recorded as
+    /// defaulting if the loan defaults.
+    ///
+    /// Returns [`LoanError::ContractPaused`], [`LoanError::PoolPaused`], or
+    /// [`LoanError::NftPaused`] when pause checks fail; [`LoanError::InvalidAmount`]
+    /// for non-positive amounts or amounts over the configured maximum;
+    /// [`LoanError::InvalidTerm`] for a zero term or one outside the configured
+    /// term limits; [`LoanError::NotInitialized`]
+    /// when the NFT contract is missing; [`LoanError::InsufficientScore`] when
+    /// the borrower's NFT score is too low; [`LoanError::SeizedBorrower`] when
+    /// the borrower or co-signer is flagged as seized; [`LoanError::MaxLoansReached`]
+    /// when the borrower is already at the loan limit;
+    /// [`LoanError::UnsupportedToken`] when `token` is not lendable;
+    /// [`LoanError::InvalidGracePeriod`] when `grace_ledgers` exceeds the max;
+    /// and [`LoanError::InvalidConfiguration`] when the borrower co-signs
+    /// their own loan.
+    #[allow(clippy::too_many_arguments)]
+    pub fn request_loan(
+        env: Env,
+        borrower: Address,
+        amount: i128,
+        term: u32,
+        memo: Option<BytesN<32>>,
+        token: Option<Address>,
+        grace_ledgers: Option<u32>,
+        cosigner: Option<Address>,
+    ) -> Result<LoanReceipt, LoanError> {
+        borrower.require_auth();
+        if let Some(cosigner) = &cosigner {
+            if *cosigner == borrower {
+                return Err(LoanError::InvalidConfiguration);
+            }
+            cosigner.require_auth();
         }
+        let token = token.unwrap_or_else(|| Self::token(&env));
+        let interest_rate_bps = Self::check_loan_request(
+            &env,
+            &borrower,
+            amount,
+            term,
+            &token,
+            grace_ledgers,
+            cosigner.as_ref(),
+        )?;
+        // Latch the borrowing threshold now that a request goes through.
+        Self::require_borrowing_enabled(&env)?;
 
         let mut loan_counter: u32 = env
             .storage()
@@ -1068,7 +2192,7 @@ impl LoanManager {
             accrued_interest: 0,
             late_fee_paid: 0,
             accrued_late_fee: 0,
-            interest_rate_bps: Self::compute_interest_rate(&env, &borrower, amount, score),
+            interest_rate_bps,
             due_date: 0,
             last_interest_ledger: 0,
             last_late_fee_ledger: 0,
@@ -1076,6 +2200,12 @@ impl LoanManager {
             interest_residual: 0,
             extension_count: 0,
             term_ledgers: term,
+            schema: Self::LOAN_SCHEMA,
+            memo: memo.unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32])),
+            created_at: env.ledger().timestamp(),
+            token,
+            grace_ledgers,
+            cosigner,
         };
 
         env.storage()
@@ -1088,25 +2218,57 @@ impl LoanManager {
 
         // Count pending loans against the borrower cap immediately.
         Self::increment_borrower_loan_count(&env, &borrower);
+        Self::adjust_status_count(&env, LoanStatus::Pending, 1);
+        events::loan_status_changed(&env, loan_counter, LoanStatus::Pending);
 
         Self::bump_instance_ttl(&env);
         Self::bump_persistent_ttl(&env, &DataKey::Loan(loan_counter));
 
         // Add loan ID to borrower's loan list
-        let borrower_loans_key = DataKey::BorrowerLoans(borrower.clone());
-        let mut borrower_loans: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&borrower_loans_key)
-            .unwrap_or(Vec::new(&env));
+        let mut borrower_loans = Self::borrower_loans(&env, &borrower);
         borrower_loans.push_back(loan_counter);
-        env.storage()
-            .instance()
-            .set(&borrower_loans_key, &borrower_loans);
-        Self::bump_instance_ttl(&env);
+        Self::write_borrower_loans(&env, &borrower, &borrower_loans);
 
         events::loan_requested(&env, loan_counter, borrower.clone(), amount);
-        Ok(loan_counter)
+        Ok(LoanReceipt {
+            loan_id: loan_counter,
+            interest_bps: loan.interest_rate_bps,
+            term_ledgers: term,
+            net_disbursement: amount,
+        })
+    }
+
+    /// Dry run of `request_loan` for `borrower` with the default term and
+    /// the configured token. Runs the same checks and returns the receipt
+    /// the request would produce, without requiring auth or writing the loan.
+    ///
+    /// Returns the same errors as `request_loan`.
+    pub fn simulate_request(
+        env: Env,
+        borrower: Address,
+        amount: i128,
+    ) -> Result<LoanReceipt, LoanError> {
+        let term = Self::read_default_term(&env);
+        let interest_bps = Self::check_loan_request(
+            &env,
+            &borrower,
+            amount,
+            term,
+            &Self::token(&env),
+            None,
+            None,
+        )?;
+        let loan_counter: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LoanCounter)
+            .unwrap_or(0);
+        Ok(LoanReceipt {
+            loan_id: loan_counter + 1,
+            interest_bps,
+            term_ledgers: term,
+            net_disbursement: amount,
+        })
     }
 
     /// Approve a pending loan and transfer principal to the borrower.
@@ -1115,13 +2277,20 @@ impl LoanManager {
     /// contract to be unpaused. The target loan must be [`LoanStatus::Pending`];
     /// approval records the default term, due date, interest/late-fee ledgers,
     /// and total outstanding balance before transferring funds from the lending
-    /// pool to the borrower.
+    /// pool to the borrower. With no disbursement delay set, the principal is
+    /// paid out in this call. Otherwise it stays in the pool in escrow until
+    /// the borrower collects it with [`Self::claim_disbursement`] once the
+    /// delay has elapsed, and the loan's term and interest only start then; an
+    /// escrow left unclaimed past the claim window can
+    /// be cancelled with [`Self::expire_approval`]. Principal scheduled into
+    /// tranches is held back for [`Self::release_tranche`] either way.
     ///
     /// Returns [`LoanError::ContractPaused`], [`LoanError::PoolPaused`], or
-    /// [`LoanError::NftPaused`] when pause checks fail; [`LoanError::LoanNotFound`]
+    /// [`LoanError::NftPaused`] when pause checks fail; [`LoanError::BorrowBlackout`]
+    /// inside a borrow blackout window; [`LoanError::LoanNotFound`]
     /// when `loan_id` is unknown; [`LoanError::LoanNotPending`] when the loan is
     /// not pending; and [`LoanError::InsufficientPoolLiquidity`] when available
-    /// pool liquidity is below the loan amount.
+    /// pool liquidity, less the pool's reserve, is below the loan amount.
     pub fn approve_loan(env: Env, loan_id: u32) -> Result<(), LoanError> {
         use soroban_sdk::token::TokenClient;
 
@@ -1129,64 +2298,91 @@ impl LoanManager {
         let admin = Self::admin(&env);
         admin.require_auth();
         Self::require_not_paused(&env)?;
+        Self::require_no_borrow_blackout(&env)?;
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Pending {
             return Err(LoanError::LoanNotPending);
         }
+        // The pool may have delisted the token since the request.
+        Self::check_supported_token(&env, &loan.token)?;
+        Self::require_borrowing_enabled(&env)?;
 
         // Read all instance-level config before any state mutations.
-        let lending_pool: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LendingPool)
-            .expect("lending pool not set");
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
+        let lending_pool = Self::lending_pool(&env);
+        let token = loan.token.clone();
         let term_ledgers = Self::read_default_term(&env);
 
         // Cross-contract READ for liquidity check — still in the CHECKS phase.
         let pool_client = PoolClient::new(&env, &lending_pool);
         let pool_balance = pool_client.pool_balance(&token);
         let total_outstanding = Self::total_outstanding(&env, &token);
-        let available_liquidity = pool_balance.checked_sub(total_outstanding).unwrap_or(0);
-        if available_liquidity < loan.amount {
+        let available_liquidity = pool_balance
+            .checked_sub(total_outstanding)
+            .and_then(|liquidity| liquidity.checked_sub(Self::tranche_reserve(&env, &token)))
+            .unwrap_or(0);
+        if available_liquidity < loan.amount
+            || pool_client.capacity(&token).borrow_headroom < loan.amount
+        {
             return Err(LoanError::InsufficientPoolLiquidity);
         }
 
         // ── EFFECTS (all state mutations before any external calls) ─────────
         // Capture values used in the transfer before mutating loan fields.
         let borrower = loan.borrower.clone();
-        let transfer_amount = loan.amount;
 
-        loan.status = LoanStatus::Approved;
+        // Scheduled tranches stay in the pool, reserved, until
+        // `release_tranche`; until then only the rest is the loan's principal.
+        let scheduled = Self::open_tranche_total(&Self::read_tranches(&env, loan_id));
+        loan.amount -= scheduled;
+        Self::adjust_tranche_reserve(&env, &token, scheduled);
+
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::Approved);
         loan.term_ledgers = term_ledgers;
-        loan.due_date = env.ledger().sequence() + term_ledgers;
-        loan.last_interest_ledger = env.ledger().sequence();
-        loan.last_late_fee_ledger = loan
-            .due_date
-            .checked_add(Self::grace_period_ledgers(&env))
-            .expect("grace period overflow");
-        Self::adjust_total_outstanding(&env, &token, transfer_amount);
+        // With a disbursement delay the principal stays in the pool until the
+        // borrower calls `claim_disbursement`, and the loan's term and
+        // accrual start then.
+        let delay = Self::disbursement_delay(&env);
+        if delay == 0 {
+            Self::start_loan_clock(&env, &mut loan);
+        }
+        Self::adjust_total_outstanding(&env, &token, loan.amount);
 
         // Commit state before any cross-contract call (CEI pattern).
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(&env, &loan_key);
+        Self::add_active_loan(&env, loan_id);
+
+        if delay > 0 {
+            let release_at = env.ledger().timestamp().saturating_add(delay);
+            let escrow_key = DataKeyExt::PendingDisbursement(loan_id);
+            env.storage().persistent().set(&escrow_key, &release_at);
+            Self::bump_persistent_ttl(&env, &escrow_key);
+            Self::adjust_borrower_outstanding(&env, &borrower, loan.amount);
+            events::disbursement_escrowed(&env, loan_id, borrower.clone(), loan.amount, release_at);
+        } else {
+            // ── INTERACTIONS (external calls last) ──────────────────────────
+            if loan.amount > 0 {
+                let token_client = TokenClient::new(&env, &token);
+                token_client.transfer(&lending_pool, &borrower, &loan.amount);
+                pool_client.sync_utilization(&token);
+            }
+            Self::adjust_borrower_outstanding(&env, &borrower, loan.amount);
 
-        // ── INTERACTIONS (external calls last) ──────────────────────────────
-        let token_client = TokenClient::new(&env, &token);
-        token_client.transfer(&lending_pool, &borrower, &transfer_amount);
-
+            events::loan_disbursed(
+                &env,
+                loan_id,
+                borrower.clone(),
+                borrower.clone(),
+                loan.amount,
+                loan.interest_rate_bps,
+                loan.due_date,
+                loan.collateral_amount,
+            );
+        }
         events::loan_approved(
             &env,
             loan_id,
@@ -1199,13 +2395,284 @@ impl LoanManager {
         Ok(())
     }
 
-    pub fn get_loan(env: Env, loan_id: u32) -> Result<Loan, LoanError> {
+    /// Release the escrowed principal of an approved loan to its borrower
+    /// once the disbursement delay has elapsed, starting the loan's term and
+    /// interest accrual. Requires borrower auth.
+    pub fn claim_disbursement(env: Env, loan_id: u32) -> Result<(), LoanError> {
+        use soroban_sdk::token::TokenClient;
+
+        Self::require_not_paused(&env)?;
+        Self::require_no_borrow_blackout(&env)?;
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        loan.borrower.require_auth();
+
+        let escrow_key = DataKeyExt::PendingDisbursement(loan_id);
+        let release_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&escrow_key)
+            .ok_or(LoanError::NoPendingDisbursement)?;
+        if loan.status != LoanStatus::Approved {
+            return Err(LoanError::LoanNotActive);
+        }
+        if env.ledger().timestamp() < release_at {
+            return Err(LoanError::DisbursementLocked);
+        }
+        if Self::approval_expired(&env, release_at) {
+            return Err(LoanError::ApprovalExpired);
+        }
+
+        env.storage().persistent().remove(&escrow_key);
+        Self::start_loan_clock(&env, &mut loan);
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        let lending_pool = Self::lending_pool(&env);
+        let token = loan.token.clone();
+        if loan.amount > 0 {
+            let token_client = TokenClient::new(&env, &token);
+            token_client.transfer(&lending_pool, &loan.borrower, &loan.amount);
+            PoolClient::new(&env, &lending_pool).sync_utilization(&token);
+        }
+
+        events::loan_disbursed(
+            &env,
+            loan_id,
+            loan.borrower.clone(),
+            loan.borrower.clone(),
+            loan.amount,
+            loan.interest_rate_bps,
+            loan.due_date,
+            loan.collateral_amount,
+        );
+        Ok(())
+    }
+
+    /// Cancel an approved loan whose escrowed principal was not claimed
+    /// within the claim window. The principal never left the pool, so the
+    /// loan's outstanding balance and active-loan slot are released and any
+    /// collateral posted against it is returned to the borrower. Callable by
+    /// anyone once the approval has expired.
+    pub fn expire_approval(env: Env, loan_id: u32) -> Result<(), LoanError> {
+        use soroban_sdk::token::TokenClient;
+
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        let escrow_key = DataKeyExt::PendingDisbursement(loan_id);
+        let release_at: u64 = env
             .storage()
             .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+            .get(&escrow_key)
+            .ok_or(LoanError::NoPendingDisbursement)?;
+        if loan.status != LoanStatus::Approved {
+            return Err(LoanError::LoanNotActive);
+        }
+        if !Self::approval_expired(&env, release_at) {
+            return Err(LoanError::ApprovalNotExpired);
+        }
+
+        env.storage().persistent().remove(&escrow_key);
+        let borrower = loan.borrower.clone();
+        let token = loan.token.clone();
+        let collateral_to_release = loan.collateral_amount;
+        Self::adjust_total_outstanding(&env, &token, -loan.amount);
+        Self::adjust_borrower_outstanding(&env, &borrower, -Self::remaining_principal(&loan));
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::Cancelled);
+        loan.collateral_amount = 0;
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(&env, &loan_key);
+        Self::decrement_borrower_loan_count(&env, &borrower);
+        Self::remove_active_loan(&env, loan_id);
+
+        if collateral_to_release > 0 {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &borrower,
+                &collateral_to_release,
+            );
+            events::collateral_returned(&env, borrower.clone(), loan_id, collateral_to_release);
+        }
+        events::approval_expired(&env, loan_id, borrower);
+        Ok(())
+    }
+
+    /// Schedule `amount` of a pending loan's principal for release at
+    /// `release_ts` instead of at approval. Tranches together may not exceed
+    /// the principal; whatever is left unscheduled is paid out on approval as
+    /// usual. A tranche only becomes debt, and starts accruing interest, when
+    /// it is released; approval reserves pool liquidity for it until then.
+    /// Returns the tranche's index. Admin only.
+    pub fn add_tranche(
+        env: Env,
+        loan_id: u32,
+        amount: i128,
+        release_ts: u64,
+    ) -> Result<u32, LoanError> {
+        Self::admin(&env).require_auth();
+        let loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        if loan.status != LoanStatus::Pending {
+            return Err(LoanError::LoanNotPending);
+        }
+        if amount <= 0 {
+            return Err(LoanError::InvalidAmount);
+        }
+        if release_ts < env.ledger().timestamp() {
+            return Err(LoanError::TimestampInPast);
+        }
+
+        let mut tranches = Self::read_tranches(&env, loan_id);
+        let scheduled = Self::open_tranche_total(&tranches)
+            .checked_add(amount)
+            .ok_or(LoanError::AmountTooLarge)?;
+        if scheduled > loan.amount {
+            return Err(LoanError::InvalidAmount);
+        }
+
+        tranches.push_back(Tranche {
+            amount,
+            release_ts,
+            released: false,
+            cancelled: false,
+        });
+        Self::write_tranches(&env, loan_id, &tranches);
+
+        let index = tranches.len() - 1;
+        events::tranche_added(&env, loan_id, index, amount, release_ts);
+        Ok(index)
+    }
+
+    /// Pay tranche `index` of an approved loan out of the pool to the
+    /// borrower once its release time has passed, adding it to the loan's
+    /// principal from then on. Callable by anyone, since the funds can only
+    /// go to the borrower. While an escrowed disbursement is still unclaimed
+    /// the tranche stays locked.
+    pub fn release_tranche(env: Env, loan_id: u32, index: u32) -> Result<(), LoanError> {
+        use soroban_sdk::token::TokenClient;
+
+        Self::require_not_paused(&env)?;
+        Self::require_no_borrow_blackout(&env)?;
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        if loan.status != LoanStatus::Approved {
+            return Err(LoanError::LoanNotActive);
+        }
+
+        let mut tranches = Self::read_tranches(&env, loan_id);
+        let mut tranche = tranches.get(index).ok_or(LoanError::TrancheNotFound)?;
+        if tranche.released || tranche.cancelled {
+            return Err(LoanError::TrancheAlreadyReleased);
+        }
+        if env.ledger().timestamp() < tranche.release_ts
+            || Self::awaiting_disbursement(&env, loan_id)
+        {
+            return Err(LoanError::DisbursementLocked);
+        }
+
+        let lending_pool = Self::lending_pool(&env);
+        let pool_client = PoolClient::new(&env, &lending_pool);
+        if pool_client.pool_balance(&loan.token) < tranche.amount {
+            return Err(LoanError::InsufficientPoolLiquidity);
+        }
+
+        // Settle accrual on the principal released so far before it grows.
+        Self::current_total_debt(&env, &mut loan)?;
+        loan.amount = loan
+            .amount
+            .checked_add(tranche.amount)
+            .ok_or(LoanError::AmountTooLarge)?;
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(&env, &loan_key);
+        Self::adjust_total_outstanding(&env, &loan.token, tranche.amount);
+        Self::adjust_borrower_outstanding(&env, &loan.borrower, tranche.amount);
+        Self::adjust_tranche_reserve(&env, &loan.token, -tranche.amount);
+
+        tranche.released = true;
+        tranches.set(index, tranche.clone());
+        Self::write_tranches(&env, loan_id, &tranches);
+
+        TokenClient::new(&env, &loan.token).transfer(
+            &lending_pool,
+            &loan.borrower,
+            &tranche.amount,
+        );
+        pool_client.sync_utilization(&loan.token);
+
+        events::tranche_released(&env, loan_id, loan.borrower, index, tranche.amount);
+        Ok(())
+    }
+
+    /// The tranche schedule of `loan_id`; empty when its principal is paid
+    /// out in one go.
+    pub fn get_tranches(env: Env, loan_id: u32) -> Vec<Tranche> {
+        Self::read_tranches(&env, loan_id)
+    }
+
+    /// Ledger timestamp from which `loan_id`'s escrowed principal can be
+    /// claimed, or `None` if nothing is held back.
+    pub fn get_pending_disbursement(env: Env, loan_id: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyExt::PendingDisbursement(loan_id))
+    }
+
+    /// Upgrade `loan_id` to the current schema. Returns whether it needed it.
+    /// Reads already upgrade lazily; this lets operators migrate eagerly.
+    pub fn migrate_loan(env: Env, loan_id: u32) -> Result<bool, LoanError> {
+        Self::admin(&env).require_auth();
+        Self::read_loan_migrating(&env, loan_id)
+            .map(|(_, migrated)| migrated)
+            .ok_or(LoanError::LoanNotFound)
+    }
+
+    /// Upgrade loans `start_id..=end_id`, skipping ids that don't exist. At
+    /// most `MAX_MIGRATION_BATCH` ids are visited per call. Returns the
+    /// number of records upgraded.
+    pub fn migrate_loans_range(env: Env, start_id: u32, end_id: u32) -> Result<u32, LoanError> {
+        Self::admin(&env).require_auth();
+        if start_id > end_id || end_id - start_id >= Self::MAX_MIGRATION_BATCH {
+            return Err(LoanError::InvalidConfiguration);
+        }
+
+        let mut migrated_count = 0u32;
+        for loan_id in start_id..=end_id {
+            if let Some((_, true)) = Self::read_loan_migrating(&env, loan_id) {
+                migrated_count += 1;
+            }
+        }
+        Ok(migrated_count)
+    }
+
+    /// Loans with `from_ts <= created_at <= to_ts`, scanning ids
+    /// `start..start + limit`. `limit` is clamped to `MAX_MIGRATION_BATCH`;
+    /// callers resume from `start + limit` until past the last loan id
+    /// (`get_loan_id_base() + get_total_loans()`).
+    pub fn loans_in_window(
+        env: Env,
+        from_ts: u64,
+        to_ts: u64,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Loan>, LoanError> {
+        if from_ts > to_ts {
+            return Err(LoanError::InvalidConfiguration);
+        }
+        let limit = limit.min(Self::MAX_MIGRATION_BATCH);
+        let mut loans = Vec::new(&env);
+        for loan_id in start.max(1)..start.max(1).saturating_add(limit) {
+            let Some(loan) = Self::read_loan(&env, loan_id) else {
+                continue;
+            };
+            if loan.created_at >= from_ts && loan.created_at <= to_ts {
+                loans.push_back(loan);
+            }
+        }
+        Ok(loans)
+    }
+
+    pub fn get_loan(env: Env, loan_id: u32) -> Result<Loan, LoanError> {
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
         let _ = Self::current_total_debt(&env, &mut loan)?;
         Ok(loan)
@@ -1215,8 +2682,8 @@ impl LoanManager {
     ///
     /// Requires `borrower` authorization and the loan manager, lending pool,
     /// and NFT contract to be unpaused. The loan must be [`LoanStatus::Approved`]
-    /// and owned by `borrower`. The repayment is split proportionally across
-    /// principal, accrued interest, and accrued late fees; if the remaining debt
+    /// and owned by `borrower`. The repayment settles accrued late fees first,
+    /// then accrued interest, then principal; if the remaining debt
     /// is at or below the configured minimum repayment amount, the final payment
     /// may forgive rounding dust and mark the loan [`LoanStatus::Repaid`].
     ///
@@ -1227,24 +2694,45 @@ impl LoanManager {
     /// loan; [`LoanError::LoanNotActive`] when the loan is not approved;
     /// [`LoanError::LoanPastDue`] after the default window; [`LoanError::AmountTooLarge`]
     /// if interest accrual overflows; and [`LoanError::RepaymentExceedsDebt`]
-    /// when `amount` exceeds current debt.
-    pub fn repay(env: Env, borrower: Address, loan_id: u32, amount: i128) -> Result<(), LoanError> {
+    /// when `amount` exceeds current debt, unless overpayment refunds are
+    /// enabled, in which case only the debt is collected.
+    ///
+    /// When `idempotency_key` is set, a repeat call by the same borrower on
+    /// the same loan with the same key and amount is a no-op, so retried
+    /// submissions cannot be applied twice. Reusing the key with a different
+    /// amount returns [`LoanError::IdempotencyKeyReused`]. Keys are scoped to
+    /// the borrower and loan, so another account cannot claim them first.
+    pub fn repay(
+        env: Env,
+        borrower: Address,
+        loan_id: u32,
+        amount: i128,
+        idempotency_key: Option<BytesN<32>>,
+    ) -> Result<(), LoanError> {
         use soroban_sdk::token::TokenClient;
 
         borrower.require_auth();
         Self::require_not_paused(&env)?;
         Self::bump_instance_ttl(&env);
 
+        if let Some(key) = idempotency_key {
+            let seen_key = DataKey::SeenPayment(borrower.clone(), loan_id, key);
+            if let Some(seen_amount) = env.storage().persistent().get::<_, i128>(&seen_key) {
+                if seen_amount != amount {
+                    return Err(LoanError::IdempotencyKeyReused);
+                }
+                return Ok(());
+            }
+            env.storage().persistent().set(&seen_key, &amount);
+            Self::bump_persistent_ttl(&env, &seen_key);
+        }
+
         if amount <= 0 {
             return Err(LoanError::InvalidAmount);
         }
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.borrower != borrower {
@@ -1254,21 +2742,29 @@ impl LoanManager {
         if loan.status != LoanStatus::Approved {
             return Err(LoanError::LoanNotActive);
         }
+        if Self::awaiting_disbursement(&env, loan_id) {
+            return Err(LoanError::DisbursementLocked);
+        }
 
         // Allow repayment until end of default window
         let current_ledger = env.ledger().sequence();
         let default_ends = loan
             .due_date
-            .checked_add(Self::default_window_ledgers(&env))
+            .checked_add(Self::loan_default_window(&env, &loan))
             .expect("default window overflow");
         if current_ledger > default_ends {
             return Err(LoanError::LoanPastDue);
         }
 
         let (total_debt, late_fee_delta) = Self::current_total_debt(&env, &mut loan)?;
-        if amount > total_debt {
+        let amount = if amount <= total_debt {
+            amount
+        } else if Self::refund_overpayment(&env) {
+            // Only the debt is pulled from the borrower; the excess never moves.
+            total_debt
+        } else {
             return Err(LoanError::RepaymentExceedsDebt);
-        }
+        };
 
         let min_repayment_amount = Self::min_repayment_amount(&env);
 
@@ -1280,19 +2776,12 @@ impl LoanManager {
             panic!("repayment amount below minimum");
         }
 
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
-        let lending_pool: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LendingPool)
-            .expect("lending pool not set");
+        let token = loan.token.clone();
+        let lending_pool = Self::lending_pool(&env);
 
+        let open_principal = Self::remaining_principal(&loan);
         let (principal_payment, interest_payment, late_fee_payment) =
-            Self::proportional_repayment_split(&loan, amount);
+            Self::waterfall_repayment_split(&loan, amount);
 
         loan.interest_paid = loan
             .interest_paid
@@ -1318,7 +2807,7 @@ impl LoanManager {
         let was_late = env.ledger().sequence()
             > loan
                 .due_date
-                .checked_add(Self::grace_period_ledgers(&env))
+                .checked_add(Self::loan_grace_period(&env, &loan))
                 .expect("grace period overflow");
 
         let mut completed = false;
@@ -1345,10 +2834,24 @@ impl LoanManager {
             // A reentrant repay() on the same loan_id will now hit LoanNotActive and
             // revert, preventing double withdrawal of collateral.
             Self::adjust_total_outstanding(&env, &token, -loan.amount);
-            loan.status = LoanStatus::Repaid;
+            Self::set_status(&env, loan_id, &mut loan, LoanStatus::Repaid);
             Self::decrement_borrower_loan_count(&env, &loan.borrower);
+            Self::remove_active_loan(&env, loan_id);
         }
 
+        // Amortizing loans free collateral as the debt shrinks, keeping the
+        // remainder at the liquidation threshold ratio.
+        let collateral_release = if completed {
+            0
+        } else {
+            Self::excess_collateral(
+                loan.collateral_amount,
+                total_debt - amount,
+                Self::liquidation_threshold_bps(&env),
+            )
+        };
+        loan.collateral_amount -= collateral_release;
+
         // ── EFFECTS committed to storage before any cross-contract call ─────────
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(&env, &loan_key);
@@ -1356,59 +2859,74 @@ impl LoanManager {
         // ── INTERACTIONS: external calls after state is durable (#630) ───────────
         let token_client = TokenClient::new(&env, &token);
         token_client.transfer(&borrower, &lending_pool, &amount);
+        let pool_client = PoolClient::new(&env, &lending_pool);
+        pool_client.sync_utilization(&token);
+        if interest_payment > 0 {
+            pool_client.record_interest(&token, &interest_payment);
+        }
+        Self::adjust_borrower_outstanding(
+            &env,
+            &borrower,
+            Self::remaining_principal(&loan) - open_principal,
+        );
+
+        if collateral_release > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &loan.borrower,
+                &collateral_release,
+            );
+            events::collateral_returned(&env, loan.borrower.clone(), loan_id, collateral_release);
+        }
 
         if completed {
             // release_collateral_internal reads collateral from storage and performs
             // its own CEI, so it is safe to call after the loan state is committed.
             Self::release_collateral_internal(&env, loan_id, &loan.borrower);
-            // Emit terminal repayment event for completed loans.
-            events::loan_repaid(&env, borrower.clone(), loan_id, amount);
-        }
-
-        if amount >= 100 {
-            let nft_contract = Self::nft_contract(&env);
-            let nft_client = NftClient::new(&env, &nft_contract);
-            let borrower_score = nft_client.get_score(&borrower);
-            if borrower_score > 0 {
-                // get_score returns 0 for burned/non-existent NFTs
-                if completed && was_late {
-                    nft_client.decrease_score(
+        }
+
+        let point_units = Self::scale_amount(&env, Self::SCORE_POINT_UNITS);
+        let nft_client = NftClient::new(&env, &Self::nft_contract(&env));
+        let borrower_score = nft_client.get_score(&borrower);
+        let mut new_score = borrower_score;
+        // get_score returns 0 for burned/non-existent NFTs
+        if amount >= point_units && borrower_score > 0 {
+            if completed && was_late {
+                nft_client.decrease_score(
+                    &borrower,
+                    &Self::LATE_REPAYMENT_SCORE_PENALTY.unsigned_abs(),
+                    &Some(env.current_contract_address()),
+                );
+            } else {
+                // Use apply_score_delta rather than update_score so score adjustments
+                // work for any token denomination without hitting RemittanceNFT's
+                // anti-dust repayment floor (which assumes XLM stroops).
+                let points_i128 = amount / point_units;
+                let points_i32 = if points_i128 > i32::MAX as i128 {
+                    i32::MAX
+                } else if points_i128 <= 0 {
+                    0
+                } else {
+                    points_i128 as i32
+                };
+
+                if points_i32 > 0 {
+                    nft_client.apply_score_delta(
                         &borrower,
-                        &Self::LATE_REPAYMENT_SCORE_PENALTY.unsigned_abs(),
+                        &points_i32,
                         &Some(env.current_contract_address()),
                     );
-                } else {
-                    // Use apply_score_delta rather than update_score so score adjustments
-                    // work for any token denomination without hitting RemittanceNFT's
-                    // anti-dust repayment floor (which assumes XLM stroops).
-                    let points_i128 = amount / 100;
-                    let points_i32 = if points_i128 > i32::MAX as i128 {
-                        i32::MAX
-                    } else if points_i128 <= 0 {
-                        0
-                    } else {
-                        points_i128 as i32
-                    };
-
-                    if points_i32 > 0 {
-                        nft_client.apply_score_delta(
-                            &borrower,
-                            &points_i32,
-                            &Some(env.current_contract_address()),
-                        );
-                    }
                 }
             }
+            new_score = nft_client.get_score(&borrower);
         }
 
         if late_fee_delta > 0 {
             events::late_fee_charged(&env, loan_id, late_fee_delta);
         }
 
-        // Emit repayment event only if loan is not completed (completed loans emit in the block above)
-        if !completed {
-            events::loan_repaid(&env, borrower, loan_id, amount);
-        }
+        let score_delta = new_score as i32 - borrower_score as i32;
+        events::loan_repaid(&env, borrower, loan_id, amount, score_delta, new_score);
 
         Ok(())
     }
@@ -1437,11 +2955,7 @@ impl LoanManager {
         }
 
         let loan_key = DataKey::Loan(loan_id);
-        let loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Approved {
@@ -1458,22 +2972,14 @@ impl LoanManager {
         let nft_client = NftClient::new(&env, &nft_contract);
         if nft_client.is_seized(&loan.borrower) {
             return Err(LoanError::SeizedBorrower);
-        }
-
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
+        }
+
+        let token = loan.token.clone();
         let token_client = TokenClient::new(&env, &token);
         token_client.transfer(&loan.borrower, &env.current_contract_address(), &amount);
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .expect("loan not found");
+        let mut loan: Loan = Self::read_loan(&env, loan_id).expect("loan not found");
 
         let updated_collateral = loan
             .collateral_amount
@@ -1490,11 +2996,7 @@ impl LoanManager {
 
     pub fn release_collateral(env: Env, loan_id: u32) -> Result<(), LoanError> {
         let loan_key = DataKey::Loan(loan_id);
-        let loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Repaid {
@@ -1511,15 +3013,49 @@ impl LoanManager {
         Self::collateral_amount(&env, loan_id)
     }
 
+    /// Whether any of `borrower`'s open loans holds collateral. Scans the
+    /// borrower's loans newest first and stops once every open loan is seen.
+    pub fn has_locked_collateral(env: Env, borrower: Address) -> bool {
+        let mut open_loans = Self::borrower_loan_count(&env, &borrower);
+        let loan_ids = Self::borrower_loans(&env, &borrower);
+        for loan_id in loan_ids.iter().rev() {
+            if open_loans == 0 {
+                break;
+            }
+            let Some(loan) = Self::read_loan(&env, loan_id) else {
+                continue;
+            };
+            match loan.status {
+                LoanStatus::Approved if loan.collateral_amount > 0 => return true,
+                LoanStatus::Pending | LoanStatus::Approved => open_loans -= 1,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Whether `loan_id`'s borrower can post `collateral_amount` on it right
+    /// now, in the loan's token. Collateral already deposited on loans is
+    /// held by this contract, so only the borrower's free balance counts.
+    pub fn can_collateralize(
+        env: Env,
+        loan_id: u32,
+        collateral_amount: i128,
+    ) -> Result<bool, LoanError> {
+        use soroban_sdk::token::TokenClient;
+
+        let loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        if collateral_amount <= 0 {
+            return Ok(false);
+        }
+        Ok(TokenClient::new(&env, &loan.token).balance(&loan.borrower) >= collateral_amount)
+    }
+
     /// Returns whether `loan_id` is currently eligible for liquidation.
     /// Non-`Approved` loans always return `false`.
     pub fn is_liquidatable(env: Env, loan_id: u32) -> Result<bool, LoanError> {
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Approved {
@@ -1535,15 +3071,198 @@ impl LoanManager {
         ))
     }
 
+    /// Repay the exact debt of `loan_id` as of this ledger, including
+    /// interest and late fees accrued up to it, so the loan always closes.
+    /// Returns the amount pulled from `borrower`. Errors as `repay`, with
+    /// [`LoanError::LoanNotActive`] when the loan is not approved.
+    pub fn repay_full(env: Env, borrower: Address, loan_id: u32) -> Result<i128, LoanError> {
+        let owed = Self::get_amount_owed(env.clone(), loan_id)?;
+        if owed == 0 {
+            return Err(LoanError::LoanNotActive);
+        }
+        Self::repay(env, borrower, loan_id, owed, None)?;
+        Ok(owed)
+    }
+
+    /// Total amount currently owed on an approved loan, including interest
+    /// and late fees accrued up to this ledger. Returns 0 for inactive loans.
+    pub fn get_amount_owed(env: Env, loan_id: u32) -> Result<i128, LoanError> {
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        if loan.status != LoanStatus::Approved {
+            return Ok(0);
+        }
+
+        let (total_debt, _) = Self::current_total_debt(&env, &mut loan)?;
+        Ok(total_debt)
+    }
+
+    /// Project the amount owed on `loan_id` as of `future_ts`, including
+    /// interest and late fees that would accrue by then if nothing is repaid.
+    /// Accrual runs on ledgers, so the gap from now is converted at ~5s per
+    /// ledger. Nothing is written back to storage.
+    pub fn amount_owed_at(env: Env, loan_id: u32, future_ts: u64) -> Result<i128, LoanError> {
+        let now = env.ledger().timestamp();
+        if future_ts < now {
+            return Err(LoanError::TimestampInPast);
+        }
+
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        if loan.status != LoanStatus::Approved {
+            return Ok(0);
+        }
+
+        let ahead = (future_ts - now) / Self::SECONDS_PER_LEDGER;
+        let ledger = u32::try_from(ahead)
+            .ok()
+            .and_then(|ahead| env.ledger().sequence().checked_add(ahead))
+            .ok_or(LoanError::AmountTooLarge)?;
+        let (total_debt, _) = Self::total_debt_at_ledger(&env, &mut loan, ledger)?;
+        Ok(total_debt)
+    }
+
+    /// Everything the repay view needs about `loan_id` in one call. Debt
+    /// figures for approved loans include interest and late fees accrued up
+    /// to this ledger; nothing is written back to storage.
+    pub fn loan_summary(env: Env, loan_id: u32) -> Result<LoanSummary, LoanError> {
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        let total_owed = if loan.status == LoanStatus::Approved {
+            Self::current_total_debt(&env, &mut loan)?.0
+        } else {
+            0
+        };
+
+        Ok(LoanSummary {
+            loan_id,
+            borrower: loan.borrower.clone(),
+            status: loan.status.clone(),
+            principal: loan.amount,
+            principal_remaining: Self::remaining_principal(&loan),
+            interest_due: loan.accrued_interest,
+            late_fee_due: loan.accrued_late_fee,
+            total_owed,
+            total_paid: loan.principal_paid + loan.interest_paid + loan.late_fee_paid,
+            principal_paid: loan.principal_paid,
+            interest_paid: loan.interest_paid,
+            late_fee_paid: loan.late_fee_paid,
+            interest_rate_bps: loan.interest_rate_bps,
+            due_date: loan.due_date,
+            is_overdue: Self::is_overdue(&env, &loan),
+            memo: loan.memo.clone(),
+        })
+    }
+
+    /// `loan_summary` for a page of `borrower`'s loans, in the order they
+    /// were requested. `start` indexes the borrower's loan list and `limit`
+    /// is clamped to `MAX_MIGRATION_BATCH`; purged loans are skipped.
+    pub fn borrower_statement(
+        env: Env,
+        borrower: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<LoanSummary>, LoanError> {
+        let loan_ids = Self::borrower_loans(&env, &borrower);
+        let end = start
+            .saturating_add(limit.min(Self::MAX_MIGRATION_BATCH))
+            .min(loan_ids.len());
+        let mut statement = Vec::new(&env);
+        for index in start..end {
+            let loan_id = loan_ids.get_unchecked(index);
+            match Self::loan_summary(env.clone(), loan_id) {
+                Ok(summary) => statement.push_back(summary),
+                Err(LoanError::LoanNotFound) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(statement)
+    }
+
+    /// Check a page of the active loan book against the `TotalOutstanding`
+    /// counter for `token`, which tracks the principal of approved loans.
+    /// `start` indexes the active loan list and `limit` is clamped to
+    /// `MAX_MIGRATION_BATCH`. Monitors start with `carried = 0` and pass
+    /// each page's `summed` and `next_start` into the next call; the last
+    /// page's `holds` is the verdict for the whole book.
+    pub fn check_borrow_invariant(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+        carried: i128,
+    ) -> BorrowInvariant {
+        let count = Self::active_loan_count(&env);
+        let end = start
+            .saturating_add(limit.min(Self::MAX_MIGRATION_BATCH))
+            .min(count);
+        let mut summed = carried;
+        for index in start..end {
+            let Some(loan_id) = Self::active_loan_at(&env, index) else {
+                continue;
+            };
+            if let Some(loan) = Self::read_loan(&env, loan_id) {
+                if loan.status == LoanStatus::Approved && loan.token == token {
+                    summed = summed.checked_add(loan.amount).expect("sum overflow");
+                }
+            }
+        }
+
+        let total = Self::total_outstanding(&env, &token);
+        let next_start = if end < count { Some(end) } else { None };
+        BorrowInvariant {
+            summed,
+            next_start,
+            holds: if next_start.is_some() {
+                summed <= total
+            } else {
+                summed == total
+            },
+        }
+    }
+
+    /// Annualized simple interest rate of `loan_id`, in bps. Interest on a
+    /// loan accrues at `interest_rate_bps` per `DEFAULT_TERM_LEDGERS`
+    /// whatever its term, so annualizing the interest over the loan's term
+    /// reduces to scaling that rate to a year of ledgers. Saturates at
+    /// `u32::MAX`; compounding is not reflected.
+    pub fn effective_apr_bps(env: Env, loan_id: u32) -> Result<u32, LoanError> {
+        let loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &DataKey::Loan(loan_id));
+        let apr = loan.interest_rate_bps as i128 * Self::LEDGERS_PER_YEAR as i128
+            / Self::DEFAULT_TERM_LEDGERS as i128;
+        Ok(apr.min(u32::MAX as i128) as u32)
+    }
+
+    /// Seconds until `loan_id` is due, for a countdown: positive before the
+    /// due ledger, zero at it, and negative by the time overdue after it.
+    /// Estimated from the ledger delta at `SECONDS_PER_LEDGER`. Only approved
+    /// loans whose principal has been disbursed have a due date to count
+    /// down to.
+    pub fn time_to_due(env: Env, loan_id: u32) -> Result<i64, LoanError> {
+        let loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &DataKey::Loan(loan_id));
+        if loan.status != LoanStatus::Approved {
+            return Err(LoanError::LoanNotActive);
+        }
+        if loan.due_date == 0 {
+            return Err(LoanError::DisbursementLocked);
+        }
+        let ledgers = i64::from(loan.due_date) - i64::from(env.ledger().sequence());
+        Ok(ledgers * Self::SECONDS_PER_LEDGER as i64)
+    }
+
     /// Returns `(collateral, total_debt, current_ratio_bps)` for `loan_id`.
     /// Non-`Approved` loans return `(collateral, 0, 0)` without accruing debt.
     pub fn get_loan_health(env: Env, loan_id: u32) -> Result<(i128, i128, u32), LoanError> {
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Approved {
@@ -1578,11 +3297,7 @@ impl LoanManager {
         Self::require_not_paused(&env)?;
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Approved {
@@ -1603,10 +3318,7 @@ impl LoanManager {
         }
 
         let collateral_amount = loan.collateral_amount;
-        let configured_bonus = collateral_amount
-            .checked_mul(Self::liquidation_bonus_bps(&env) as i128)
-            .and_then(|value| value.checked_div(Self::MAX_RATIO_BPS as i128))
-            .expect("liquidation bonus overflow");
+        let configured_bonus = Self::mul_bps(collateral_amount, Self::liquidation_bonus_bps(&env));
 
         // Ensure bonus cap is enforced - bonus BPS should never exceed MAX_LIQUIDATION_BONUS_BPS
         debug_assert!(
@@ -1634,23 +3346,17 @@ impl LoanManager {
             (collateral_amount, 0, 0)
         };
 
+        let open_principal = Self::remaining_principal(&loan);
         Self::apply_debt_recovery(&mut loan, debt_repaid);
-        loan.status = LoanStatus::Liquidated;
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::Liquidated);
         loan.collateral_amount = 0;
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(&env, &loan_key);
         Self::decrement_borrower_loan_count(&env, &loan.borrower);
+        Self::remove_active_loan(&env, loan_id);
 
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
-        let lending_pool: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LendingPool)
-            .expect("lending pool not set");
+        let token = loan.token.clone();
+        let lending_pool = Self::lending_pool(&env);
         let token_client = TokenClient::new(&env, &token);
 
         if debt_repaid > 0 {
@@ -1670,6 +3376,7 @@ impl LoanManager {
                 &borrower_refund,
             );
         }
+        Self::adjust_borrower_outstanding(&env, &loan.borrower, -open_principal);
 
         events::loan_liquidated(
             &env,
@@ -1698,11 +3405,7 @@ impl LoanManager {
         borrower.require_auth();
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.borrower != borrower {
@@ -1714,18 +3417,14 @@ impl LoanManager {
 
         // Return collateral if any was posted
         let collateral_to_release = loan.collateral_amount;
-        loan.status = LoanStatus::Cancelled;
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::Cancelled);
         loan.collateral_amount = 0;
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if collateral_to_release > 0 {
             use soroban_sdk::token::TokenClient;
-            let token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::Token)
-                .expect("token not set");
+            let token = loan.token.clone();
             let token_client = TokenClient::new(&env, &token);
             token_client.transfer(
                 &env.current_contract_address(),
@@ -1739,15 +3438,36 @@ impl LoanManager {
         Ok(())
     }
 
+    /// Reprice a loan that has been requested but not yet approved. The new
+    /// rate must fall within the configured min/max rate bounds.
+    pub fn set_loan_interest(env: Env, loan_id: u32, new_bps: u32) -> Result<(), LoanError> {
+        Self::admin(&env).require_auth();
+
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        if loan.status != LoanStatus::Pending {
+            return Err(LoanError::LoanNotPending);
+        }
+        if new_bps < Self::min_rate_bps(&env) || new_bps > Self::max_rate_bps(&env) {
+            return Err(LoanError::InvalidRate);
+        }
+
+        let old_bps = loan.interest_rate_bps;
+        loan.interest_rate_bps = new_bps;
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(&env, &loan_key);
+        events::loan_repriced(&env, loan_id, old_bps, new_bps);
+
+        Ok(())
+    }
+
     pub fn reject_loan(env: Env, loan_id: u32, reason: String) -> Result<(), LoanError> {
         Self::admin(&env).require_auth();
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Pending {
@@ -1756,18 +3476,14 @@ impl LoanManager {
 
         // Return collateral if any was posted
         let collateral_to_release = loan.collateral_amount;
-        loan.status = LoanStatus::Rejected;
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::Rejected);
         loan.collateral_amount = 0;
         env.storage().persistent().set(&loan_key, &loan);
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if collateral_to_release > 0 {
             use soroban_sdk::token::TokenClient;
-            let token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::Token)
-                .expect("token not set");
+            let token = loan.token.clone();
             let token_client = TokenClient::new(&env, &token);
             token_client.transfer(
                 &env.current_contract_address(),
@@ -1786,6 +3502,52 @@ impl LoanManager {
         Ok(())
     }
 
+    /// Write off the unrecovered balance of a defaulted loan as bad debt.
+    /// Admin-only. Accrued interest and late fees are cleared, the remaining
+    /// debt is added to the running write-off total, and the loan moves to
+    /// [`LoanStatus::WrittenOff`], after which its remaining principal reads
+    /// as 0; `amount` keeps the original principal. The borrower's score
+    /// penalty was already applied when the loan defaulted, so none is
+    /// applied here.
+    pub fn write_off(env: Env, loan_id: u32) -> Result<i128, LoanError> {
+        Self::admin(&env).require_auth();
+
+        let loan_key = DataKey::Loan(loan_id);
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
+        if loan.status != LoanStatus::Defaulted {
+            return Err(LoanError::LoanNotDefaulted);
+        }
+
+        let written_off = Self::remaining_principal(&loan)
+            .checked_add(loan.accrued_interest)
+            .and_then(|value| value.checked_add(loan.accrued_late_fee))
+            .expect("debt overflow");
+        loan.accrued_interest = 0;
+        loan.accrued_late_fee = 0;
+        Self::set_status(&env, loan_id, &mut loan, LoanStatus::WrittenOff);
+        env.storage().persistent().set(&loan_key, &loan);
+        Self::bump_persistent_ttl(&env, &loan_key);
+
+        let total = Self::get_total_written_off(env.clone())
+            .checked_add(written_off)
+            .expect("write-off total overflow");
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalWrittenOff, &total);
+        Self::bump_instance_ttl(&env);
+
+        events::loan_written_off(&env, loan_id, loan.borrower, written_off);
+        Ok(written_off)
+    }
+
+    /// Cumulative debt written off via [`Self::write_off`].
+    pub fn get_total_written_off(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalWrittenOff)
+            .unwrap_or(0)
+    }
+
     /// Purge a loan in a terminal state (Repaid, Cancelled, Rejected) from
     /// on-chain storage. Admin-only. Removes the loan record and any
     /// residual collateral entry, then emits a `LoanPurged` event.
@@ -1793,11 +3555,7 @@ impl LoanManager {
         Self::admin(&env).require_auth();
 
         let loan_key = DataKey::Loan(loan_id);
-        let loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         match loan.status {
@@ -1845,11 +3603,7 @@ impl LoanManager {
         admin.require_auth();
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         // Borrower must also sign.
@@ -1858,12 +3612,15 @@ impl LoanManager {
         if loan.status != LoanStatus::Approved {
             return Err(LoanError::LoanNotActive);
         }
+        if Self::awaiting_disbursement(&env, loan_id) {
+            return Err(LoanError::DisbursementLocked);
+        }
 
         // Allow refinance until end of default window
         let current_ledger = env.ledger().sequence();
         let default_ends = loan
             .due_date
-            .checked_add(Self::default_window_ledgers(&env))
+            .checked_add(Self::loan_default_window(&env, &loan))
             .expect("default window overflow");
         if current_ledger > default_ends {
             return Err(LoanError::LoanPastDue);
@@ -1876,19 +3633,7 @@ impl LoanManager {
             return Err(LoanError::InvalidAmount);
         }
 
-        let min_term: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinTermLedgers)
-            .unwrap_or(0);
-        let max_term: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxTermLedgers)
-            .unwrap_or(u32::MAX);
-        if new_term < min_term || new_term > max_term {
-            return Err(LoanError::InvalidTerm);
-        }
+        Self::validate_term(&env, new_term)?;
 
         // Re-validate credit score (treat refinance like new loan application)
         let nft_contract: Address = env
@@ -1898,15 +3643,8 @@ impl LoanManager {
             .ok_or(LoanError::NotInitialized)?;
         let nft_client = NftClient::new(&env, &nft_contract);
 
-        let current_score = nft_client.get_score(&loan.borrower);
-        let min_score: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinScore)
-            .unwrap_or(500);
-        if current_score < min_score {
-            return Err(LoanError::InsufficientScore);
-        }
+        let current_score = nft_client.current_score(&loan.borrower);
+        Self::check_min_score(&env, current_score)?;
 
         // Validate collateral covers new amount (collateral must be >= loan amount)
         if loan.collateral_amount < new_amount {
@@ -1932,16 +3670,8 @@ impl LoanManager {
         // Adjust principal to new_amount.
         let remaining_principal = Self::remaining_principal(&loan);
 
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
-        let lending_pool: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LendingPool)
-            .expect("lending pool not set");
+        let token = loan.token.clone();
+        let lending_pool = Self::lending_pool(&env);
         let token_client = TokenClient::new(&env, &token);
 
         match new_amount.cmp(&remaining_principal) {
@@ -1992,11 +3722,13 @@ impl LoanManager {
             }
             core::cmp::Ordering::Equal => {}
         }
+        PoolClient::new(&env, &lending_pool).sync_utilization(&token);
 
         let outstanding_delta = new_amount
             .checked_sub(loan.amount)
             .expect("outstanding delta overflow");
         Self::adjust_total_outstanding(&env, &token, outstanding_delta);
+        Self::adjust_borrower_outstanding(&env, &loan.borrower, new_amount - remaining_principal);
 
         // Reset loan terms with new amount and rate.
         loan.amount = new_amount;
@@ -2034,6 +3766,26 @@ impl LoanManager {
         Self::late_fee_rate_bps(&env)
     }
 
+    /// Compound interest every `ledgers` ledgers. Zero restores simple interest.
+    pub fn set_compound_period(env: Env, ledgers: u32) -> Result<(), LoanError> {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+
+        let old_ledgers = Self::compound_period_ledgers(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::CompoundPeriodLedgers, &ledgers);
+        Self::bump_instance_ttl(&env);
+        events::compound_period_updated(&env, admin, old_ledgers, ledgers);
+
+        Ok(())
+    }
+
+    pub fn get_compound_period(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        Self::compound_period_ledgers(&env)
+    }
+
     pub fn set_liquidation_threshold(env: Env, ratio_bps: u32) -> Result<(), LoanError> {
         Self::validate_liquidation_threshold(ratio_bps)?;
         Self::admin(&env).require_auth();
@@ -2063,11 +3815,7 @@ impl LoanManager {
     }
 
     pub fn set_grace_period_ledgers(env: Env, ledgers: u32) -> Result<(), LoanError> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("not initialized");
+        let admin = Self::admin(&env);
         admin.require_auth();
 
         // Enforce invariant: default_window must be >= grace_period
@@ -2117,8 +3865,134 @@ impl LoanManager {
         Ok(())
     }
 
-    pub fn get_default_window_ledgers(env: Env) -> u32 {
-        Self::default_window_ledgers(&env)
+    pub fn get_default_window_ledgers(env: Env) -> u32 {
+        Self::default_window_ledgers(&env)
+    }
+
+    /// Cap on the per-loan grace borrowers may request in `request_loan`.
+    /// Defaults to the global default window.
+    pub fn set_max_loan_grace(env: Env, ledgers: u32) {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::MaxLoanGraceLedgers, &ledgers);
+        Self::bump_instance_ttl(&env);
+        events::max_loan_grace_updated(&env, admin, ledgers);
+    }
+
+    pub fn get_max_loan_grace(env: Env) -> u32 {
+        Self::max_loan_grace_ledgers(&env)
+    }
+
+    /// Bar borrowers with at least `max_defaults` recorded defaults from new
+    /// loans until `ban_ledgers` have passed since their last default.
+    /// A `max_defaults` of 0 disables the ban.
+    pub fn set_default_ban(env: Env, max_defaults: u32, ban_ledgers: u32) {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDefaults, &max_defaults);
+        env.storage()
+            .instance()
+            .set(&DataKey::DefaultBanLedgers, &ban_ledgers);
+        Self::bump_instance_ttl(&env);
+        events::default_ban_updated(&env, admin, max_defaults, ban_ledgers);
+    }
+
+    /// Seconds an approved loan's principal is held before the borrower can
+    /// claim it with `claim_disbursement`. 0 (the default) disburses on
+    /// approval.
+    pub fn set_disbursement_delay(env: Env, delay_secs: u64) {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::DisbursementDelaySecs, &delay_secs);
+        Self::bump_instance_ttl(&env);
+        events::disbursement_delay_updated(&env, admin, delay_secs);
+    }
+
+    pub fn get_disbursement_delay(env: Env) -> u64 {
+        Self::disbursement_delay(&env)
+    }
+
+    /// Seconds after its release time that an escrowed disbursement can
+    /// still be claimed; after that `expire_approval` cancels the loan.
+    /// 0 (the default) keeps approvals open indefinitely.
+    pub fn set_disbursement_claim_window(env: Env, window_secs: u64) {
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::DisbursementClaimWindow, &window_secs);
+        Self::bump_instance_ttl(&env);
+        events::disbursement_claim_window_updated(&env, admin, window_secs);
+    }
+
+    pub fn get_disbursement_claim_window(env: Env) -> u64 {
+        Self::disbursement_claim_window(&env)
+    }
+
+    /// Refuse new loan requests, approvals and disbursement claims while the
+    /// ledger timestamp is in `[from_ts, to_ts)`, e.g. for maintenance.
+    /// Repayments and everything else keep working. Setting an empty window
+    /// (`from_ts == to_ts`) clears it.
+    pub fn set_borrow_blackout(env: Env, from_ts: u64, to_ts: u64) -> Result<(), LoanError> {
+        if to_ts < from_ts {
+            return Err(LoanError::InvalidConfiguration);
+        }
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::BorrowBlackout, &(from_ts, to_ts));
+        Self::bump_instance_ttl(&env);
+        events::borrow_blackout_updated(&env, admin, from_ts, to_ts);
+        Ok(())
+    }
+
+    /// Returns `(from_ts, to_ts)`, or `(0, 0)` if no window was set.
+    pub fn get_borrow_blackout(env: Env) -> (u64, u64) {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::BorrowBlackout)
+            .unwrap_or((0, 0))
+    }
+
+    pub fn is_borrow_blackout(env: Env) -> bool {
+        let (from_ts, to_ts) = Self::get_borrow_blackout(env.clone());
+        let now = env.ledger().timestamp();
+        from_ts <= now && now < to_ts
+    }
+
+    /// Returns `(max_defaults, ban_ledgers)`.
+    pub fn get_default_ban(env: Env) -> (u32, u32) {
+        Self::bump_instance_ttl(&env);
+        let max_defaults = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxDefaults)
+            .unwrap_or(0);
+        let ban_ledgers = env
+            .storage()
+            .instance()
+            .get(&DataKey::DefaultBanLedgers)
+            .unwrap_or(0);
+        (max_defaults, ban_ledgers)
+    }
+
+    /// Ledger sequence until which `borrower` may not request new loans,
+    /// or 0 if they have not crossed the default threshold.
+    pub fn borrow_banned_until(env: Env, borrower: Address) -> u64 {
+        let (max_defaults, _) = Self::get_default_ban(env.clone());
+        if max_defaults == 0 {
+            return 0;
+        }
+        let nft_client = NftClient::new(&env, &Self::nft_contract(&env));
+        Self::ban_ends(&env, &borrower, nft_client.get_default_count(&borrower))
     }
 
     pub fn set_min_score(env: Env, min_score: u32) -> Result<(), LoanError> {
@@ -2171,11 +4045,7 @@ impl LoanManager {
             panic!("min repayment amount cannot be negative");
         }
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("not initialized");
+        let admin = Self::admin(&env);
         admin.require_auth();
 
         let old_amount = Self::min_repayment_amount(&env);
@@ -2190,6 +4060,139 @@ impl LoanManager {
         Self::min_repayment_amount(&env)
     }
 
+    /// Hold off all borrowing until the pool's deposits of the configured
+    /// token reach `threshold`. Once reached, borrowing stays enabled even if
+    /// deposits later drop. 0 (the default) disables the gate.
+    pub fn set_borrowing_threshold(env: Env, threshold: i128) -> Result<(), LoanError> {
+        Self::admin(&env).require_auth();
+        if threshold < 0 {
+            return Err(LoanError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::BorrowingEnabledThreshold, &threshold);
+        Self::bump_instance_ttl(&env);
+        events::borrowing_threshold_updated(&env, threshold);
+        Ok(())
+    }
+
+    pub fn get_borrowing_threshold(env: Env) -> i128 {
+        Self::bump_instance_ttl(&env);
+        Self::borrowing_threshold(&env)
+    }
+
+    pub fn is_borrowing_enabled(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        if env.storage().instance().has(&DataKeyExt::BorrowingEnabled) {
+            return true;
+        }
+        let threshold = Self::borrowing_threshold(&env);
+        threshold == 0
+            || PoolClient::new(&env, &Self::lending_pool(&env))
+                .get_total_deposits(&Self::token(&env))
+                >= threshold
+    }
+
+    /// Choose how `repay` treats payments above the amount owed: collect
+    /// only the debt (`true`) or reject with `RepaymentExceedsDebt` (`false`,
+    /// the default).
+    pub fn set_refund_overpayment(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::RefundOverpayment, &enabled);
+        Self::bump_instance_ttl(&env);
+        events::refund_overpayment_updated(&env, enabled);
+    }
+
+    pub fn get_refund_overpayment(env: Env) -> bool {
+        Self::refund_overpayment(&env)
+    }
+
+    /// Choose how accrued interest is rounded to whole units: `Floor`
+    /// (default) or round-half-up. Applies to interest accrued afterwards.
+    pub fn set_rounding_mode(env: Env, mode: RoundingMode) {
+        Self::admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::RoundingMode, &mode);
+        Self::bump_instance_ttl(&env);
+        events::rounding_mode_updated(&env, mode);
+    }
+
+    pub fn get_rounding_mode(env: Env) -> RoundingMode {
+        Self::rounding_mode(&env)
+    }
+
+    /// Number of loans currently in each status. Purged loans keep counting
+    /// under the terminal status they were purged from.
+    pub fn status_counts(env: Env) -> Map<LoanStatus, u32> {
+        Self::bump_instance_ttl(&env);
+        let mut counts = Map::new(&env);
+        for status in Self::ALL_STATUSES {
+            counts.set(status.clone(), Self::status_count(&env, status));
+        }
+        counts
+    }
+
+    /// Rebuild cached counters from loan records, paging through
+    /// `start_id..=end_id` (at most `MAX_MIGRATION_BATCH` ids per call).
+    /// A page starting at the first loan id (`get_loan_id_base() + 1`)
+    /// resets the per-status counters, so callers walk every page from
+    /// there up to the loan counter in order.
+    /// Borrowers seen in the page get their loan count recomputed from
+    /// their full loan list. Purged loans no longer exist and are not
+    /// counted. Returns the number of loans found in the page.
+    pub fn reindex_counters(env: Env, start_id: u32, end_id: u32) -> Result<u32, LoanError> {
+        Self::admin(&env).require_auth();
+        if start_id == 0 || start_id > end_id || end_id - start_id >= Self::MAX_MIGRATION_BATCH {
+            return Err(LoanError::InvalidConfiguration);
+        }
+
+        if start_id <= Self::loan_id_base(&env) + 1 {
+            for status in Self::ALL_STATUSES {
+                env.storage()
+                    .instance()
+                    .remove(&DataKey::StatusCount(status));
+            }
+        }
+
+        let mut found = 0u32;
+        let mut borrowers: Vec<Address> = Vec::new(&env);
+        for loan_id in start_id..=end_id {
+            let Some(loan) = Self::read_loan(&env, loan_id) else {
+                continue;
+            };
+            found += 1;
+            Self::adjust_status_count(&env, loan.status, 1);
+            if !borrowers.contains(&loan.borrower) {
+                borrowers.push_back(loan.borrower);
+            }
+        }
+
+        for borrower in borrowers.iter() {
+            let mut count = 0u32;
+            for loan_id in Self::borrower_loans(&env, &borrower).iter() {
+                if let Some(loan) = Self::read_loan(&env, loan_id) {
+                    if Self::holds_borrower_slot(&loan.status) {
+                        count += 1;
+                    }
+                }
+            }
+            let key = DataKey::BorrowerLoanCount(borrower.clone());
+            env.storage().persistent().set(&key, &count);
+            Self::bump_persistent_ttl(&env, &key);
+        }
+
+        Self::bump_instance_ttl(&env);
+        events::counters_reindexed(&env, start_id, end_id, found);
+        Ok(found)
+    }
+
+    /// Decimals of the loan token, captured at initialize. Contracts
+    /// initialized before this was recorded report the 7-decimal reference.
+    pub fn get_decimals(env: Env) -> u32 {
+        Self::token_decimals(&env)
+    }
+
     pub fn set_max_loans_per_borrower(env: Env, max_loans: u32) -> Result<(), LoanError> {
         if max_loans == 0 {
             return Err(LoanError::InvalidAmount);
@@ -2220,6 +4223,36 @@ impl LoanManager {
         Self::borrower_loan_count(&env, &borrower)
     }
 
+    /// Cap each borrower's open principal across all approved loans;
+    /// `request_loan` rejects a loan that would take the borrower above it.
+    /// 0 removes the cap.
+    pub fn set_max_outstanding_per_borrower(
+        env: Env,
+        max_outstanding: i128,
+    ) -> Result<(), LoanError> {
+        if max_outstanding < 0 {
+            return Err(LoanError::InvalidAmount);
+        }
+        let admin = Self::admin(&env);
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::MaxOutstandingPerBorrower, &max_outstanding);
+        Self::bump_instance_ttl(&env);
+        events::max_outstanding_per_borrower_updated(&env, admin, max_outstanding);
+        Ok(())
+    }
+
+    pub fn get_max_outstanding_per_borrower(env: Env) -> i128 {
+        Self::bump_instance_ttl(&env);
+        Self::max_outstanding_per_borrower(&env)
+    }
+
+    /// Open principal across `borrower`'s approved loans.
+    pub fn get_borrower_outstanding(env: Env, borrower: Address) -> i128 {
+        Self::borrower_outstanding(&env, &borrower)
+    }
+
     pub fn get_admin(env: Env) -> Address {
         Self::admin(&env)
     }
@@ -2230,7 +4263,12 @@ impl LoanManager {
     }
 
     pub fn get_total_loans(env: Env) -> u32 {
-        Self::loan_counter(&env)
+        Self::loan_counter(&env) - Self::loan_id_base(&env)
+    }
+
+    /// Loan ids start at `get_loan_id_base() + 1`.
+    pub fn get_loan_id_base(env: Env) -> u32 {
+        Self::loan_id_base(&env)
     }
 
     pub fn get_lending_pool(env: Env) -> Address {
@@ -2245,16 +4283,24 @@ impl LoanManager {
         Self::token(&env)
     }
 
+    /// Token the borrower must approve for repayments: the token `loan_id`
+    /// was disbursed in, or the configured token for new loans when
+    /// `loan_id` is `None`.
+    pub fn get_repay_token(env: Env, loan_id: Option<u32>) -> Result<Address, LoanError> {
+        match loan_id {
+            Some(loan_id) => Self::read_loan(&env, loan_id)
+                .map(|loan| loan.token)
+                .ok_or(LoanError::LoanNotFound),
+            None => Ok(Self::token(&env)),
+        }
+    }
+
     pub fn get_total_outstanding(env: Env, token: Address) -> i128 {
         Self::total_outstanding(&env, &token)
     }
 
     pub fn get_borrower_loans(env: Env, borrower: Address) -> Vec<u32> {
-        Self::bump_instance_ttl(&env);
-        env.storage()
-            .instance()
-            .get(&DataKey::BorrowerLoans(borrower))
-            .unwrap_or(Vec::new(&env))
+        Self::borrower_loans(&env, &borrower)
     }
 
     pub fn get_min_score(env: Env) -> u32 {
@@ -2265,6 +4311,25 @@ impl LoanManager {
             .unwrap_or(500)
     }
 
+    /// When `true`, a score exactly at the minimum fails the gate
+    /// (`score <= min` is rejected); by default only `score < min` is.
+    pub fn set_threshold_inclusive(env: Env, inclusive: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::ThresholdInclusive, &inclusive);
+        Self::bump_instance_ttl(&env);
+        events::threshold_inclusive_updated(&env, inclusive);
+    }
+
+    pub fn get_threshold_inclusive(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ThresholdInclusive)
+            .unwrap_or(false)
+    }
+
     pub fn set_interest_rate(env: Env, rate_bps: u32) -> Result<(), LoanError> {
         Self::admin(&env).require_auth();
         if rate_bps == 0 {
@@ -2301,6 +4366,163 @@ impl LoanManager {
         env.storage().instance().get(&DataKey::RateOracle)
     }
 
+    /// Set score-tier interest discounts as `min_score -> discount_bps`.
+    /// A borrower gets the discount of the highest tier they reach, taken
+    /// off the base rate when their loan's rate is locked in.
+    pub fn set_rate_discounts(env: Env, discounts: Map<u32, u32>) -> Result<(), LoanError> {
+        Self::admin(&env).require_auth();
+        for (_, discount_bps) in discounts.iter() {
+            if discount_bps > 10_000 {
+                return Err(LoanError::InvalidConfiguration);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RateDiscounts, &discounts);
+        Self::bump_instance_ttl(&env);
+        events::rate_discounts_updated(&env, discounts);
+        Ok(())
+    }
+
+    pub fn get_rate_discounts(env: Env) -> Map<u32, u32> {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::RateDiscounts)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Restrict `request_loan` to whitelisted borrowers. When disabled, the
+    /// whitelist is kept but not enforced.
+    pub fn set_whitelist_enabled(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::WhitelistEnabled, &enabled);
+        Self::bump_instance_ttl(&env);
+        events::whitelist_mode_updated(&env, enabled);
+    }
+
+    pub fn is_whitelist_enabled(env: Env) -> bool {
+        Self::whitelist_enabled(&env)
+    }
+
+    pub fn add_borrower(env: Env, borrower: Address) {
+        Self::admin(&env).require_auth();
+        let key = DataKey::BorrowerWhitelist(borrower.clone());
+        env.storage().persistent().set(&key, &true);
+        Self::bump_persistent_ttl(&env, &key);
+        events::borrower_whitelist_updated(&env, borrower, true);
+    }
+
+    pub fn remove_borrower(env: Env, borrower: Address) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BorrowerWhitelist(borrower.clone()));
+        events::borrower_whitelist_updated(&env, borrower, false);
+    }
+
+    /// Grant an address keeper rights: it may run operational calls such as
+    /// `check_default`, `check_defaults` and `sweep_defaults` but cannot
+    /// change configuration.
+    pub fn add_keeper(env: Env, keeper: Address) {
+        Self::admin(&env).require_auth();
+        let key = DataKey::Keeper(keeper.clone());
+        env.storage().persistent().set(&key, &true);
+        Self::bump_persistent_ttl(&env, &key);
+        let mut keepers = Self::keepers(&env);
+        if !keepers.contains(&keeper) {
+            keepers.push_back(keeper.clone());
+            Self::write_keepers(&env, &keepers);
+        }
+        events::keeper_updated(&env, keeper, true);
+    }
+
+    pub fn remove_keeper(env: Env, keeper: Address) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Keeper(keeper.clone()));
+        let mut keepers = Self::keepers(&env);
+        if let Some(index) = keepers.first_index_of(&keeper) {
+            keepers.remove(index);
+            Self::write_keepers(&env, &keepers);
+        }
+        events::keeper_updated(&env, keeper, false);
+    }
+
+    /// List keepers granted before the keeper list was kept, so `roles`
+    /// reports them. Keeper grants are stored per address and cannot be
+    /// enumerated, so the admin supplies the candidates; addresses that are
+    /// not keepers or are already listed are skipped. Returns the number
+    /// added.
+    pub fn backfill_keepers(env: Env, candidates: Vec<Address>) -> u32 {
+        Self::admin(&env).require_auth();
+        let mut keepers = Self::keepers(&env);
+        let mut added = 0u32;
+        for candidate in candidates.iter() {
+            if !keepers.contains(&candidate)
+                && env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Keeper(candidate.clone()))
+            {
+                keepers.push_back(candidate);
+                added += 1;
+            }
+        }
+        if added > 0 {
+            Self::write_keepers(&env, &keepers);
+        }
+        added
+    }
+
+    /// Snapshot of the admin, pending admin, keepers and NFT minters.
+    /// Keepers granted before the keeper list was kept appear once listed
+    /// with `backfill_keepers`.
+    pub fn roles(env: Env) -> RoleSet {
+        RoleSet {
+            admin: Self::admin(&env),
+            pending_admin: Self::get_proposed_admin(env.clone()),
+            keepers: Self::keepers(&env),
+            minters: NftClient::new(&env, &Self::nft_contract(&env)).get_authorized_minters(),
+        }
+    }
+
+    fn keepers(env: &Env) -> Vec<Address> {
+        let key = DataKeyExt::Keepers;
+        let keepers: Option<Vec<Address>> = env.storage().persistent().get(&key);
+        if keepers.is_some() {
+            Self::bump_persistent_ttl(env, &key);
+        }
+        keepers.unwrap_or(Vec::new(env))
+    }
+
+    fn write_keepers(env: &Env, keepers: &Vec<Address>) {
+        let key = DataKeyExt::Keepers;
+        env.storage().persistent().set(&key, keepers);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    pub fn is_keeper(env: Env, addr: Address) -> bool {
+        let key = DataKey::Keeper(addr);
+        if env.storage().persistent().has(&key) {
+            Self::bump_persistent_ttl(&env, &key);
+            return true;
+        }
+        false
+    }
+
+    pub fn is_whitelisted(env: Env, borrower: Address) -> bool {
+        let key = DataKey::BorrowerWhitelist(borrower);
+        if env.storage().persistent().has(&key) {
+            Self::bump_persistent_ttl(&env, &key);
+            return true;
+        }
+        false
+    }
+
     pub fn set_min_rate_bps(env: Env, min_rate: u32) -> Result<(), LoanError> {
         Self::admin(&env).require_auth();
 
@@ -2443,7 +4665,8 @@ impl LoanManager {
             .instance()
             .set(&DataKey::ProposedAdmin, &new_admin);
         Self::bump_instance_ttl(&env);
-        env.events().publish(
+        events::publish(
+            &env,
             (Symbol::new(&env, "AdminProposed"), current_admin),
             new_admin,
         );
@@ -2462,33 +4685,32 @@ impl LoanManager {
             .set(&DataKey::Admin, &proposed_admin);
         env.storage().instance().remove(&DataKey::ProposedAdmin);
         Self::bump_instance_ttl(&env);
-        env.events()
-            .publish((Symbol::new(&env, "AdminTransferred"),), proposed_admin);
+        events::admin_transferred(&env, proposed_admin);
         Ok(())
     }
 
     pub fn pause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         let paused_at_ledger = env.ledger().sequence();
         env.storage().instance().set(&DataKey::Paused, &true);
         env.storage()
             .instance()
             .set(&DataKey::PausedAtLedger, &paused_at_ledger);
         Self::bump_instance_ttl(&env);
-        events::paused(&env, paused_at_ledger);
-        env.events()
-            .publish((Symbol::new(&env, "ContractPaused"),), paused_at_ledger);
+        events::paused(&env, admin, paused_at_ledger);
+        events::contract_paused(&env, paused_at_ledger);
     }
 
     pub fn unpause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         let unpaused_at_ledger = env.ledger().sequence();
         env.storage().instance().set(&DataKey::Paused, &false);
         env.storage().instance().remove(&DataKey::PausedAtLedger);
         Self::bump_instance_ttl(&env);
-        events::unpaused(&env, unpaused_at_ledger);
-        env.events()
-            .publish((Symbol::new(&env, "ContractUnpaused"),), unpaused_at_ledger);
+        events::unpaused(&env, admin, unpaused_at_ledger);
+        events::contract_unpaused(&env, unpaused_at_ledger);
     }
 
     pub fn is_paused(env: Env) -> bool {
@@ -2507,53 +4729,24 @@ impl LoanManager {
             .unwrap_or(0)
     }
 
-    pub fn check_default(env: Env, loan_id: u32) -> Result<(), LoanError> {
-        Self::admin(&env).require_auth();
+    /// Default a single overdue loan. Callable by the admin or a keeper.
+    pub fn check_default(env: Env, caller: Address, loan_id: u32) -> Result<(), LoanError> {
+        Self::require_operator(&env, &caller)?;
         Self::require_not_paused(&env)?;
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         if loan.status != LoanStatus::Approved {
             return Err(LoanError::LoanNotActive);
         }
 
-        let current_ledger = env.ledger().sequence();
-        let default_eligible_after = loan
-            .due_date
-            .checked_add(Self::default_window_ledgers(&env))
-            .expect("default window overflow");
-        if current_ledger <= default_eligible_after {
+        if !Self::is_default_eligible(&env, &loan) {
             return Err(LoanError::LoanNotPastDue);
         }
 
-        loan.status = LoanStatus::Defaulted;
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("token not set");
-        Self::adjust_total_outstanding(&env, &token, -loan.amount);
-        env.storage().persistent().set(&loan_key, &loan);
-        Self::bump_persistent_ttl(&env, &loan_key);
-        Self::decrement_borrower_loan_count(&env, &loan.borrower);
-        Self::seize_collateral_internal(&env, loan_id);
-
-        let nft_contract = Self::nft_contract(&env);
-        let nft_client = NftClient::new(&env, &nft_contract);
-        nft_client.decrease_score(
-            &loan.borrower,
-            &Self::DEFAULT_SCORE_PENALTY_POINTS,
-            &Some(env.current_contract_address()),
-        );
-        nft_client.record_default(&loan.borrower, &Some(env.current_contract_address()));
-
-        events::loan_defaulted(&env, loan_id, loan.borrower.clone());
+        Self::mark_defaulted(&env, loan_id, loan);
         Ok(())
     }
 
@@ -2577,11 +4770,7 @@ impl LoanManager {
         }
 
         let loan_key = DataKey::Loan(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&loan_key)
-            .ok_or(LoanError::LoanNotFound)?;
+        let mut loan: Loan = Self::read_loan(&env, loan_id).ok_or(LoanError::LoanNotFound)?;
         Self::bump_persistent_ttl(&env, &loan_key);
 
         // Verify borrower matches
@@ -2593,12 +4782,15 @@ impl LoanManager {
         if loan.status != LoanStatus::Approved {
             return Err(LoanError::LoanNotActive);
         }
+        if Self::awaiting_disbursement(&env, loan_id) {
+            return Err(LoanError::DisbursementLocked);
+        }
 
         // Check if loan is past due (in default window)
         let current_ledger = env.ledger().sequence();
         let default_ends = loan
             .due_date
-            .checked_add(Self::default_window_ledgers(&env))
+            .checked_add(Self::loan_default_window(&env, &loan))
             .expect("default window overflow");
         if current_ledger > default_ends {
             return Err(LoanError::LoanPastDue);
@@ -2611,23 +4803,12 @@ impl LoanManager {
 
         // Calculate extension fee (1% of remaining principal)
         let remaining_principal = Self::remaining_principal(&loan);
-        let extension_fee = remaining_principal
-            .checked_mul(Self::EXTENSION_FEE_BPS as i128)
-            .and_then(|v| v.checked_div(10_000))
-            .expect("extension fee overflow");
+        let extension_fee = Self::mul_bps(remaining_principal, Self::EXTENSION_FEE_BPS);
 
         // Collect extension fee from borrower if any
         if extension_fee > 0 {
-            let token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::Token)
-                .expect("token not set");
-            let lending_pool: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::LendingPool)
-                .expect("lending pool not set");
+            let token = loan.token.clone();
+            let lending_pool = Self::lending_pool(&env);
             let token_client = TokenClient::new(&env, &token);
             token_client.transfer(&borrower, &lending_pool, &extension_fee);
         }
@@ -2662,14 +4843,16 @@ impl LoanManager {
         Ok(())
     }
 
-    pub fn check_defaults(env: Env, loan_ids: Vec<u32>) -> Result<u32, LoanError> {
-        Self::admin(&env).require_auth();
+    /// Default every eligible loan in `loan_ids`. Callable by the admin or a
+    /// keeper.
+    pub fn check_defaults(env: Env, caller: Address, loan_ids: Vec<u32>) -> Result<u32, LoanError> {
+        Self::require_operator(&env, &caller)?;
         Self::require_not_paused(&env)?;
         let mut defaulted_count = 0u32;
 
         for loan_id in loan_ids.iter() {
             let loan_key = DataKey::Loan(loan_id);
-            let mut loan: Loan = match env.storage().persistent().get(&loan_key) {
+            let loan: Loan = match Self::read_loan(&env, loan_id) {
                 Some(l) => l,
                 None => continue,
             };
@@ -2679,37 +4862,11 @@ impl LoanManager {
                 continue;
             }
 
-            let current_ledger = env.ledger().sequence();
-            let default_eligible_after = loan
-                .due_date
-                .checked_add(Self::default_window_ledgers(&env))
-                .expect("default window overflow");
-            if current_ledger <= default_eligible_after {
+            if !Self::is_default_eligible(&env, &loan) {
                 continue;
             }
 
-            loan.status = LoanStatus::Defaulted;
-            let token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::Token)
-                .expect("token not set");
-            Self::adjust_total_outstanding(&env, &token, -loan.amount);
-            env.storage().persistent().set(&loan_key, &loan);
-            Self::bump_persistent_ttl(&env, &loan_key);
-            Self::decrement_borrower_loan_count(&env, &loan.borrower);
-            Self::seize_collateral_internal(&env, loan_id);
-
-            let nft_contract = Self::nft_contract(&env);
-            let nft_client = NftClient::new(&env, &nft_contract);
-            nft_client.decrease_score(
-                &loan.borrower,
-                &Self::DEFAULT_SCORE_PENALTY_POINTS,
-                &Some(env.current_contract_address()),
-            );
-            nft_client.record_default(&loan.borrower, &Some(env.current_contract_address()));
-
-            events::loan_defaulted(&env, loan_id, loan.borrower.clone());
+            Self::mark_defaulted(&env, loan_id, loan);
             defaulted_count = defaulted_count
                 .checked_add(1)
                 .expect("defaulted count overflow");
@@ -2717,6 +4874,116 @@ impl LoanManager {
 
         Ok(defaulted_count)
     }
+
+    /// Default up to `max` overdue loans from the active-loan index in one call.
+    /// Callable by the admin or a keeper. `max` is clamped to `MAX_SWEEP_BATCH`, and at most
+    /// `MAX_SWEEP_SCAN` index entries are visited, resuming where the previous
+    /// sweep stopped and wrapping at the end, so repeated calls cover a book
+    /// of any size. Returns the number of loans defaulted.
+    pub fn sweep_defaults(env: Env, caller: Address, max: u32) -> Result<u32, LoanError> {
+        Self::require_operator(&env, &caller)?;
+        Self::require_not_paused(&env)?;
+        let limit = max.min(Self::MAX_SWEEP_BATCH);
+        let scan_limit = Self::active_loan_count(&env).min(Self::MAX_SWEEP_SCAN);
+        let mut position: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKeyExt::SweepCursor)
+            .unwrap_or(0);
+        let mut defaulted_count = 0u32;
+        let mut scanned = 0u32;
+
+        while scanned < scan_limit && defaulted_count < limit {
+            let count = Self::active_loan_count(&env);
+            if count == 0 {
+                break;
+            }
+            if position >= count {
+                position = 0;
+            }
+            scanned += 1;
+
+            let Some(loan_id) = Self::active_loan_at(&env, position) else {
+                position += 1;
+                continue;
+            };
+            let loan = match Self::read_loan(&env, loan_id) {
+                Some(loan)
+                    if loan.status == LoanStatus::Approved
+                        && Self::is_default_eligible(&env, &loan) =>
+                {
+                    loan
+                }
+                _ => {
+                    position += 1;
+                    continue;
+                }
+            };
+
+            // mark_defaulted moves the last entry into this slot, so the
+            // position is revisited rather than advanced.
+            Self::mark_defaulted(&env, loan_id, loan);
+            defaulted_count += 1;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::SweepCursor, &position);
+        Ok(defaulted_count)
+    }
+
+    /// Index approved loans `start_id..=end_id` that are missing from the
+    /// active-loan index, e.g. loans approved before it existed, and drop the
+    /// legacy single-Vec index. At most `MAX_MIGRATION_BATCH` ids are visited
+    /// per call. Returns the number of loans indexed.
+    pub fn index_active_loans_range(
+        env: Env,
+        start_id: u32,
+        end_id: u32,
+    ) -> Result<u32, LoanError> {
+        Self::admin(&env).require_auth();
+        if start_id > end_id || end_id - start_id >= Self::MAX_MIGRATION_BATCH {
+            return Err(LoanError::InvalidConfiguration);
+        }
+        env.storage().persistent().remove(&DataKey::ActiveLoans);
+
+        let mut indexed = 0u32;
+        for loan_id in start_id..=end_id {
+            let Some(loan) = Self::read_loan(&env, loan_id) else {
+                continue;
+            };
+            if loan.status == LoanStatus::Approved
+                && !env
+                    .storage()
+                    .persistent()
+                    .has(&DataKeyExt::ActiveLoanSlot(loan_id))
+            {
+                Self::add_active_loan(&env, loan_id);
+                indexed += 1;
+            }
+        }
+        Ok(indexed)
+    }
+
+    /// Loan ids at positions `start..start + limit` of the active-loan index.
+    /// `limit` is clamped to `MAX_MIGRATION_BATCH`. Order is not stable:
+    /// removing a loan moves the last entry into its slot.
+    pub fn get_active_loans(env: Env, start: u32, limit: u32) -> Vec<u32> {
+        let end = start
+            .saturating_add(limit.min(Self::MAX_MIGRATION_BATCH))
+            .min(Self::active_loan_count(&env));
+        let mut loans = Vec::new(&env);
+        for position in start..end {
+            if let Some(loan_id) = Self::active_loan_at(&env, position) {
+                loans.push_back(loan_id);
+            }
+        }
+        loans
+    }
+
+    pub fn get_active_loan_count(env: Env) -> u32 {
+        Self::active_loan_count(&env)
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,13 @@
-use crate::{DataKey, Loan, LoanError, LoanManager, LoanManagerClient, LoanStatus};
+use crate::{
+    DataKey, DataKeyExt, Loan, LoanError, LoanManager, LoanManagerClient, LoanStatus, RoundingMode,
+};
 use lending_pool::{LendingPool, LendingPoolClient};
 use remittance_nft::{RemittanceNFT, RemittanceNFTClient};
 use soroban_sdk::testutils::{Events, Ledger as _};
 use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
 use soroban_sdk::{
-    contract, contractimpl, testutils::Address as _, Address, BytesN, Env, FromVal, String,
+    contract, contractimpl, contracttype, testutils::Address as _, Address, BytesN, Env, FromVal,
+    IntoVal, String,
 };
 
 // Mock RateOracle contract for testing the oracle interest-rate code path.
@@ -22,6 +25,21 @@ impl MockRateOracle {
     }
 }
 
+// Minimal token exposing only `decimals`, enough for LoanManager::initialize.
+#[contract]
+pub struct MockDecimalsToken;
+
+#[contractimpl]
+impl MockDecimalsToken {
+    pub fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&"decimals").unwrap_or(7)
+    }
+
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&"decimals", &decimals);
+    }
+}
+
 fn setup_test<'a>(
     env: &Env,
 ) -> (
@@ -55,7 +73,14 @@ fn setup_test<'a>(
     nft_client.authorize_minter(&loan_manager_id);
 
     // 5. Initialize the Loan Manager with the NFT contract, lending pool, token, and admin
-    loan_manager_client.initialize(&nft_contract_id, &pool_contract_id, &token_id, &admin);
+    loan_manager_client.initialize(
+        &nft_contract_id,
+        &pool_contract_id,
+        &token_id,
+        &admin,
+        &None,
+        &None,
+    );
 
     // Disable dust spam protection for the loan manager tests
     nft_client.set_min_repayment_amount(&0);
@@ -201,7 +226,9 @@ fn test_migration_guard_prevents_double_execution() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // First migration should succeed
@@ -245,7 +272,9 @@ fn test_loan_request_success() {
     );
 
     // Should succeed and return loan_id
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     assert_eq!(loan_id, 1);
 
     // Verify loan was created with Pending status
@@ -277,7 +306,7 @@ fn test_loan_request_failure_low_score() {
     );
 
     // Should panic
-    manager.request_loan(&borrower, &1000, &17280);
+    manager.request_loan(&borrower, &1000, &17280, &None, &None, &None, &None);
 }
 
 #[test]
@@ -304,7 +333,9 @@ fn test_approve_loan_flow() {
     stellar_token.mint(&pool_client, &10000);
 
     // 3. Request a loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     // 4. Verify loan is pending
     let loan = manager.get_loan(&loan_id);
@@ -339,7 +370,9 @@ fn test_approve_loan_fails_when_pool_has_insufficient_liquidity() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let result = manager.try_approve_loan(&loan_id);
     assert_eq!(result, Err(Ok(LoanError::InsufficientPoolLiquidity)));
 
@@ -375,8 +408,12 @@ fn test_approve_loan_accounts_for_outstanding_approved_loans() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let first_loan = manager.request_loan(&borrower_one, &6_000, &17280);
-    let second_loan = manager.request_loan(&borrower_two, &6_000, &17280);
+    let first_loan = manager
+        .request_loan(&borrower_one, &6_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let second_loan = manager
+        .request_loan(&borrower_two, &6_000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     manager.approve_loan(&first_loan);
     let second_result = manager.try_approve_loan(&second_loan);
@@ -403,7 +440,9 @@ fn test_cancel_pending_loan() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.cancel_loan(&borrower, &loan_id);
 
     let loan = manager.get_loan(&loan_id);
@@ -427,7 +466,9 @@ fn test_reject_pending_loan() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.reject_loan(&loan_id, &String::from_str(&env, "manual review failed"));
 
     let loan = manager.get_loan(&loan_id);
@@ -460,21 +501,28 @@ fn test_paused_blocks_new_loans_and_repayments_but_allows_collateral_release() {
     stellar_token.mint(&borrower, &5_000);
 
     // Loan A: pending loan that should be cancellable even while paused.
-    let loan_a = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_a = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     // Loan B: approve before pausing so we can verify repay is blocked while paused.
-    let loan_b = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_b = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_b);
 
     // Loan C: pending loan used to verify approvals are blocked while paused.
-    let loan_c = manager.request_loan(&borrower, &500, &17280);
+    let loan_c = manager
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     // Pause the contract.
     manager.pause();
     assert!(manager.is_paused());
 
     // New loan requests are blocked.
-    let blocked_request = manager.try_request_loan(&borrower, &500, &17280);
+    let blocked_request =
+        manager.try_request_loan(&borrower, &500, &17280, &None, &None, &None, &None);
     assert_eq!(blocked_request, Err(Ok(LoanError::ContractPaused)));
 
     // Approvals are blocked.
@@ -482,7 +530,7 @@ fn test_paused_blocks_new_loans_and_repayments_but_allows_collateral_release() {
     assert_eq!(blocked_approve, Err(Ok(LoanError::ContractPaused)));
 
     // Repayments are blocked.
-    let blocked_repay = manager.try_repay(&borrower, &loan_b, &100);
+    let blocked_repay = manager.try_repay(&borrower, &loan_b, &100, &None);
     assert_eq!(blocked_repay, Err(Ok(LoanError::ContractPaused)));
 
     // Existing borrower-initiated cleanup should still work while paused (cancel pending loan).
@@ -496,7 +544,7 @@ fn test_paused_blocks_new_loans_and_repayments_but_allows_collateral_release() {
     assert!(!manager.is_paused());
 
     // Now repay should succeed (partial repay).
-    manager.repay(&borrower, &loan_b, &100);
+    manager.repay(&borrower, &loan_b, &100, &None);
 }
 
 #[test]
@@ -552,7 +600,9 @@ fn test_cancel_pending_loan_returns_collateral() {
     let _borrower_balance_before = token_client.balance(&borrower);
     let _contract_balance_before = token_client.balance(&manager.address);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     env.as_contract(&manager.address, || {
         let loan_key = DataKey::Loan(loan_id);
         let mut loan: Loan = env.storage().persistent().get(&loan_key).unwrap();
@@ -590,7 +640,9 @@ fn test_reject_pending_loan_returns_collateral() {
 
     let borrower_balance_before = token_client.balance(&borrower);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     env.as_contract(&manager.address, || {
         let loan_key = DataKey::Loan(loan_id);
         let mut loan: Loan = env.storage().persistent().get(&loan_key).unwrap();
@@ -663,7 +715,9 @@ fn test_configurable_interest_rate_and_default_term() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &20_000);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &20_000, &None, &None, &None, &None)
+        .loan_id;
     let pending_loan = manager.get_loan(&loan_id);
     assert_eq!(pending_loan.interest_rate_bps, 1_800);
 
@@ -713,7 +767,9 @@ fn test_legacy_zero_interest_config_falls_back_to_default() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let pending_loan = manager.get_loan(&loan_id);
     assert_eq!(pending_loan.interest_rate_bps, 1_200);
 }
@@ -742,13 +798,15 @@ fn test_repayment_flow() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     env.ledger()
         .set_sequence_number(env.ledger().sequence() + 2_000);
 
-    manager.repay(&borrower, &loan_id, &500);
+    manager.repay(&borrower, &loan_id, &500, &None);
 
     let loan = manager.get_loan(&loan_id);
     assert!(loan.principal_paid > 0);
@@ -760,7 +818,7 @@ fn test_repayment_flow() {
         - loan.principal_paid
         - loan.interest_paid
         - loan.late_fee_paid;
-    manager.repay(&borrower, &loan_id, &remaining_debt);
+    manager.repay(&borrower, &loan_id, &remaining_debt, &None);
     let completed = manager.get_loan(&loan_id);
     assert_eq!(completed.status, LoanStatus::Repaid);
 
@@ -768,6 +826,39 @@ fn test_repayment_flow() {
     assert_eq!(nft_client.get_score(&borrower), 610);
 }
 
+#[test]
+fn test_repayment_points_respect_the_nft_points_cap() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &10_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &10_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    // 5_000 repaid would be 50 points; the NFT caps each award at 3.
+    nft_client.set_max_points_per_loan(&3);
+    manager.repay(&borrower, &loan_id, &5_000, &None);
+    assert_eq!(nft_client.get_score(&borrower), 603);
+
+    nft_client.set_max_points_per_loan(&0);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
+    assert_eq!(nft_client.get_score(&borrower), 613);
+}
+
 #[test]
 fn test_partial_repayment_tracks_split_balances() {
     let env = Env::default();
@@ -790,10 +881,12 @@ fn test_partial_repayment_tracks_split_balances() {
     stellar_token.mint(&borrower, &2_000_000);
 
     manager.set_max_loan_amount(&1_000_000);
-    let loan_id = manager.request_loan(&borrower, &1_000_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
-    manager.repay(&borrower, &loan_id, &400_000);
+    manager.repay(&borrower, &loan_id, &400_000, &None);
 
     let after_partial = manager.get_loan(&loan_id);
     assert!(after_partial.principal_paid > 0);
@@ -828,11 +921,13 @@ fn test_minimum_repayment_amount_enforced() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     manager.set_min_repayment_amount(&150);
-    manager.repay(&borrower, &loan_id, &100);
+    manager.repay(&borrower, &loan_id, &100, &None);
 }
 
 #[test]
@@ -858,11 +953,13 @@ fn test_full_repayment_ignores_minimum_amount() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     manager.set_min_repayment_amount(&150);
-    manager.repay(&borrower, &loan_id, &1_000);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
 
     let loan = manager.get_loan(&loan_id);
     assert_eq!(loan.status, LoanStatus::Repaid);
@@ -886,7 +983,7 @@ fn test_request_loan_above_max_amount_fails() {
     );
     manager.set_max_loan_amount(&500);
 
-    let result = manager.try_request_loan(&borrower, &600, &17280);
+    let result = manager.try_request_loan(&borrower, &600, &17280, &None, &None, &None, &None);
     assert_eq!(result, Err(Ok(LoanError::InvalidAmount)));
 }
 
@@ -912,11 +1009,13 @@ fn test_small_repayment_does_not_change_score() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     manager.set_min_repayment_amount(&1);
-    manager.repay(&borrower, &loan_id, &99);
+    manager.repay(&borrower, &loan_id, &99, &None);
 
     assert_eq!(nft_client.get_score(&borrower), 600);
 }
@@ -942,7 +1041,9 @@ fn test_late_full_repayment_applies_score_penalty() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let due_date = manager.get_loan(&loan_id).due_date;
@@ -951,7 +1052,7 @@ fn test_late_full_repayment_applies_score_penalty() {
 
     let loan = manager.get_loan(&loan_id);
     let payoff = loan.amount + loan.accrued_interest + loan.accrued_late_fee;
-    manager.repay(&borrower, &loan_id, &payoff);
+    manager.repay(&borrower, &loan_id, &payoff, &None);
 
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
     assert_eq!(nft_client.get_score(&borrower), 590);
@@ -967,7 +1068,7 @@ fn test_access_controls_unauthorized_repay() {
     let borrower = Address::generate(&env);
 
     // Attempting to repay without proper Authorization scope should panic natively.
-    manager.repay(&borrower, &1, &500);
+    manager.repay(&borrower, &1, &500, &None);
 }
 
 #[test]
@@ -1005,7 +1106,9 @@ fn test_approve_already_approved_loan() {
     stellar_token.mint(&pool_client, &10000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Try to approve again - should panic
@@ -1033,7 +1136,9 @@ fn test_approve_loan_insufficient_pool_liquidity() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &100);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let result = manager.try_approve_loan(&loan_id);
     assert_eq!(result, Err(Ok(LoanError::InsufficientPoolLiquidity)));
 }
@@ -1061,17 +1166,23 @@ fn test_borrower_max_active_loans_enforced_and_released_on_repay() {
 
     manager.set_max_loans_per_borrower(&2);
 
-    let loan_1 = manager.request_loan(&borrower, &1000, &17280);
-    let loan_2 = manager.request_loan(&borrower, &1500, &17280);
+    let loan_1 = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_2 = manager
+        .request_loan(&borrower, &1500, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_1);
     manager.approve_loan(&loan_2);
     assert_eq!(manager.get_borrower_loan_count(&borrower), 2);
 
-    manager.repay(&borrower, &loan_1, &1000);
+    manager.repay(&borrower, &loan_1, &1000, &None);
     assert_eq!(manager.get_loan(&loan_1).status, LoanStatus::Repaid);
     assert_eq!(manager.get_borrower_loan_count(&borrower), 1);
 
-    let loan_3 = manager.request_loan(&borrower, &500, &17280);
+    let loan_3 = manager
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
     assert_eq!(loan_3, 3);
 }
 
@@ -1098,13 +1209,17 @@ fn test_borrower_max_active_loans_blocks_new_requests() {
 
     manager.set_max_loans_per_borrower(&2);
 
-    let loan_1 = manager.request_loan(&borrower, &1000, &17280);
-    let loan_2 = manager.request_loan(&borrower, &1500, &17280);
+    let loan_1 = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_2 = manager
+        .request_loan(&borrower, &1500, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_1);
     manager.approve_loan(&loan_2);
     assert_eq!(manager.get_borrower_loan_count(&borrower), 2);
 
-    manager.request_loan(&borrower, &500, &17280);
+    manager.request_loan(&borrower, &500, &17280, &None, &None, &None, &None);
 }
 
 #[test]
@@ -1125,7 +1240,7 @@ fn test_request_loan_negative_amount() {
         &None,
     );
 
-    manager.request_loan(&borrower, &-1000, &17280);
+    manager.request_loan(&borrower, &-1000, &17280, &None, &None, &None, &None);
 }
 
 #[test]
@@ -1133,7 +1248,7 @@ fn test_check_default_success() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1148,7 +1263,9 @@ fn test_check_default_success() {
     let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     assert!(!nft_client.is_seized(&borrower));
@@ -1158,7 +1275,7 @@ fn test_check_default_success() {
     env.ledger()
         .set_sequence_number(due_date + default_window + 1);
 
-    manager.check_default(&loan_id);
+    manager.check_default(&admin, &loan_id);
 
     let loan = manager.get_loan(&loan_id);
     assert_eq!(loan.status, LoanStatus::Defaulted);
@@ -1174,7 +1291,7 @@ fn test_check_default_not_past_due() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1189,10 +1306,12 @@ fn test_check_default_not_past_due() {
     let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
-    manager.check_default(&loan_id);
+    manager.check_default(&admin, &loan_id);
 }
 
 #[test]
@@ -1201,7 +1320,7 @@ fn test_check_default_already_repaid() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1217,15 +1336,17 @@ fn test_check_default_already_repaid() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
-    manager.repay(&borrower, &loan_id, &1000);
+    manager.repay(&borrower, &loan_id, &1000, &None);
 
     env.ledger()
         .set_sequence_number(env.ledger().sequence() + 40_000);
 
-    manager.check_default(&loan_id);
+    manager.check_default(&admin, &loan_id);
 }
 
 #[test]
@@ -1233,7 +1354,7 @@ fn test_check_default_respects_default_window() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1249,13 +1370,15 @@ fn test_check_default_respects_default_window() {
     stellar_token.mint(&pool_client, &10_000);
 
     manager.set_default_window_ledgers(&10_000);
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let due_date = manager.get_loan(&loan_id).due_date;
     env.ledger().set_sequence_number(due_date + 9_999);
 
-    let result = manager.try_check_default(&loan_id);
+    let result = manager.try_check_default(&admin, &loan_id);
     assert_eq!(result, Err(Ok(LoanError::LoanNotPastDue)));
 }
 
@@ -1264,7 +1387,7 @@ fn test_check_defaults_batch() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
     let borrower3 = Address::generate(&env);
@@ -1295,10 +1418,18 @@ fn test_check_defaults_batch() {
     let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &100_000);
 
-    let loan_id1 = manager.request_loan(&borrower1, &1000, &17280);
-    let loan_id2 = manager.request_loan(&borrower2, &1000, &17280);
-    let loan_id3 = manager.request_loan(&borrower3, &1000, &17280);
-    let loan_id4 = manager.request_loan(&borrower3, &1000, &17280);
+    let loan_id1 = manager
+        .request_loan(&borrower1, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id2 = manager
+        .request_loan(&borrower2, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id3 = manager
+        .request_loan(&borrower3, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id4 = manager
+        .request_loan(&borrower3, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     manager.approve_loan(&loan_id1);
     manager.approve_loan(&loan_id2);
@@ -1310,7 +1441,7 @@ fn test_check_defaults_batch() {
         .set_sequence_number(due_date + default_window + 1);
 
     let loan_ids = soroban_sdk::vec![&env, loan_id1, loan_id2, loan_id3, loan_id4, 999];
-    let defaulted_count = manager.check_defaults(&loan_ids);
+    let defaulted_count = manager.check_defaults(&admin, &loan_ids);
     assert_eq!(defaulted_count, 3);
 
     assert_eq!(manager.get_loan(&loan_id1).status, LoanStatus::Defaulted);
@@ -1331,10 +1462,10 @@ fn test_check_defaults_empty_batch_returns_zero() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, _nft_client, _pool_client, _token_id, _token_admin) = setup_test(&env);
+    let (manager, _nft_client, _pool_client, _token_id, admin) = setup_test(&env);
 
     let loan_ids = soroban_sdk::vec![&env];
-    let defaulted_count = manager.check_defaults(&loan_ids);
+    let defaulted_count = manager.check_defaults(&admin, &loan_ids);
 
     assert_eq!(defaulted_count, 0);
 }
@@ -1344,7 +1475,7 @@ fn test_check_defaults_all_ineligible_returns_zero() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1359,12 +1490,16 @@ fn test_check_defaults_all_ineligible_returns_zero() {
     let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let pending_loan_id = manager.request_loan(&borrower, &1000, &17280);
-    let approved_loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let pending_loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let approved_loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&approved_loan_id);
 
     let loan_ids = soroban_sdk::vec![&env, pending_loan_id, approved_loan_id, 999];
-    let defaulted_count = manager.check_defaults(&loan_ids);
+    let defaulted_count = manager.check_defaults(&admin, &loan_ids);
 
     assert_eq!(defaulted_count, 0);
     assert_eq!(
@@ -1402,20 +1537,23 @@ fn test_overdue_repayment_charges_late_fee() {
     manager.set_late_fee_rate(&500);
     manager.set_grace_period_ledgers(&0);
     env.ledger().set_sequence_number(1);
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let due_date = manager.get_loan(&loan_id).due_date;
     env.ledger().set_sequence_number(due_date + 8_640);
 
-    manager.repay(&borrower, &loan_id, &300);
+    manager.repay(&borrower, &loan_id, &300, &None);
 
+    // Late fee, then interest, then principal.
     let loan = manager.get_loan(&loan_id);
-    assert_eq!(loan.interest_paid, 45);
-    assert_eq!(loan.late_fee_paid, 6);
-    assert_eq!(loan.principal_paid, 249);
-    assert_eq!(loan.accrued_interest, 135);
-    assert_eq!(loan.accrued_late_fee, 19);
+    assert_eq!(loan.late_fee_paid, 25);
+    assert_eq!(loan.interest_paid, 180);
+    assert_eq!(loan.principal_paid, 95);
+    assert_eq!(loan.accrued_interest, 0);
+    assert_eq!(loan.accrued_late_fee, 0);
     assert_eq!(loan.status, LoanStatus::Approved);
     assert_eq!(token_client.balance(&pool_client), 9_300);
 }
@@ -1444,17 +1582,19 @@ fn test_overdue_partial_repayment_still_reduces_principal() {
     manager.set_late_fee_rate(&500);
     manager.set_grace_period_ledgers(&0);
     env.ledger().set_sequence_number(1);
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let due_date = manager.get_loan(&loan_id).due_date;
     env.ledger().set_sequence_number(due_date + 8_640);
 
-    manager.repay(&borrower, &loan_id, &300);
+    manager.repay(&borrower, &loan_id, &300, &None);
 
     let loan = manager.get_loan(&loan_id);
     assert!(loan.principal_paid > 0);
-    assert!(loan.accrued_late_fee > 0);
+    assert_eq!(loan.accrued_late_fee, 0);
     assert_eq!(
         loan.principal_paid + loan.interest_paid + loan.late_fee_paid,
         300
@@ -1494,7 +1634,9 @@ fn test_deposit_collateral_and_auto_release_on_full_repayment() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let contract_balance_before = token_client.balance(&manager.address);
@@ -1507,7 +1649,7 @@ fn test_deposit_collateral_and_auto_release_on_full_repayment() {
     );
 
     let borrower_balance_before_full_repay = token_client.balance(&borrower);
-    manager.repay(&borrower, &loan_id, &1_000);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
 
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
     assert_eq!(manager.get_collateral(&loan_id), 0);
@@ -1522,7 +1664,7 @@ fn test_collateral_is_seized_on_default() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -1539,7 +1681,9 @@ fn test_collateral_is_seized_on_default() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &400);
 
@@ -1550,7 +1694,7 @@ fn test_collateral_is_seized_on_default() {
     let default_window = manager.get_default_window_ledgers();
     env.ledger()
         .set_sequence_number(due_date + default_window + 1);
-    manager.check_default(&loan_id);
+    manager.check_default(&admin, &loan_id);
 
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Defaulted);
     assert_eq!(manager.get_collateral(&loan_id), 0);
@@ -1569,7 +1713,7 @@ fn test_collateral_is_seized_on_batch_default() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
 
@@ -1595,8 +1739,12 @@ fn test_collateral_is_seized_on_batch_default() {
     stellar_token.mint(&borrower1, &20_000);
     stellar_token.mint(&borrower2, &20_000);
 
-    let loan_id1 = manager.request_loan(&borrower1, &1_000, &17280);
-    let loan_id2 = manager.request_loan(&borrower2, &1_000, &17280);
+    let loan_id1 = manager
+        .request_loan(&borrower1, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id2 = manager
+        .request_loan(&borrower2, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id1);
     manager.approve_loan(&loan_id2);
     manager.deposit_collateral(&loan_id1, &300);
@@ -1610,7 +1758,7 @@ fn test_collateral_is_seized_on_batch_default() {
         .set_sequence_number(due_date + default_window + 1);
 
     let loan_ids = soroban_sdk::vec![&env, loan_id1, loan_id2];
-    manager.check_defaults(&loan_ids);
+    manager.check_defaults(&admin, &loan_ids);
 
     assert_eq!(manager.get_collateral(&loan_id1), 0);
     assert_eq!(manager.get_collateral(&loan_id2), 0);
@@ -1646,7 +1794,9 @@ fn test_liquidate_under_threshold_transfers_bonus_and_refund() {
     manager.set_liquidation_threshold(&14_500);
     manager.set_liquidation_bonus_bps(&1_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_400);
 
@@ -1696,7 +1846,9 @@ fn test_liquidate_rejects_healthy_collateral_ratio() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_600);
 
@@ -1739,7 +1891,9 @@ fn test_liquidation_bonus_cap_enforced() {
 
     // Create a loan and liquidate it with the cap in effect
     manager.set_liquidation_threshold(&14_500);
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_400); // Collateral = 1400
 
@@ -1776,7 +1930,9 @@ fn test_deposit_collateral_rejects_non_active_loan() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &500, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.deposit_collateral(&loan_id, &100);
 }
 
@@ -1801,7 +1957,9 @@ fn test_small_loan_interest_accrual_precision() {
     stellar_token.mint(&pool_address, &10_000);
 
     // Request a small loan of 50 units
-    let loan_id = manager.request_loan(&borrower, &50, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &50, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let initial_loan = manager.get_loan(&loan_id);
@@ -1846,7 +2004,9 @@ fn test_query_functions() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_address, &10_000);
 
-    let _loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let _loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     assert_eq!(manager.get_total_loans(), 1);
 }
 
@@ -1878,13 +2038,17 @@ fn test_get_borrower_loans() {
     stellar_token.mint(&borrower, &10_000);
 
     // Request first loan
-    let loan_id_1 = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id_1 = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let borrower_loans = manager.get_borrower_loans(&borrower);
     assert_eq!(borrower_loans.len(), 1);
     assert_eq!(borrower_loans.get(0).unwrap(), loan_id_1);
 
     // Request second loan (while first is still pending)
-    let loan_id_2 = manager.request_loan(&borrower, &500, &17280);
+    let loan_id_2 = manager
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
     let borrower_loans = manager.get_borrower_loans(&borrower);
     assert_eq!(borrower_loans.len(), 2);
     assert_eq!(borrower_loans.get(0).unwrap(), loan_id_1);
@@ -1903,7 +2067,7 @@ fn test_get_borrower_loans() {
     // Repay first loan completely
     let loan_1 = manager.get_loan(&loan_id_1);
     let repay_amount_1 = loan_1.amount + loan_1.accrued_interest + loan_1.accrued_late_fee;
-    manager.repay(&borrower, &loan_id_1, &repay_amount_1);
+    manager.repay(&borrower, &loan_id_1, &repay_amount_1, &None);
 
     // Borrower loans should still contain both loans (historical record)
     let borrower_loans = manager.get_borrower_loans(&borrower);
@@ -1939,13 +2103,17 @@ fn test_pending_loans_count_against_cap() {
     client.set_max_loans_per_borrower(&2);
 
     // Request two loans (both pending) — should consume the full cap
-    let _loan_id_1 = client.request_loan(&borrower, &500, &17280);
-    let _loan_id_2 = client.request_loan(&borrower, &500, &17280);
+    let _loan_id_1 = client
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let _loan_id_2 = client
+        .request_loan(&borrower, &500, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     assert_eq!(client.get_borrower_loan_count(&borrower), 2);
 
     // Third request must be rejected even though neither loan is approved yet
-    let result = client.try_request_loan(&borrower, &500, &17280);
+    let result = client.try_request_loan(&borrower, &500, &17280, &None, &None, &None, &None);
     assert_eq!(result, Err(Ok(LoanError::MaxLoansReached)));
 }
 
@@ -1974,7 +2142,9 @@ fn test_extend_loan_happy_path() {
     stellar_token.mint(&pool_client, &10_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Get original due date
@@ -2015,7 +2185,9 @@ fn test_extend_loan_wrong_borrower() {
     stellar_token.mint(&pool_client, &10_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Try to extend with wrong borrower
@@ -2042,7 +2214,9 @@ fn test_extend_loan_rejected_for_pending_loan() {
     );
 
     // Request but don't approve
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     // Try to extend pending loan
     let result = manager.try_extend_loan(&borrower, &loan_id, &1000);
@@ -2071,9 +2245,11 @@ fn test_extend_loan_rejected_for_repaid_loan() {
     stellar_token.mint(&borrower, &5_000);
 
     // Request, approve, and repay loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
-    manager.repay(&borrower, &loan_id, &1000);
+    manager.repay(&borrower, &loan_id, &1000, &None);
 
     // Try to extend repaid loan
     let result = manager.try_extend_loan(&borrower, &loan_id, &1000);
@@ -2085,7 +2261,7 @@ fn test_extend_loan_rejected_for_defaulted_loan() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     // Setup
@@ -2101,7 +2277,9 @@ fn test_extend_loan_rejected_for_defaulted_loan() {
     stellar_token.mint(&pool_client, &10_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Move time past default window
@@ -2119,7 +2297,7 @@ fn test_extend_loan_rejected_for_defaulted_loan() {
     });
 
     // Mark as defaulted
-    manager.check_defaults(&soroban_sdk::vec![&env, loan_id]);
+    manager.check_defaults(&admin, &soroban_sdk::vec![&env, loan_id]);
 
     // Try to extend defaulted loan - should fail because status is no longer Approved
     let result = manager.try_extend_loan(&borrower, &loan_id, &1000);
@@ -2147,7 +2325,9 @@ fn test_extend_loan_rejected_for_zero_ledgers() {
     stellar_token.mint(&pool_client, &10_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Try to extend with 0 ledgers
@@ -2177,7 +2357,9 @@ fn test_extend_loan_max_extensions_limit() {
     stellar_token.mint(&borrower, &50_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Extend 3 times (max)
@@ -2217,7 +2399,9 @@ fn test_extend_loan_charges_fee() {
     let token_client = TokenClient::new(&env, &token_id);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Get borrower balance before extension
@@ -2255,7 +2439,9 @@ fn test_extend_loan_multiple_extensions() {
     stellar_token.mint(&borrower, &5_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let loan_initial = manager.get_loan(&loan_id);
@@ -2317,7 +2503,9 @@ fn test_oracle_rate_within_bounds_accepted() {
     manager.set_rate_oracle(&oracle_id);
 
     // Request loan — the oracle branch should be taken
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let loan = manager.get_loan(&loan_id);
 
     // Should use the oracle rate (800 BPS), not the default (1200 BPS)
@@ -2490,7 +2678,9 @@ fn test_oracle_rate_below_min_falls_back_to_default() {
     manager.set_min_rate_bps(&500);
 
     // Request loan — oracle returns 100 which is below min_rate_bps=500
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let loan = manager.get_loan(&loan_id);
 
     // Should fall back to default rate (1200 BPS)
@@ -2526,7 +2716,9 @@ fn test_oracle_rate_above_max_falls_back_to_default() {
     manager.set_max_rate_bps(&2_000);
 
     // Request loan — oracle returns 5000 which is above max_rate_bps=2000
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     let loan = manager.get_loan(&loan_id);
 
     // Should fall back to default rate (1200 BPS)
@@ -2558,7 +2750,9 @@ fn test_rate_bounds_persist_across_operations() {
     manager.set_max_rate_bps(&50_000);
 
     // Request and approve loan
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Verify bounds are still in place
@@ -2601,7 +2795,17 @@ fn test_interest_calculation_overflow_safety() {
     manager.set_interest_rate(&50_000);
 
     manager.set_max_loan_amount(&large_principal);
-    let loan_id = manager.request_loan(&borrower, &large_principal, &17280);
+    let loan_id = manager
+        .request_loan(
+            &borrower,
+            &large_principal,
+            &17280,
+            &None,
+            &None,
+            &None,
+            &None,
+        )
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Fast-forward far enough to trigger overflow protection, but keep the
@@ -2611,7 +2815,7 @@ fn test_interest_calculation_overflow_safety() {
         .set_sequence_number(loan.last_interest_ledger + 10_000);
 
     // Should not panic, should either calculate correctly or return AmountTooLarge error on next interaction
-    let result = manager.try_repay(&borrower, &loan_id, &100);
+    let result = manager.try_repay(&borrower, &loan_id, &100, &None);
     // Given the massive principal and long duration, it should not panic.
     assert!(
         matches!(result, Ok(Ok(())) | Err(Ok(LoanError::AmountTooLarge))),
@@ -2644,7 +2848,9 @@ fn test_liquidate_with_collateral_shortfall_has_no_refund() {
 
     manager.set_liquidation_threshold(&15_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &900);
 
@@ -2689,13 +2895,15 @@ fn test_liquidate_rejects_repaid_loan() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &400);
 
     let loan = manager.get_loan(&loan_id);
     let repay_amount = loan.amount + loan.accrued_interest + loan.accrued_late_fee;
-    manager.repay(&borrower, &loan_id, &repay_amount);
+    manager.repay(&borrower, &loan_id, &repay_amount, &None);
 
     let result = manager.try_liquidate(&liquidator, &loan_id);
     assert_eq!(result, Err(Ok(LoanError::LoanNotActive)));
@@ -2727,7 +2935,9 @@ fn test_liquidate_emits_loan_liquidated_event_with_expected_amounts() {
     manager.set_liquidation_threshold(&14_500);
     manager.set_liquidation_bonus_bps(&1_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_400);
 
@@ -2762,7 +2972,9 @@ fn test_late_fee_cap_at_total_debt_limit() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &1000);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &1000, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Jump far into the future so late fees accrue significantly
@@ -2798,11 +3010,13 @@ fn test_late_fees_stop_accruing_when_principal_paid() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1000, &1000);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &1000, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Pay off only the principal
-    manager.repay(&borrower, &loan_id, &1000);
+    manager.repay(&borrower, &loan_id, &1000, &None);
 
     // Jump into late fee territory
     env.ledger()
@@ -2837,7 +3051,9 @@ fn test_refinance_loan_increases_principal_draws_from_pool() {
     stellar_token.mint(&pool_client, &50_000);
 
     // Approve a 1_000-unit loan, then set collateral high enough for refinance.
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Inject collateral directly so the contract accepts the larger amount.
@@ -2893,7 +3109,9 @@ fn test_refinance_loan_decreases_principal_returns_excess_to_pool() {
     // Give borrower tokens so they can return the excess principal.
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &2_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &2_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Set collateral so the contract doesn't reject the call.
@@ -2941,7 +3159,9 @@ fn test_refinance_loan_fails_when_score_drops_below_minimum() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &50_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Artificially lower the borrower's score below the 500 minimum by
@@ -2984,7 +3204,7 @@ fn test_pause_blocks_request_loan() {
     assert!(manager.is_paused());
 
     // Should fail with ContractPaused error
-    let result = manager.try_request_loan(&borrower, &1000, &17280);
+    let result = manager.try_request_loan(&borrower, &1000, &17280, &None, &None, &None, &None);
     assert_eq!(result, Err(Ok(LoanError::ContractPaused)));
 }
 
@@ -3009,7 +3229,9 @@ fn test_pause_blocks_approve_loan() {
     stellar_token.mint(&pool_client, &10_000);
 
     // Request loan before pausing
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     // Pause the contract
     manager.pause();
@@ -3042,7 +3264,9 @@ fn test_pause_blocks_repay() {
     stellar_token.mint(&borrower, &2_000);
 
     // Request and approve loan before pausing
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Pause the contract
@@ -3050,7 +3274,7 @@ fn test_pause_blocks_repay() {
     assert!(manager.is_paused());
 
     // Should fail with ContractPaused error
-    let result = manager.try_repay(&borrower, &loan_id, &100);
+    let result = manager.try_repay(&borrower, &loan_id, &100, &None);
     assert_eq!(result, Err(Ok(LoanError::ContractPaused)));
 }
 
@@ -3076,7 +3300,7 @@ fn test_unpause_restores_request_loan() {
     assert!(manager.is_paused());
 
     // Verify request_loan is blocked
-    let result = manager.try_request_loan(&borrower, &1000, &17280);
+    let result = manager.try_request_loan(&borrower, &1000, &17280, &None, &None, &None, &None);
     assert_eq!(result, Err(Ok(LoanError::ContractPaused)));
 
     // Unpause the contract
@@ -3084,7 +3308,9 @@ fn test_unpause_restores_request_loan() {
     assert!(!manager.is_paused());
 
     // Now request_loan should succeed
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     assert_eq!(loan_id, 1);
 
     let loan = manager.get_loan(&loan_id);
@@ -3138,13 +3364,15 @@ fn test_collateral_release_works_while_paused() {
     stellar_token.mint(&borrower, &2_000);
 
     // Request, approve, and fully repay loan before pausing
-    let loan_id = manager.request_loan(&borrower, &1000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     // Fully repay the loan
     let loan = manager.get_loan(&loan_id);
     let total_owed = loan.amount + loan.accrued_interest;
-    manager.repay(&borrower, &loan_id, &total_owed);
+    manager.repay(&borrower, &loan_id, &total_owed, &None);
 
     // Verify loan is repaid
     let loan = manager.get_loan(&loan_id);
@@ -3185,9 +3413,11 @@ fn test_purge_repaid_loan_removes_storage() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
-    manager.repay(&borrower, &loan_id, &1_000);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
 
     manager.purge_loan(&loan_id);
@@ -3216,7 +3446,9 @@ fn test_purge_cancelled_loan_removes_storage() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.cancel_loan(&borrower, &loan_id);
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Cancelled);
 
@@ -3245,7 +3477,9 @@ fn test_purge_rejected_loan_removes_storage() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.reject_loan(&loan_id, &String::from_str(&env, "manual review failed"));
     assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Rejected);
 
@@ -3274,7 +3508,9 @@ fn test_purge_pending_loan_rejected() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
 
     let result = manager.try_purge_loan(&loan_id);
     assert_eq!(result, Err(Ok(LoanError::LoanNotPurgable)));
@@ -3300,7 +3536,9 @@ fn test_purge_approved_loan_rejected() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     let result = manager.try_purge_loan(&loan_id);
@@ -3339,9 +3577,11 @@ fn test_purge_emits_loan_purged_event() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
-    manager.repay(&borrower, &loan_id, &1_000);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
 
     manager.purge_loan(&loan_id);
 
@@ -3368,7 +3608,9 @@ fn test_purge_cancelled_loan_decrements_borrower_loan_count() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
     manager.cancel_loan(&borrower, &loan_id);
     manager.purge_loan(&loan_id);
 
@@ -3401,7 +3643,9 @@ fn test_get_total_outstanding_tracks_approve_and_repay() {
     stellar_token.mint(&pool_client, &10_000);
     stellar_token.mint(&borrower, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     assert_eq!(manager.get_total_outstanding(&token_id), 1_000);
 
@@ -3413,7 +3657,7 @@ fn test_get_total_outstanding_tracks_approve_and_repay() {
         - loan.principal_paid
         - loan.interest_paid
         - loan.late_fee_paid;
-    manager.repay(&borrower, &loan_id, &remaining_debt);
+    manager.repay(&borrower, &loan_id, &remaining_debt, &None);
     assert_eq!(manager.get_total_outstanding(&token_id), 0);
 }
 
@@ -3422,7 +3666,7 @@ fn test_get_total_outstanding_decreases_on_check_default() {
     let env = Env::default();
     env.mock_all_auths_allowing_non_root_auth();
 
-    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
     let borrower = Address::generate(&env);
 
     let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
@@ -3437,7 +3681,9 @@ fn test_get_total_outstanding_decreases_on_check_default() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &10_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     assert_eq!(manager.get_total_outstanding(&token_id), 1_000);
 
@@ -3446,7 +3692,7 @@ fn test_get_total_outstanding_decreases_on_check_default() {
     env.ledger()
         .set_sequence_number(due_date + default_window + 1);
 
-    manager.check_default(&loan_id);
+    manager.check_default(&admin, &loan_id);
     assert_eq!(manager.get_total_outstanding(&token_id), 0);
 }
 
@@ -3471,7 +3717,9 @@ fn test_is_liquidatable_healthy_loan() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_600);
 
@@ -3501,7 +3749,9 @@ fn test_is_liquidatable_under_threshold() {
 
     manager.set_liquidation_threshold(&14_500);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_400);
 
@@ -3529,7 +3779,9 @@ fn test_is_liquidatable_exactly_at_threshold() {
     stellar_token.mint(&pool_client, &20_000);
     stellar_token.mint(&borrower, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     // Default threshold 150% → collateral/debt must be < 1.5 to liquidate.
     manager.deposit_collateral(&loan_id, &1_500);
@@ -3557,7 +3809,9 @@ fn test_is_liquidatable_zero_collateral() {
     let stellar_token = StellarAssetClient::new(&env, &token_id);
     stellar_token.mint(&pool_client, &20_000);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
 
     assert!(manager.is_liquidatable(&loan_id));
@@ -3580,7 +3834,9 @@ fn test_is_liquidatable_non_active_loan() {
         &None,
     );
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     assert!(!manager.is_liquidatable(&loan_id));
 }
 
@@ -3607,7 +3863,9 @@ fn test_get_loan_health_matches_liquidation_state() {
 
     manager.set_liquidation_threshold(&14_500);
 
-    let loan_id = manager.request_loan(&borrower, &1_000, &17_280);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
     manager.approve_loan(&loan_id);
     manager.deposit_collateral(&loan_id, &1_400);
 
@@ -3617,7 +3875,9 @@ fn test_get_loan_health_matches_liquidation_state() {
     assert!(ratio_bps > 0);
     assert!(manager.is_liquidatable(&loan_id));
 
-    let pending_id = manager.request_loan(&borrower, &500, &17_280);
+    let pending_id = manager
+        .request_loan(&borrower, &500, &17_280, &None, &None, &None, &None)
+        .loan_id;
     let (pending_collateral, pending_debt, pending_ratio) = manager.get_loan_health(&pending_id);
     assert_eq!(pending_collateral, 0);
     assert_eq!(pending_debt, 0);
@@ -3670,3 +3930,3234 @@ fn test_set_rate_oracle_emits_rate_oracle_updated_event() {
         "RateOracleUpdated event should be emitted"
     );
 }
+
+#[test]
+fn test_sweep_defaults_respects_cap_and_skips_current_loans() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    let borrowers = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for borrower in borrowers.iter() {
+        nft_client.mint(
+            borrower,
+            &600,
+            &history_hash,
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+    }
+
+    let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+
+    let loan_id1 = manager
+        .request_loan(&borrowers[0], &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id2 = manager
+        .request_loan(&borrowers[1], &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id3 = manager
+        .request_loan(&borrowers[2], &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let loan_id4 = manager
+        .request_loan(&borrowers[3], &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id1);
+    manager.approve_loan(&loan_id2);
+    manager.approve_loan(&loan_id3);
+
+    let start = env.ledger().sequence();
+    env.ledger().set_sequence_number(start + 20_000);
+    manager.approve_loan(&loan_id4);
+    assert_eq!(
+        manager.get_active_loans(&0, &10),
+        soroban_sdk::vec![&env, loan_id1, loan_id2, loan_id3, loan_id4]
+    );
+
+    let due_date = manager.get_loan(&loan_id1).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+
+    assert_eq!(
+        manager.try_sweep_defaults(&Address::generate(&env), &2),
+        Err(Ok(LoanError::Unauthorized))
+    );
+    assert_eq!(manager.sweep_defaults(&admin, &2), 2);
+    assert_eq!(manager.get_loan(&loan_id1).status, LoanStatus::Defaulted);
+    assert_eq!(manager.get_loan(&loan_id2).status, LoanStatus::Defaulted);
+    assert_eq!(manager.get_loan(&loan_id3).status, LoanStatus::Approved);
+
+    assert_eq!(manager.sweep_defaults(&admin, &10), 1);
+    assert_eq!(manager.get_loan(&loan_id3).status, LoanStatus::Defaulted);
+    assert_eq!(manager.get_loan(&loan_id4).status, LoanStatus::Approved);
+    assert_eq!(
+        manager.get_active_loans(&0, &10),
+        soroban_sdk::vec![&env, loan_id4]
+    );
+    assert_eq!(nft_client.get_score(&borrowers[3]), 600);
+
+    assert_eq!(manager.sweep_defaults(&admin, &10), 0);
+}
+
+#[test]
+fn test_index_active_loans_range_backfills_unindexed_loans() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    // Rewind to a pre-upgrade layout: only the legacy Vec knows the loan.
+    env.as_contract(&manager.address, || {
+        let storage = env.storage();
+        storage.persistent().remove(&DataKeyExt::ActiveLoanAt(0));
+        storage
+            .persistent()
+            .remove(&DataKeyExt::ActiveLoanSlot(loan_id));
+        storage.instance().set(&DataKeyExt::ActiveLoanCount, &0u32);
+        storage
+            .persistent()
+            .set(&DataKey::ActiveLoans, &soroban_sdk::vec![&env, loan_id]);
+        storage
+            .persistent()
+            .extend_ttl(&DataKey::ActiveLoans, 100_000, 100_000);
+    });
+    assert_eq!(manager.get_active_loan_count(), 0);
+
+    let due_date = manager.get_loan(&loan_id).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    assert_eq!(manager.sweep_defaults(&admin, &10), 0);
+
+    assert_eq!(
+        manager.try_index_active_loans_range(&loan_id, &(loan_id + 50)),
+        Err(Ok(LoanError::InvalidConfiguration))
+    );
+    assert_eq!(
+        manager.index_active_loans_range(&loan_id, &(loan_id + 5)),
+        1
+    );
+    assert_eq!(
+        manager.index_active_loans_range(&loan_id, &(loan_id + 5)),
+        0
+    );
+    assert_eq!(
+        manager.get_active_loans(&0, &10),
+        soroban_sdk::vec![&env, loan_id]
+    );
+    env.as_contract(&manager.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::ActiveLoans));
+    });
+
+    assert_eq!(manager.sweep_defaults(&admin, &10), 1);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Defaulted);
+    assert_eq!(manager.get_active_loan_count(), 0);
+}
+
+#[test]
+fn test_repaid_loan_leaves_active_index() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &history_hash,
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &10_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert_eq!(
+        manager.get_active_loans(&0, &10),
+        soroban_sdk::vec![&env, loan_id]
+    );
+
+    manager.repay(&borrower, &loan_id, &1000, &None);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
+    assert_eq!(manager.get_active_loans(&0, &10), soroban_sdk::vec![&env]);
+}
+
+#[test]
+fn test_request_loan_uses_decayed_score() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &history_hash,
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    // 50 points per 100 ledgers of inactivity, floored at 300.
+    nft_client.set_score_decay(&50, &100, &300);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 300);
+
+    // Stored score would pass the 500 minimum; the live view does not.
+    assert_eq!(nft_client.get_score(&borrower), 600);
+    assert_eq!(nft_client.current_score(&borrower), 450);
+    let result = manager.try_request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    assert_eq!(result, Err(Ok(LoanError::InsufficientScore)));
+}
+
+#[test]
+fn test_compound_interest_exceeds_simple_over_several_periods() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let simple_borrower = Address::generate(&env);
+    let compound_borrower = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    for borrower in [&simple_borrower, &compound_borrower] {
+        nft_client.mint(
+            borrower,
+            &600,
+            &history_hash,
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+    }
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+    env.ledger().set_sequence_number(100);
+
+    let simple_loan = manager
+        .request_loan(
+            &simple_borrower,
+            &10_000,
+            &17_280,
+            &None,
+            &None,
+            &None,
+            &None,
+        )
+        .loan_id;
+    let compound_loan = manager
+        .request_loan(
+            &compound_borrower,
+            &10_000,
+            &17_280,
+            &None,
+            &None,
+            &None,
+            &None,
+        )
+        .loan_id;
+    manager.approve_loan(&simple_loan);
+    manager.approve_loan(&compound_loan);
+    assert_eq!(manager.get_compound_period(), 0);
+
+    // Five periods of 1_728 ledgers at 12% per 17_280-ledger term.
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 8_640);
+    assert_eq!(manager.get_amount_owed(&simple_loan), 10_600);
+
+    manager.set_compound_period(&1_728);
+    assert_eq!(manager.get_compound_period(), 1_728);
+    // 10_000 * 1.012^5 = 10_614.6...
+    assert_eq!(manager.get_amount_owed(&compound_loan), 10_614);
+
+    // A partial period does not accrue until it completes.
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1_000);
+    assert_eq!(manager.get_amount_owed(&compound_loan), 10_614);
+}
+
+#[test]
+fn test_whitelist_gates_request_loan_only_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let listed = Address::generate(&env);
+    let unlisted = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    for borrower in [&listed, &unlisted] {
+        nft_client.mint(
+            borrower,
+            &600,
+            &history_hash,
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+    }
+
+    // Disabled by default: anyone with a score may request.
+    assert!(!manager.is_whitelist_enabled());
+    manager.request_loan(&unlisted, &1_000, &17_280, &None, &None, &None, &None);
+
+    manager.add_borrower(&listed);
+    assert!(manager.is_whitelisted(&listed));
+    assert!(!manager.is_whitelisted(&unlisted));
+
+    manager.set_whitelist_enabled(&true);
+    let result = manager.try_request_loan(&unlisted, &1_000, &17_280, &None, &None, &None, &None);
+    assert_eq!(result, Err(Ok(LoanError::BorrowerNotWhitelisted)));
+    manager.request_loan(&listed, &1_000, &17_280, &None, &None, &None, &None);
+
+    manager.remove_borrower(&listed);
+    assert!(!manager.is_whitelisted(&listed));
+    let result = manager.try_request_loan(&listed, &1_000, &17_280, &None, &None, &None, &None);
+    assert_eq!(result, Err(Ok(LoanError::BorrowerNotWhitelisted)));
+
+    manager.set_whitelist_enabled(&false);
+    manager.request_loan(&unlisted, &1_000, &17_280, &None, &None, &None, &None);
+}
+
+#[test]
+fn test_loan_summary_on_time_and_overdue() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &history_hash,
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &10_000);
+    env.ledger().set_sequence_number(100);
+
+    let loan_id = manager
+        .request_loan(&borrower, &10_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let pending = manager.loan_summary(&loan_id);
+    assert_eq!(pending.status, LoanStatus::Pending);
+    assert_eq!(pending.total_owed, 0);
+    assert!(!pending.is_overdue);
+
+    manager.approve_loan(&loan_id);
+    let due_date = manager.get_loan(&loan_id).due_date;
+
+    // Halfway through the term, after a partial repayment.
+    env.ledger().set_sequence_number(100 + 8_640);
+    manager.repay(&borrower, &loan_id, &1_000, &None);
+    let summary = manager.loan_summary(&loan_id);
+    assert_eq!(summary.loan_id, loan_id);
+    assert_eq!(summary.borrower, borrower);
+    assert_eq!(summary.status, LoanStatus::Approved);
+    assert_eq!(summary.principal, 10_000);
+    assert_eq!(summary.due_date, due_date);
+    assert_eq!(summary.total_paid, 1_000);
+    assert_eq!(summary.late_fee_due, 0);
+    assert!(!summary.is_overdue);
+    assert_eq!(
+        summary.total_owed,
+        summary.principal_remaining + summary.interest_due
+    );
+    assert_eq!(summary.total_owed, manager.get_amount_owed(&loan_id));
+
+    // Past due and past grace: late fees accrue and the loan reads overdue.
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_grace_period_ledgers() + 1_000);
+    let summary = manager.loan_summary(&loan_id);
+    assert!(summary.is_overdue);
+    assert!(summary.late_fee_due > 0);
+    assert_eq!(
+        summary.total_owed,
+        summary.principal_remaining + summary.interest_due + summary.late_fee_due
+    );
+    assert_eq!(summary.total_owed, manager.get_amount_owed(&loan_id));
+}
+
+#[test]
+fn test_set_loan_interest_reprices_pending_loan_only() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &history_hash,
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let old_rate = manager.get_loan(&loan_id).interest_rate_bps;
+
+    manager.set_loan_interest(&loan_id, &900);
+    let events = env.events().all();
+    let event = events.get(events.len() - 1).unwrap();
+    let topic_0 = soroban_sdk::Symbol::from_val(&env, &event.1.get(0).unwrap());
+    assert_eq!(topic_0, soroban_sdk::Symbol::new(&env, "LoanRepriced"));
+    assert_eq!(<(u32, u32)>::from_val(&env, &event.2), (old_rate, 900));
+    assert_eq!(manager.get_loan(&loan_id).interest_rate_bps, 900);
+
+    manager.set_max_rate_bps(&2_000);
+    let result = manager.try_set_loan_interest(&loan_id, &2_001);
+    assert_eq!(result, Err(Ok(LoanError::InvalidRate)));
+
+    manager.approve_loan(&loan_id);
+    assert_eq!(manager.get_loan(&loan_id).interest_rate_bps, 900);
+    let result = manager.try_set_loan_interest(&loan_id, &1_000);
+    assert_eq!(result, Err(Ok(LoanError::LoanNotPending)));
+}
+
+/// Loan layout before extensions, explicit terms and schema versioning.
+#[contracttype]
+#[derive(Clone)]
+struct LegacyLoanV1 {
+    borrower: Address,
+    amount: i128,
+    collateral_amount: i128,
+    principal_paid: i128,
+    interest_paid: i128,
+    accrued_interest: i128,
+    late_fee_paid: i128,
+    accrued_late_fee: i128,
+    interest_rate_bps: u32,
+    due_date: u32,
+    last_interest_ledger: u32,
+    last_late_fee_ledger: u32,
+    status: LoanStatus,
+    interest_residual: i128,
+}
+
+fn store_legacy_loan(env: &Env, manager: &LoanManagerClient, loan_id: u32, borrower: &Address) {
+    let legacy = LegacyLoanV1 {
+        borrower: borrower.clone(),
+        amount: 1_000,
+        collateral_amount: 0,
+        principal_paid: 0,
+        interest_paid: 0,
+        accrued_interest: 0,
+        late_fee_paid: 0,
+        accrued_late_fee: 0,
+        interest_rate_bps: 1_200,
+        due_date: 0,
+        last_interest_ledger: 0,
+        last_late_fee_ledger: 0,
+        status: LoanStatus::Pending,
+        interest_residual: 0,
+    };
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(loan_id), &legacy);
+    });
+}
+
+#[test]
+fn test_legacy_loan_is_upgraded_on_read() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, _nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    store_legacy_loan(&env, &manager, 7, &borrower);
+
+    let loan = manager.get_loan(&7);
+    assert_eq!(loan.borrower, borrower);
+    assert_eq!(loan.amount, 1_000);
+    assert_eq!(loan.status, LoanStatus::Pending);
+    assert_eq!(loan.extension_count, 0);
+    assert_eq!(loan.term_ledgers, manager.get_default_term());
+    assert_eq!(loan.schema, 7);
+    assert_eq!(loan.token, manager.get_token());
+    assert_eq!(loan.memo, BytesN::from_array(&env, &[0u8; 32]));
+
+    // The upgraded record was written back, so the explicit migration is a no-op.
+    assert!(!manager.migrate_loan(&7));
+    env.as_contract(&manager.address, || {
+        let stored: Loan = env.storage().persistent().get(&DataKey::Loan(7)).unwrap();
+        assert_eq!(stored.schema, 7);
+    });
+}
+
+#[test]
+fn test_migrate_loans_range_upgrades_only_legacy_records() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let current_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    store_legacy_loan(&env, &manager, 2, &borrower);
+    store_legacy_loan(&env, &manager, 3, &borrower);
+
+    assert_eq!(manager.migrate_loans_range(&current_id, &10), 2);
+    assert_eq!(manager.migrate_loans_range(&current_id, &10), 0);
+    assert_eq!(manager.get_loan(&3).schema, 7);
+
+    let result = manager.try_migrate_loans_range(&1, &51);
+    assert_eq!(result, Err(Ok(LoanError::InvalidConfiguration)));
+    let result = manager.try_migrate_loan(&99);
+    assert_eq!(result, Err(Ok(LoanError::LoanNotFound)));
+}
+
+#[test]
+fn test_approve_loan_emits_disbursement_terms() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &10_000);
+    env.ledger().set_sequence_number(500);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    let disbursed = env
+        .events()
+        .all()
+        .iter()
+        .find(|event| {
+            event.1.get(0).is_some_and(|t| {
+                soroban_sdk::Symbol::from_val(&env, &t)
+                    == soroban_sdk::Symbol::new(&env, "LoanDisbursed")
+            })
+        })
+        .expect("LoanDisbursed event not emitted");
+    assert_eq!(u32::from_val(&env, &disbursed.1.get(1).unwrap()), loan_id);
+    assert_eq!(
+        Address::from_val(&env, &disbursed.1.get(2).unwrap()),
+        borrower
+    );
+    let (recipient, principal, rate_bps, due_date, collateral) =
+        <(Address, i128, u32, u32, i128)>::from_val(&env, &disbursed.2);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(recipient, borrower);
+    assert_eq!(principal, 1_000);
+    assert_eq!(rate_bps, loan.interest_rate_bps);
+    assert_eq!(due_date, 500 + 17_280);
+    assert_eq!(due_date, loan.due_date);
+    assert_eq!(collateral, 0);
+}
+
+#[test]
+fn test_refinance_loan_rechecks_decayed_score() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &50_000);
+    stellar_token.mint(&borrower, &5_000);
+    env.ledger().set_sequence_number(100);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    manager.deposit_collateral(&loan_id, &2_000);
+
+    // Inactivity decay drags the live score under the 500 minimum.
+    nft_client.set_score_decay(&50, &100, &300);
+    env.ledger().set_sequence_number(400);
+    assert_eq!(nft_client.get_score(&borrower), 600);
+
+    let result = manager.try_refinance_loan(&loan_id, &1_500, &17_280);
+    assert_eq!(result, Err(Ok(LoanError::InsufficientScore)));
+    assert_eq!(manager.get_loan(&loan_id).amount, 1_000);
+
+    // With decay switched off the same refinance goes through.
+    nft_client.set_score_decay(&0, &100, &300);
+    manager.refinance_loan(&loan_id, &1_500, &17_280);
+    assert_eq!(manager.get_loan(&loan_id).amount, 1_500);
+}
+
+#[test]
+fn test_request_loan_memo_round_trips() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let mut corridor = [0u8; 32];
+    corridor[..6].copy_from_slice(b"US-MX1");
+    let memo = BytesN::from_array(&env, &corridor);
+
+    let tagged = manager
+        .request_loan(
+            &borrower,
+            &1_000,
+            &17_280,
+            &Some(memo.clone()),
+            &None,
+            &None,
+            &None,
+        )
+        .loan_id;
+    let untagged = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+
+    assert_eq!(manager.get_loan(&tagged).memo, memo);
+    assert_eq!(manager.loan_summary(&tagged).memo, memo);
+    assert_eq!(
+        manager.get_loan(&untagged).memo,
+        BytesN::from_array(&env, &[0u8; 32])
+    );
+}
+
+fn setup_with_decimals<'a>(env: &Env, decimals: u32) -> LoanManagerClient<'a> {
+    let admin = Address::generate(env);
+    let nft_contract_id = env.register(RemittanceNFT, ());
+    let nft_client = RemittanceNFTClient::new(env, &nft_contract_id);
+    nft_client.initialize(&admin);
+
+    let token_id = env.register(MockDecimalsToken, ());
+    MockDecimalsTokenClient::new(env, &token_id).set_decimals(&decimals);
+
+    let pool_contract_id = env.register(LendingPool, ());
+    LendingPoolClient::new(env, &pool_contract_id).initialize(&admin);
+
+    let loan_manager_id = env.register(LoanManager, ());
+    let manager = LoanManagerClient::new(env, &loan_manager_id);
+    nft_client.authorize_minter(&loan_manager_id);
+    manager.initialize(
+        &nft_contract_id,
+        &pool_contract_id,
+        &token_id,
+        &admin,
+        &None,
+        &None,
+    );
+    manager
+}
+
+#[test]
+fn test_thresholds_scale_with_token_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let seven = setup_with_decimals(&env, 7);
+    assert_eq!(seven.get_decimals(), 7);
+    assert_eq!(seven.get_max_loan_amount(), 50_000);
+    assert_eq!(seven.get_min_repayment_amount(), 100);
+
+    let six = setup_with_decimals(&env, 6);
+    assert_eq!(six.get_decimals(), 6);
+    assert_eq!(six.get_max_loan_amount(), 5_000);
+    assert_eq!(six.get_min_repayment_amount(), 10);
+
+    // Explicit admin overrides are taken as-is, not rescaled.
+    six.set_min_repayment_amount(&250);
+    assert_eq!(six.get_min_repayment_amount(), 250);
+}
+
+#[test]
+fn test_repayment_points_scale_with_token_decimals() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    // Repay the same value in each denomination (5_000 at 7 decimals is 500
+    // at 6) and check both borrowers earn the same points.
+    let mut scores = [0u32; 2];
+    for (slot, (decimals, loan_amount, repaid)) in [(7u32, 10_000i128, 5_000i128), (6, 1_000, 500)]
+        .into_iter()
+        .enumerate()
+    {
+        let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+        env.as_contract(&manager.address, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::TokenDecimals, &decimals);
+        });
+        assert_eq!(manager.get_decimals(), decimals);
+
+        let borrower = Address::generate(&env);
+        nft_client.mint(
+            &borrower,
+            &600,
+            &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+        let stellar_token = StellarAssetClient::new(&env, &token_id);
+        stellar_token.mint(&pool_client, &100_000);
+        stellar_token.mint(&borrower, &10_000);
+
+        let loan_id = manager
+            .request_loan(&borrower, &loan_amount, &17280, &None, &None, &None, &None)
+            .loan_id;
+        manager.approve_loan(&loan_id);
+        manager.repay(&borrower, &loan_id, &repaid, &None);
+        scores[slot] = nft_client.get_score(&borrower);
+    }
+
+    // scale_amount(SCORE_POINT_UNITS) is 100 at 7 decimals and 10 at 6.
+    assert_eq!(scores, [650, 650]);
+}
+
+#[test]
+fn test_stellar_asset_reports_seven_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (manager, _nft_client, _pool, _token_id, _admin) = setup_test(&env);
+    assert_eq!(manager.get_decimals(), 7);
+}
+
+#[test]
+fn test_status_counts_follow_transitions() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let history_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for borrower in [&alice, &bob] {
+        nft_client.mint(
+            borrower,
+            &600,
+            &history_hash,
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+    }
+
+    let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&alice, &10_000);
+
+    let repaid = manager
+        .request_loan(&alice, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let rejected = manager
+        .request_loan(&alice, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let cancelled = manager
+        .request_loan(&alice, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let defaulted = manager
+        .request_loan(&bob, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.request_loan(&bob, &1000, &17280, &None, &None, &None, &None);
+
+    let counts = manager.status_counts();
+    assert_eq!(counts.get(LoanStatus::Pending), Some(5));
+    assert_eq!(counts.get(LoanStatus::Approved), Some(0));
+
+    manager.approve_loan(&repaid);
+    manager.approve_loan(&defaulted);
+    manager.reject_loan(&rejected, &String::from_str(&env, "incomplete"));
+    manager.cancel_loan(&alice, &cancelled);
+    manager.repay(&alice, &repaid, &1000, &None);
+
+    let due_date = manager.get_loan(&defaulted).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    manager.sweep_defaults(&admin, &10);
+
+    let counts = manager.status_counts();
+    assert_eq!(counts.get(LoanStatus::Pending), Some(1));
+    assert_eq!(counts.get(LoanStatus::Approved), Some(0));
+    assert_eq!(counts.get(LoanStatus::Repaid), Some(1));
+    assert_eq!(counts.get(LoanStatus::Defaulted), Some(1));
+    assert_eq!(counts.get(LoanStatus::Cancelled), Some(1));
+    assert_eq!(counts.get(LoanStatus::Rejected), Some(1));
+    assert_eq!(counts.get(LoanStatus::Liquidated), Some(0));
+}
+
+#[test]
+fn test_keeper_runs_defaults_but_not_config() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+
+    let keeper = Address::generate(&env);
+    let other_keeper = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    manager.add_keeper(&keeper);
+    manager.add_keeper(&other_keeper);
+    assert!(manager.is_keeper(&keeper));
+    assert!(manager.is_keeper(&other_keeper));
+    assert!(!manager.is_keeper(&outsider));
+
+    env.ledger().set_sequence_number(100);
+    let first = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let second = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    let third = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&first);
+    manager.approve_loan(&second);
+    manager.approve_loan(&third);
+    let due_date = manager.get_loan(&first).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+
+    assert_eq!(
+        manager.try_check_default(&outsider, &first),
+        Err(Ok(LoanError::Unauthorized))
+    );
+
+    manager.check_default(&keeper, &first);
+    assert_eq!(env.auths()[0].0, keeper);
+    assert_eq!(manager.get_loan(&first).status, LoanStatus::Defaulted);
+
+    manager.remove_keeper(&keeper);
+    assert!(!manager.is_keeper(&keeper));
+    assert_eq!(
+        manager.try_check_defaults(&keeper, &soroban_sdk::vec![&env, second]),
+        Err(Ok(LoanError::Unauthorized))
+    );
+    assert_eq!(
+        manager.check_defaults(&other_keeper, &soroban_sdk::vec![&env, second]),
+        1
+    );
+
+    // The bulk sweep is held to the same role.
+    assert_eq!(
+        manager.try_sweep_defaults(&outsider, &10),
+        Err(Ok(LoanError::Unauthorized))
+    );
+    assert_eq!(
+        manager.try_sweep_defaults(&keeper, &10),
+        Err(Ok(LoanError::Unauthorized))
+    );
+    assert_eq!(manager.sweep_defaults(&other_keeper, &10), 1);
+    assert_eq!(manager.get_loan(&third).status, LoanStatus::Defaulted);
+
+    // Keeper auth alone does not satisfy admin-only configuration calls.
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &other_keeper,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &manager.address,
+            fn_name: "set_interest_rate",
+            args: soroban_sdk::vec![&env, 1500u32.into_val(&env)],
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(manager.try_set_interest_rate(&1500).is_err());
+}
+
+#[test]
+fn test_request_loan_receipt_matches_stored_loan() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let first = manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    let second = manager.request_loan(&borrower, &2_500, &20_000, &None, &None, &None, &None);
+    assert_eq!(second.loan_id, first.loan_id + 1);
+
+    let loan = manager.get_loan(&second.loan_id);
+    assert_eq!(second.interest_bps, loan.interest_rate_bps);
+    assert_eq!(second.term_ledgers, loan.term_ledgers);
+    assert_eq!(second.net_disbursement, loan.amount);
+    assert_eq!(second.net_disbursement, 2_500);
+}
+
+#[test]
+fn test_repay_releases_collateral_above_threshold() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    let token = TokenClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &5_000);
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    manager.deposit_collateral(&loan_id, &1_500);
+    let threshold = manager.get_liquidation_threshold() as i128;
+    assert_eq!(threshold, 15_000);
+
+    // 600 left needs 900 of collateral; the other 600 comes back.
+    let before = token.balance(&borrower);
+    manager.repay(&borrower, &loan_id, &400, &None);
+    assert_eq!(manager.get_loan(&loan_id).collateral_amount, 900);
+    assert_eq!(token.balance(&borrower), before - 400 + 600);
+
+    manager.repay(&borrower, &loan_id, &400, &None);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.collateral_amount, 300);
+    assert!(loan.collateral_amount * 10_000 >= (loan.amount - loan.principal_paid) * threshold);
+
+    manager.repay(&borrower, &loan_id, &200, &None);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Repaid);
+    assert_eq!(loan.collateral_amount, 0);
+}
+
+#[test]
+fn test_repay_idempotency_key_blocks_replays() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &5_000);
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    let first_key = Some(BytesN::from_array(&env, &[1u8; 32]));
+    manager.repay(&borrower, &loan_id, &300, &first_key);
+    assert_eq!(manager.get_loan(&loan_id).principal_paid, 300);
+
+    // A retried submission with the same key changes nothing.
+    manager.repay(&borrower, &loan_id, &300, &first_key);
+    assert_eq!(manager.get_loan(&loan_id).principal_paid, 300);
+    assert_eq!(TokenClient::new(&env, &token_id).balance(&borrower), 5_700);
+
+    // Reusing the key for a different amount is an error, not a silent no-op.
+    assert_eq!(
+        manager.try_repay(&borrower, &loan_id, &200, &first_key),
+        Err(Ok(LoanError::IdempotencyKeyReused))
+    );
+
+    let second_key = Some(BytesN::from_array(&env, &[2u8; 32]));
+    manager.repay(&borrower, &loan_id, &300, &second_key);
+    assert_eq!(manager.get_loan(&loan_id).principal_paid, 600);
+}
+
+#[test]
+fn test_repay_idempotency_key_cannot_be_claimed_by_another_borrower() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    env.ledger().set_sequence_number(100);
+
+    let mut loans = soroban_sdk::Vec::new(&env);
+    for _ in 0..2 {
+        let borrower = Address::generate(&env);
+        nft_client.mint(
+            &borrower,
+            &600,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+        stellar_token.mint(&borrower, &5_000);
+        let loan_id = manager
+            .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+            .loan_id;
+        manager.approve_loan(&loan_id);
+        loans.push_back((borrower, loan_id));
+    }
+    let (attacker, attacker_loan) = loans.get(0).unwrap();
+    let (victim, victim_loan) = loans.get(1).unwrap();
+
+    // The attacker front-runs with the victim's key on their own loan.
+    let key = Some(BytesN::from_array(&env, &[9u8; 32]));
+    manager.repay(&attacker, &attacker_loan, &100, &key);
+
+    // The victim's repayment still goes through.
+    manager.repay(&victim, &victim_loan, &500, &key);
+    assert_eq!(manager.get_loan(&victim_loan).principal_paid, 500);
+    assert_eq!(TokenClient::new(&env, &token_id).balance(&victim), 5_500);
+}
+
+#[test]
+fn test_request_loan_enforces_term_limits() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    manager.set_max_term_ledgers(&50_000);
+    manager.set_min_term_ledgers(&1_000);
+
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &50_001, &None, &None, &None, &None),
+        Err(Ok(LoanError::InvalidTerm))
+    );
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &999, &None, &None, &None, &None),
+        Err(Ok(LoanError::InvalidTerm))
+    );
+
+    let receipt = manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    assert_eq!(manager.get_loan(&receipt.loan_id).term_ledgers, 17_280);
+    manager.request_loan(&borrower, &1_000, &1_000, &None, &None, &None, &None);
+    manager.request_loan(&borrower, &1_000, &50_000, &None, &None, &None, &None);
+}
+
+#[test]
+fn test_write_off_defaulted_loan() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &1_000);
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert_eq!(
+        manager.try_write_off(&loan_id),
+        Err(Ok(LoanError::LoanNotDefaulted))
+    );
+
+    manager.repay(&borrower, &loan_id, &300, &None);
+    let due_date = manager.get_loan(&loan_id).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    manager.check_default(&admin, &loan_id);
+    let score_after_default = nft_client.get_score(&borrower);
+
+    assert_eq!(manager.write_off(&loan_id), 700);
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &topics.get(0).unwrap()),
+        soroban_sdk::Symbol::new(&env, "LoanWrittenOff")
+    );
+    assert_eq!(i128::from_val(&env, &data), 700);
+
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::WrittenOff);
+    assert_eq!(loan.amount, 1_000);
+    assert_eq!(loan.principal_paid, 300);
+    let summary = manager.loan_summary(&loan_id);
+    assert_eq!(summary.principal, 1_000);
+    assert_eq!(summary.principal_remaining, 0);
+    assert_eq!(loan.accrued_interest, 0);
+    assert_eq!(loan.accrued_late_fee, 0);
+    assert_eq!(manager.get_borrower_outstanding(&borrower), 0);
+    assert_eq!(manager.get_total_outstanding(&token_id), 0);
+    assert_eq!(manager.get_total_written_off(), 700);
+    assert_eq!(nft_client.get_score(&borrower), score_after_default);
+    assert_eq!(manager.status_counts().get(LoanStatus::WrittenOff), Some(1));
+    assert_eq!(manager.status_counts().get(LoanStatus::Defaulted), Some(0));
+
+    assert_eq!(
+        manager.try_write_off(&loan_id),
+        Err(Ok(LoanError::LoanNotDefaulted))
+    );
+}
+
+#[test]
+fn test_nft_outstanding_tracks_open_principal() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &1_000);
+
+    env.ledger().set_sequence_number(100);
+    let first = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let second = manager
+        .request_loan(&borrower, &500, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(nft_client.get_outstanding(&borrower), 0);
+
+    manager.approve_loan(&first);
+    assert_eq!(nft_client.get_outstanding(&borrower), 1_000);
+    manager.approve_loan(&second);
+    assert_eq!(nft_client.get_outstanding(&borrower), 1_500);
+
+    manager.repay(&borrower, &first, &400, &None);
+    assert_eq!(nft_client.get_outstanding(&borrower), 1_100);
+    manager.repay(&borrower, &first, &600, &None);
+    assert_eq!(nft_client.get_outstanding(&borrower), 500);
+
+    let due_date = manager.get_loan(&second).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    manager.check_default(&admin, &second);
+    assert_eq!(nft_client.get_outstanding(&borrower), 0);
+}
+
+#[test]
+fn test_overpayment_rejected_by_default_and_capped_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    let token = TokenClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &5_000);
+
+    env.ledger().set_sequence_number(100);
+    let exact = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let capped = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&exact);
+    manager.approve_loan(&capped);
+    assert!(!manager.get_refund_overpayment());
+
+    assert_eq!(
+        manager.try_repay(&borrower, &exact, &1_001, &None),
+        Err(Ok(LoanError::RepaymentExceedsDebt))
+    );
+    manager.repay(&borrower, &exact, &1_000, &None);
+    assert_eq!(manager.get_loan(&exact).status, LoanStatus::Repaid);
+
+    manager.set_refund_overpayment(&true);
+    let before = token.balance(&borrower);
+    manager.repay(&borrower, &capped, &1_500, &None);
+    assert_eq!(manager.get_loan(&capped).status, LoanStatus::Repaid);
+    assert_eq!(token.balance(&borrower), before - 1_000);
+}
+
+#[test]
+fn test_score_gate_at_threshold_inclusive_and_exclusive() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &500,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    assert_eq!(manager.get_min_score(), 500);
+    assert!(!manager.get_threshold_inclusive());
+
+    // Exclusive (default): a score equal to the minimum passes.
+    manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+
+    // Inclusive: the same score is now rejected.
+    manager.set_threshold_inclusive(&true);
+    assert!(manager.get_threshold_inclusive());
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None),
+        Err(Ok(LoanError::InsufficientScore))
+    );
+}
+
+#[test]
+fn test_check_nft_reports_good_and_bogus_contracts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (manager, _nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    assert!(manager.check_nft());
+
+    // Point the manager at an address with no contract deployed.
+    let bogus = Address::generate(&env);
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::NftContract, &bogus);
+    });
+    assert!(!manager.check_nft());
+
+    // An uninitialized NFT deployment is reported as unhealthy too.
+    let uninitialized = env.register(RemittanceNFT, ());
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::NftContract, &uninitialized);
+    });
+    assert!(!manager.check_nft());
+}
+
+#[test]
+fn test_repeated_defaults_ban_borrowing_until_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &700,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+    manager.set_default_ban(&2, &1_000);
+    assert_eq!(manager.get_default_ban(), (2, 1_000));
+
+    let first = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let second = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&first);
+    manager.approve_loan(&second);
+
+    let due_date = manager.get_loan(&second).due_date;
+    let defaulted_at = due_date + manager.get_default_window_ledgers() + 1;
+    env.ledger().set_sequence_number(defaulted_at);
+    manager.check_default(&admin, &first);
+    assert_eq!(manager.borrow_banned_until(&borrower), 0);
+    manager.check_default(&admin, &second);
+    assert_eq!(nft_client.get_default_count(&borrower), 2);
+    assert_eq!(
+        manager.borrow_banned_until(&borrower),
+        defaulted_at as u64 + 1_000
+    );
+
+    // Lift the NFT seizure so only the default ban stands in the way.
+    env.as_contract(&nft_client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&remittance_nft::DataKey::Seized(borrower.clone()));
+    });
+
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None),
+        Err(Ok(LoanError::TooManyDefaults))
+    );
+
+    env.ledger().set_sequence_number(defaulted_at + 1_000);
+    manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+}
+
+#[test]
+fn test_borrower_loans_are_persistent_and_migrate_from_instance() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    let legacy_borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    env.as_contract(&manager.address, || {
+        let key = DataKey::BorrowerLoans(borrower.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+    assert_eq!(
+        manager.get_borrower_loans(&borrower),
+        soroban_sdk::vec![&env, loan_id]
+    );
+
+    // A list left in instance storage by an older version is moved on read.
+    env.as_contract(&manager.address, || {
+        env.storage().instance().set(
+            &DataKey::BorrowerLoans(legacy_borrower.clone()),
+            &soroban_sdk::vec![&env, 7u32, 9u32],
+        );
+    });
+    assert_eq!(
+        manager.get_borrower_loans(&legacy_borrower),
+        soroban_sdk::vec![&env, 7u32, 9u32]
+    );
+    env.as_contract(&manager.address, || {
+        let key = DataKey::BorrowerLoans(legacy_borrower.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+}
+
+#[test]
+fn test_events_carry_configured_namespace_topic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let nft_contract_id = env.register(RemittanceNFT, ());
+    let nft_client = RemittanceNFTClient::new(&env, &nft_contract_id);
+    nft_client.initialize(&admin);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let pool_contract_id = env.register(LendingPool, ());
+    LendingPoolClient::new(&env, &pool_contract_id).initialize(&admin);
+    let manager_id = env.register(LoanManager, ());
+    let manager = LoanManagerClient::new(&env, &manager_id);
+    nft_client.authorize_minter(&manager_id);
+    let namespace = soroban_sdk::Symbol::new(&env, "testnet");
+    manager.initialize(
+        &nft_contract_id,
+        &pool_contract_id,
+        &token_id,
+        &admin,
+        &Some(namespace.clone()),
+        &None,
+    );
+    assert_eq!(manager.get_event_namespace(), Some(namespace.clone()));
+
+    manager.set_refund_overpayment(&true);
+    let events = env.events().all();
+    let (_, topics, _) = events.last().unwrap();
+    assert_eq!(topics.len(), 2);
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &topics.get(0).unwrap()),
+        namespace
+    );
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &topics.get(1).unwrap()),
+        soroban_sdk::Symbol::new(&env, "RefundOverpaymentUpdated")
+    );
+
+    // Including the pause events published alongside their admin-tagged
+    // counterparts.
+    manager.pause();
+    let events = env.events().all();
+    let (_, topics, _) = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &topics.get(0).unwrap()),
+        namespace
+    );
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &topics.get(1).unwrap()),
+        soroban_sdk::Symbol::new(&env, "ContractPaused")
+    );
+
+    // Without a namespace the topics are unchanged.
+    let (default_manager, _nft, _pool, _token, _admin) = setup_test(&env);
+    assert_eq!(default_manager.get_event_namespace(), None);
+    default_manager.set_refund_overpayment(&true);
+    let events = env.events().all();
+    let (_, topics, _) = events.last().unwrap();
+    assert_eq!(topics.len(), 1);
+}
+
+#[test]
+fn test_reindex_counters_repairs_corrupted_counts() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+
+    let approved = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    manager.approve_loan(&approved);
+    let expected = manager.status_counts();
+
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::StatusCount(LoanStatus::Approved), &9u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BorrowerLoanCount(borrower.clone()), &0u32);
+    });
+    assert_eq!(manager.status_counts().get(LoanStatus::Approved), Some(9));
+
+    assert_eq!(
+        manager.try_reindex_counters(&1, &100),
+        Err(Ok(LoanError::InvalidConfiguration))
+    );
+    // Page through in two calls, as a caller would for a large book.
+    assert_eq!(manager.reindex_counters(&1, &1), 1);
+    assert_eq!(manager.reindex_counters(&2, &50), 1);
+
+    assert_eq!(manager.status_counts(), expected);
+    env.as_contract(&manager.address, || {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BorrowerLoanCount(borrower.clone()))
+            .unwrap();
+        assert_eq!(count, 2);
+    });
+}
+
+#[test]
+fn test_can_collateralize_checks_free_balance_not_locked() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &5_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert!(manager.can_collateralize(&loan_id, &6_000));
+
+    // Lock 4_000 of the borrower's 6_000 (5_000 + 1_000 disbursed).
+    manager.deposit_collateral(&loan_id, &4_000);
+    assert!(manager.can_collateralize(&loan_id, &2_000));
+    assert!(!manager.can_collateralize(&loan_id, &2_001));
+    assert!(!manager.can_collateralize(&loan_id, &0));
+    assert_eq!(
+        manager.try_can_collateralize(&(loan_id + 1), &1),
+        Err(Ok(LoanError::LoanNotFound))
+    );
+}
+
+#[test]
+fn test_time_to_due_counts_down_and_goes_negative() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &10_000);
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(
+        manager.try_time_to_due(&loan_id),
+        Err(Ok(LoanError::LoanNotActive))
+    );
+    manager.approve_loan(&loan_id);
+    let due_date = manager.get_loan(&loan_id).due_date;
+
+    // 17_280 ledgers of ~5s: one day.
+    assert_eq!(manager.time_to_due(&loan_id), 86_400);
+    env.ledger().set_sequence_number(due_date);
+    assert_eq!(manager.time_to_due(&loan_id), 0);
+    env.ledger().set_sequence_number(due_date + 25);
+    assert_eq!(manager.time_to_due(&loan_id), -125);
+}
+
+#[test]
+fn test_rounding_mode_rounds_accrued_interest_half_up() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &10_000);
+    manager.set_interest_rate(&1_000);
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert_eq!(manager.get_rounding_mode(), RoundingMode::Floor);
+
+    // 1_000 at 10% over 17_280 ledgers accrues 1/172.8 per ledger:
+    // 86 ledgers is just under half a unit, 87 just over.
+    env.ledger().set_sequence_number(186);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_000);
+    manager.set_rounding_mode(&RoundingMode::Round);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_000);
+
+    env.ledger().set_sequence_number(187);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_001);
+    manager.set_rounding_mode(&RoundingMode::Floor);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_000);
+}
+
+#[test]
+fn test_migrate_advances_storage_version_and_reruns_are_noops() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, _nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    assert_eq!(manager.storage_version(), 1);
+
+    // Simulate a deployment that predates the marker and the late-fee key.
+    env.as_contract(&manager.address, || {
+        env.storage().instance().remove(&DataKey::StorageVersion);
+        env.storage().instance().remove(&DataKey::LateFeeRateBps);
+    });
+    assert_eq!(manager.storage_version(), 0);
+
+    manager.migrate();
+    assert_eq!(manager.storage_version(), 1);
+    env.as_contract(&manager.address, || {
+        assert!(env.storage().instance().has(&DataKey::LateFeeRateBps));
+        env.storage().instance().remove(&DataKey::LateFeeRateBps);
+    });
+
+    manager.migrate();
+    assert_eq!(manager.storage_version(), 1);
+    env.as_contract(&manager.address, || {
+        assert!(!env.storage().instance().has(&DataKey::LateFeeRateBps));
+    });
+}
+
+#[test]
+fn test_rate_discounts_lower_locked_rate_for_higher_scores() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    let uri = String::from_str(&env, "ipfs://QmTest");
+    let low = Address::generate(&env);
+    let high = Address::generate(&env);
+    let top = Address::generate(&env);
+    nft_client.mint(
+        &low,
+        &550,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &uri,
+        &None,
+    );
+    nft_client.mint(
+        &high,
+        &800,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &uri,
+        &None,
+    );
+    nft_client.mint(
+        &top,
+        &950,
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &uri,
+        &None,
+    );
+    manager.set_interest_rate(&1_000);
+
+    let mut discounts = soroban_sdk::Map::new(&env);
+    discounts.set(600u32, 200u32);
+    discounts.set(850u32, 10_000u32);
+    manager.set_rate_discounts(&discounts);
+    assert_eq!(manager.get_rate_discounts(), discounts);
+
+    let low_rate = manager
+        .request_loan(&low, &1_000, &17_280, &None, &None, &None, &None)
+        .interest_bps;
+    let high_rate = manager
+        .request_loan(&high, &1_000, &17_280, &None, &None, &None, &None)
+        .interest_bps;
+    let top_rate = manager
+        .request_loan(&top, &1_000, &17_280, &None, &None, &None, &None)
+        .interest_bps;
+    assert_eq!(low_rate, 1_000);
+    assert_eq!(high_rate, 800);
+    // The discount is floored at a zero rate.
+    assert_eq!(top_rate, 0);
+
+    let mut invalid = soroban_sdk::Map::new(&env);
+    invalid.set(600u32, 10_001u32);
+    assert_eq!(
+        manager.try_set_rate_discounts(&invalid),
+        Err(Ok(LoanError::InvalidConfiguration))
+    );
+}
+
+#[test]
+#[should_panic(expected = "not initialized")]
+fn test_request_loan_before_initialize_panics_uniformly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let manager_id = env.register(LoanManager, ());
+    let manager = LoanManagerClient::new(&env, &manager_id);
+
+    let borrower = Address::generate(&env);
+    manager.request_loan(&borrower, &1_000, &1_000, &None, &None, &None, &None);
+}
+
+#[test]
+#[should_panic(expected = "not initialized")]
+fn test_admin_setter_before_initialize_panics_uniformly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let manager_id = env.register(LoanManager, ());
+    let manager = LoanManagerClient::new(&env, &manager_id);
+
+    manager.set_min_score(&600);
+}
+
+#[test]
+fn test_loans_in_window_filters_by_creation_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_client, _token_id, _admin) = setup_test(&env);
+    manager.set_max_loans_per_borrower(&10);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    for ts in [1_000u64, 2_000, 3_000, 4_000] {
+        env.ledger().with_mut(|li| li.timestamp = ts);
+        manager.request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    }
+
+    let window = manager.loans_in_window(&1_500, &3_000, &1, &10);
+    assert_eq!(window.len(), 2);
+    assert_eq!(window.get(0).unwrap().created_at, 2_000);
+    assert_eq!(window.get(1).unwrap().created_at, 3_000);
+
+    // Paging scans ids from `start`.
+    let window = manager.loans_in_window(&0, &u64::MAX, &3, &1);
+    assert_eq!(window.len(), 1);
+    assert_eq!(window.get(0).unwrap().created_at, 3_000);
+
+    assert!(matches!(
+        manager.try_loans_in_window(&3_000, &1_000, &1, &10),
+        Err(Ok(LoanError::InvalidConfiguration))
+    ));
+}
+
+fn approve_with_disbursement_delay(
+    env: &Env,
+    delay_secs: u64,
+) -> (LoanManagerClient<'_>, TokenClient<'_>, Address, u32) {
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(env);
+    let borrower = Address::generate(env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(env, &[0u8; 32]),
+        &String::from_str(env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(env, &token_id).mint(&pool_address, &10_000);
+
+    manager.set_disbursement_delay(&delay_secs);
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    (manager, TokenClient::new(env, &token_id), borrower, loan_id)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn test_claim_disbursement_before_delay_panics() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, token, borrower, loan_id) = approve_with_disbursement_delay(&env, 3_600);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Approved);
+    assert_eq!(token.balance(&borrower), 0);
+    assert_eq!(manager.get_pending_disbursement(&loan_id), Some(13_600));
+
+    env.ledger().with_mut(|li| li.timestamp = 13_599);
+    manager.claim_disbursement(&loan_id);
+}
+
+#[test]
+fn test_claim_disbursement_after_delay_transfers_funds() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, token, borrower, loan_id) = approve_with_disbursement_delay(&env, 3_600);
+
+    env.ledger().with_mut(|li| li.timestamp = 13_600);
+    manager.claim_disbursement(&loan_id);
+    assert_eq!(token.balance(&borrower), 1_000);
+    assert_eq!(manager.get_pending_disbursement(&loan_id), None);
+
+    let result = manager.try_claim_disbursement(&loan_id);
+    assert_eq!(result, Err(Ok(LoanError::NoPendingDisbursement)));
+}
+
+#[test]
+fn test_escrowed_loan_accrues_nothing_until_claimed() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, _token, borrower, loan_id) = approve_with_disbursement_delay(&env, 3_600);
+    let keeper = Address::generate(&env);
+    manager.add_keeper(&keeper);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.due_date, 0);
+    assert_eq!(loan.last_interest_ledger, 0);
+
+    // Well past what would have been the term and default window.
+    let start = env.ledger().sequence();
+    env.ledger().set_sequence_number(start + 100_000);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_000);
+    assert_eq!(manager.sweep_defaults(&keeper, &10), 0);
+    assert_eq!(
+        manager.try_repay(&borrower, &loan_id, &500, &None),
+        Err(Ok(LoanError::DisbursementLocked))
+    );
+
+    // The term and accrual start at the claim.
+    env.ledger().with_mut(|li| li.timestamp = 13_600);
+    manager.claim_disbursement(&loan_id);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Approved);
+    assert_eq!(loan.due_date, start + 100_000 + 17_280);
+    assert_eq!(loan.last_interest_ledger, start + 100_000);
+    assert_eq!(manager.get_amount_owed(&loan_id), 1_000);
+
+    env.ledger().set_sequence_number(start + 100_000 + 17_280);
+    assert!(manager.get_amount_owed(&loan_id) > 1_000);
+}
+
+#[test]
+fn test_zero_disbursement_delay_pays_on_approval() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, token, borrower, loan_id) = approve_with_disbursement_delay(&env, 0);
+    assert_eq!(token.balance(&borrower), 1_000);
+    assert_eq!(manager.get_pending_disbursement(&loan_id), None);
+}
+
+#[test]
+fn test_loan_ids_start_after_configured_base() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let nft_contract_id = env.register(RemittanceNFT, ());
+    let nft_client = RemittanceNFTClient::new(&env, &nft_contract_id);
+    nft_client.initialize(&admin);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let pool_contract_id = env.register(LendingPool, ());
+    LendingPoolClient::new(&env, &pool_contract_id).initialize(&admin);
+    let manager_id = env.register(LoanManager, ());
+    let manager = LoanManagerClient::new(&env, &manager_id);
+    nft_client.authorize_minter(&manager_id);
+    manager.initialize(
+        &nft_contract_id,
+        &pool_contract_id,
+        &token_id,
+        &admin,
+        &None,
+        &Some(1_000_000),
+    );
+    assert_eq!(manager.get_loan_id_base(), 1_000_000);
+    assert_eq!(manager.get_total_loans(), 0);
+
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(loan_id, 1_000_001);
+    assert_eq!(manager.get_loan(&loan_id).borrower, borrower);
+    assert_eq!(manager.get_total_loans(), 1);
+}
+
+#[test]
+fn test_loan_disburses_in_pool_supported_token() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &other_token).mint(&pool_address, &10_000);
+    pool_client.set_supported_token(&other_token, &true);
+    assert!(pool_client.is_supported_token(&other_token));
+
+    let loan_id = manager
+        .request_loan(
+            &borrower,
+            &1_000,
+            &17_280,
+            &None,
+            &Some(other_token.clone()),
+            &None,
+            &None,
+        )
+        .loan_id;
+    assert_eq!(manager.get_loan(&loan_id).token, other_token);
+    manager.approve_loan(&loan_id);
+
+    let other_client = TokenClient::new(&env, &other_token);
+    assert_eq!(other_client.balance(&borrower), 1_000);
+    assert_eq!(other_client.balance(&pool_address), 9_000);
+
+    // Collateral is checked in the loan's token, not the configured one.
+    StellarAssetClient::new(&env, &token_id).mint(&borrower, &5_000);
+    assert!(manager.can_collateralize(&loan_id, &1_000));
+    assert!(!manager.can_collateralize(&loan_id, &1_001));
+}
+
+#[test]
+fn test_request_loan_rejects_unlisted_token() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let unlisted = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let result = manager.try_request_loan(
+        &borrower,
+        &1_000,
+        &17_280,
+        &None,
+        &Some(unlisted.clone()),
+        &None,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(LoanError::UnsupportedToken)));
+
+    // Delisting after the request blocks approval.
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    pool_client.set_supported_token(&unlisted, &true);
+    let loan_id = manager
+        .request_loan(
+            &borrower,
+            &1_000,
+            &17_280,
+            &None,
+            &Some(unlisted.clone()),
+            &None,
+            &None,
+        )
+        .loan_id;
+    pool_client.set_supported_token(&unlisted, &false);
+    let result = manager.try_approve_loan(&loan_id);
+    assert_eq!(result, Err(Ok(LoanError::UnsupportedToken)));
+}
+
+#[test]
+fn test_pause_and_unpause_events_carry_admin_and_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, _nft_client, _pool_client, _token_id, admin) = setup_test(&env);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_234;
+        li.sequence_number = 77;
+    });
+
+    for (paused, name) in [(true, "Paused"), (false, "Unpaused")] {
+        if paused {
+            manager.pause();
+        } else {
+            manager.unpause();
+        }
+        let event = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::from_val(&env, &topics.get(0).unwrap())
+                    == soroban_sdk::Symbol::new(&env, name)
+            })
+            .expect("pause event");
+        assert_eq!(Address::from_val(&env, &event.1.get(1).unwrap()), admin);
+        assert_eq!(<(u32, u64)>::from_val(&env, &event.2), (77, 1_234));
+    }
+}
+
+#[test]
+fn test_borrowing_enabled_once_deposits_reach_threshold() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&provider, &10_000);
+
+    manager.set_borrowing_threshold(&5_000);
+    pool_client.deposit(&provider, &token_id, &4_999);
+    assert!(!manager.is_borrowing_enabled());
+    let result = manager.try_request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None);
+    assert_eq!(result, Err(Ok(LoanError::BorrowingNotEnabled)));
+
+    pool_client.deposit(&provider, &token_id, &1);
+    assert!(manager.is_borrowing_enabled());
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+
+    // Once enabled, borrowing stays enabled after deposits fall back.
+    env.ledger().with_mut(|li| li.sequence_number += 100_000);
+    pool_client.withdraw(&provider, &token_id, &4_000);
+    assert!(manager.is_borrowing_enabled());
+    manager.approve_loan(&loan_id);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Approved);
+}
+
+#[test]
+fn test_mul_bps_values() {
+    assert_eq!(LoanManager::mul_bps(1_000, 0), 0);
+    assert_eq!(LoanManager::mul_bps(1_000, 250), 25);
+    assert_eq!(LoanManager::mul_bps(9_999, 1), 0);
+    assert_eq!(LoanManager::mul_bps(-1_000, 250), -25);
+    // Large values don't overflow the intermediate product.
+    assert_eq!(LoanManager::mul_bps(i128::MAX, 10_000), i128::MAX);
+    assert_eq!(LoanManager::mul_bps(i128::MAX, 5_000), i128::MAX / 2);
+}
+
+#[test]
+#[should_panic(expected = "bps overflow")]
+fn test_mul_bps_overflow_panics() {
+    LoanManager::mul_bps(i128::MAX, 10_001);
+}
+
+#[test]
+fn test_borrower_statement_covers_mixed_loans() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+    stellar_token.mint(&borrower, &10_000);
+    env.ledger().set_sequence_number(100);
+
+    let repaid_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let defaulted_id = manager
+        .request_loan(&borrower, &2_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let active_id = manager
+        .request_loan(&borrower, &3_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&repaid_id);
+    manager.approve_loan(&defaulted_id);
+
+    env.ledger().set_sequence_number(1_000);
+    let owed = manager.get_amount_owed(&repaid_id);
+    manager.repay(&borrower, &repaid_id, &owed, &None);
+
+    let due_date = manager.get_loan(&defaulted_id).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    manager.approve_loan(&active_id);
+    manager.check_default(&admin, &defaulted_id);
+
+    let statement = manager.borrower_statement(&borrower, &0, &10);
+    assert_eq!(statement.len(), 3);
+
+    let repaid = statement.get(0).unwrap();
+    assert_eq!(repaid.loan_id, repaid_id);
+    assert_eq!(repaid.status, LoanStatus::Repaid);
+    assert_eq!(repaid.principal, 1_000);
+    assert_eq!(repaid.principal_remaining, 0);
+    assert_eq!(repaid.total_paid, owed);
+    assert_eq!(repaid.total_owed, 0);
+
+    let defaulted = statement.get(1).unwrap();
+    assert_eq!(defaulted.status, LoanStatus::Defaulted);
+    assert_eq!(defaulted.principal, 2_000);
+    assert_eq!(defaulted.total_owed, 0);
+    assert_eq!(defaulted.due_date, due_date);
+
+    let active = statement.get(2).unwrap();
+    assert_eq!(active.status, LoanStatus::Approved);
+    assert_eq!(active.principal_remaining, 3_000);
+    assert_eq!(active.total_owed, manager.get_amount_owed(&active_id));
+    assert!(!active.is_overdue);
+    assert_eq!(active.due_date, manager.get_loan(&active_id).due_date);
+
+    // Paging walks the borrower's loan list.
+    let page = manager.borrower_statement(&borrower, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().loan_id, defaulted_id);
+    assert_eq!(manager.borrower_statement(&borrower, &3, &10).len(), 0);
+}
+
+#[test]
+fn test_effective_apr_for_30_and_365_day_loans() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &1_000_000);
+    manager.set_interest_rate(&10);
+    env.ledger().set_sequence_number(100);
+    const DAY: u32 = 17_280;
+
+    let mut loans = soroban_sdk::Vec::<u32>::new(&env);
+    for days in [30u32, 365] {
+        let borrower = Address::generate(&env);
+        nft_client.mint(
+            &borrower,
+            &600,
+            &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+            &String::from_str(&env, "ipfs://QmTest"),
+            &None,
+        );
+        manager.set_default_term(&(days * DAY));
+        let loan_id = manager
+            .request_loan(
+                &borrower,
+                &40_000,
+                &(days * DAY),
+                &None,
+                &None,
+                &None,
+                &None,
+            )
+            .loan_id;
+        manager.approve_loan(&loan_id);
+        assert_eq!(manager.get_loan(&loan_id).term_ledgers, days * DAY);
+        loans.push_back(loan_id);
+    }
+
+    let start = env.ledger().sequence();
+    env.ledger().set_sequence_number(start + 10 * DAY);
+    for loan_id in loans.iter() {
+        let loan = manager.get_loan(&loan_id);
+        let apr = manager.effective_apr_bps(&loan_id);
+        // Rate per day annualized over 365 days, whatever the term.
+        assert_eq!(apr, loan.interest_rate_bps * 365);
+        // Ten days of accrual match the annualized rate.
+        assert_eq!(
+            manager.get_amount_owed(&loan_id) - loan.amount,
+            40_000 * apr as i128 * 10 / 365 / 10_000
+        );
+    }
+}
+
+#[test]
+fn test_zero_loan_grace_is_defaultable_right_after_due() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &10_000);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let loan_id = manager
+        .request_loan(&borrower, &1000, &17280, &None, &None, &Some(0), &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.grace_ledgers, Some(0));
+
+    env.ledger().set_sequence_number(loan.due_date + 1);
+    manager.check_default(&admin, &loan_id);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Defaulted);
+}
+
+#[test]
+fn test_long_loan_grace_outlasts_global_window() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &10_000);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let global_window = manager.get_default_window_ledgers();
+    let long_grace = global_window * 3;
+    assert_eq!(manager.get_max_loan_grace(), global_window);
+    assert_eq!(
+        manager.try_request_loan(
+            &borrower,
+            &1000,
+            &17280,
+            &None,
+            &None,
+            &Some(long_grace),
+            &None
+        ),
+        Err(Ok(LoanError::InvalidGracePeriod))
+    );
+
+    manager.set_max_loan_grace(&long_grace);
+    let loan_id = manager
+        .request_loan(
+            &borrower,
+            &1000,
+            &17280,
+            &None,
+            &None,
+            &Some(long_grace),
+            &None,
+        )
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    let due_date = manager.get_loan(&loan_id).due_date;
+
+    env.ledger()
+        .set_sequence_number(due_date + global_window + 1);
+    assert_eq!(
+        manager.try_check_default(&admin, &loan_id),
+        Err(Ok(LoanError::LoanNotPastDue))
+    );
+
+    env.ledger().set_sequence_number(due_date + long_grace + 1);
+    manager.check_default(&admin, &loan_id);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Defaulted);
+}
+
+#[test]
+fn test_simulate_request_matches_actual_receipt() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &10_000);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &700,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let simulated = manager.simulate_request(&borrower, &2_500);
+    assert_eq!(manager.get_total_loans(), 0);
+
+    let term = simulated.term_ledgers;
+    let receipt = manager.request_loan(&borrower, &2_500, &term, &None, &None, &None, &None);
+    assert_eq!(simulated, receipt);
+}
+
+#[test]
+fn test_simulate_request_reports_ineligible_borrower() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_address, _token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &400,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    assert_eq!(
+        manager.try_simulate_request(&borrower, &1_000),
+        Err(Ok(LoanError::InsufficientScore))
+    );
+    assert_eq!(
+        manager.try_simulate_request(&borrower, &0),
+        Err(Ok(LoanError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_repaid_interest_accumulates_in_pool_lifetime_total() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    pool_client.set_loan_manager(&manager.address);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+
+    let borrower = Address::generate(&env);
+    stellar_token.mint(&borrower, &100_000);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &40_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    env.ledger().set_sequence_number(100 + 8_640);
+    manager.repay(&borrower, &loan_id, &10_000, &None);
+    let first = manager.get_loan(&loan_id).interest_paid;
+    assert!(first > 0);
+    assert_eq!(pool_client.total_interest_earned(&token_id), first);
+
+    env.ledger().set_sequence_number(100 + 17_000);
+    let owed = manager.get_amount_owed(&loan_id);
+    manager.repay(&borrower, &loan_id, &owed, &None);
+    let total = manager.get_loan(&loan_id).interest_paid;
+    assert!(total > first);
+    assert_eq!(pool_client.total_interest_earned(&token_id), total);
+}
+
+#[test]
+fn test_approve_loan_respects_pool_reserve() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&provider, &10_000);
+    pool_client.set_withdrawal_cooldown(&0);
+    pool_client.deposit(&provider, &token_id, &10_000);
+    pool_client.set_reserve_ratio(&5_000);
+
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let too_big = manager
+        .request_loan(&borrower, &6_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(
+        manager.try_approve_loan(&too_big),
+        Err(Ok(LoanError::InsufficientPoolLiquidity))
+    );
+
+    let fits = manager
+        .request_loan(&borrower, &5_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&fits);
+    assert_eq!(pool_client.capacity(&token_id).borrow_headroom, 0);
+}
+
+#[test]
+fn test_cosigner_backs_low_score_borrower_and_shares_default() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &10_000);
+    let borrower = Address::generate(&env);
+    let cosigner = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &400,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    nft_client.mint(
+        &cosigner,
+        &700,
+        &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None),
+        Err(Ok(LoanError::InsufficientScore))
+    );
+    assert_eq!(
+        manager.try_request_loan(
+            &borrower,
+            &1_000,
+            &17280,
+            &None,
+            &None,
+            &None,
+            &Some(borrower.clone())
+        ),
+        Err(Ok(LoanError::InvalidConfiguration))
+    );
+
+    let loan_id = manager
+        .request_loan(
+            &borrower,
+            &1_000,
+            &17280,
+            &None,
+            &None,
+            &None,
+            &Some(cosigner.clone()),
+        )
+        .loan_id;
+    assert_eq!(manager.get_loan(&loan_id).cosigner, Some(cosigner.clone()));
+    manager.approve_loan(&loan_id);
+
+    let due_date = manager.get_loan(&loan_id).due_date;
+    env.ledger()
+        .set_sequence_number(due_date + manager.get_default_window_ledgers() + 1);
+    manager.check_default(&admin, &loan_id);
+
+    assert!(nft_client.get_score(&borrower) < 400);
+    assert!(nft_client.get_score(&cosigner) < 700);
+    assert_eq!(nft_client.get_default_count(&borrower), 1);
+    assert_eq!(nft_client.get_default_count(&cosigner), 1);
+}
+
+#[test]
+fn test_expired_approval_frees_reserved_collateral() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, token, borrower, loan_id) = approve_with_disbursement_delay(&env, 3_600);
+    let token_id = token.address.clone();
+    StellarAssetClient::new(&env, &token_id).mint(&borrower, &400);
+    manager.deposit_collateral(&loan_id, &400);
+    manager.set_disbursement_claim_window(&600);
+    assert_eq!(token.balance(&borrower), 0);
+    assert_eq!(manager.get_borrower_loan_count(&borrower), 1);
+    assert_eq!(manager.get_total_outstanding(&token_id), 1_000);
+
+    // Still inside the claim window.
+    env.ledger().with_mut(|li| li.timestamp = 14_199);
+    assert_eq!(
+        manager.try_expire_approval(&loan_id),
+        Err(Ok(LoanError::ApprovalNotExpired))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 14_200);
+    assert_eq!(
+        manager.try_claim_disbursement(&loan_id),
+        Err(Ok(LoanError::ApprovalExpired))
+    );
+    manager.expire_approval(&loan_id);
+
+    let loan = manager.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Cancelled);
+    assert_eq!(loan.collateral_amount, 0);
+    assert_eq!(token.balance(&borrower), 400);
+    assert_eq!(manager.get_borrower_loan_count(&borrower), 0);
+    assert_eq!(manager.get_total_outstanding(&token_id), 0);
+    assert_eq!(manager.get_pending_disbursement(&loan_id), None);
+}
+
+#[test]
+fn test_borrow_invariant_pages_and_detects_drift() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&pool_address, &100_000);
+    manager.set_max_loans_per_borrower(&5);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    for amount in [1_000i128, 2_000, 3_000] {
+        let loan_id = manager
+            .request_loan(&borrower, &amount, &17280, &None, &None, &None, &None)
+            .loan_id;
+        manager.approve_loan(&loan_id);
+    }
+
+    let first = manager.check_borrow_invariant(&token_id, &0, &2, &0);
+    assert_eq!(first.summed, 3_000);
+    assert_eq!(first.next_start, Some(2));
+    assert!(first.holds);
+    let last = manager.check_borrow_invariant(&token_id, &2, &2, &first.summed);
+    assert_eq!(last.summed, 6_000);
+    assert_eq!(last.next_start, None);
+    assert!(last.holds);
+
+    // Corrupt the aggregate counter.
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalOutstanding(token_id.clone()), &5_000i128);
+    });
+    assert!(!manager.check_borrow_invariant(&token_id, &0, &10, &0).holds);
+}
+
+#[test]
+fn test_repay_full_closes_loan_as_debt_grows() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+    let borrower = Address::generate(&env);
+    stellar_token.mint(&borrower, &10_000);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    env.ledger().set_sequence_number(100);
+    let loan_id = manager
+        .request_loan(&borrower, &40_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    env.ledger().set_sequence_number(5_000);
+    let quoted = manager.get_amount_owed(&loan_id);
+    // The transaction lands later, after more interest has accrued.
+    env.ledger().set_sequence_number(9_000);
+    let owed = manager.get_amount_owed(&loan_id);
+    assert!(owed > quoted);
+
+    let token = TokenClient::new(&env, &token_id);
+    let before = token.balance(&borrower);
+    assert_eq!(manager.repay_full(&borrower, &loan_id), owed);
+    assert_eq!(before - token.balance(&borrower), owed);
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
+    assert_eq!(
+        manager.try_repay_full(&borrower, &loan_id),
+        Err(Ok(LoanError::LoanNotActive))
+    );
+}
+
+#[test]
+fn test_borrower_outstanding_cap_rejects_loan_over_the_cap() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    assert_eq!(
+        manager.try_set_max_outstanding_per_borrower(&-1),
+        Err(Ok(LoanError::InvalidAmount))
+    );
+    manager.set_max_outstanding_per_borrower(&50_000);
+    assert_eq!(manager.get_max_outstanding_per_borrower(), 50_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &30_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert_eq!(manager.get_borrower_outstanding(&borrower), 30_000);
+
+    assert_eq!(
+        manager.try_request_loan(&borrower, &25_000, &17280, &None, &None, &None, &None),
+        Err(Ok(LoanError::BorrowerDebtCapExceeded))
+    );
+    manager.request_loan(&borrower, &20_000, &17280, &None, &None, &None, &None);
+
+    manager.set_max_outstanding_per_borrower(&0);
+    manager.request_loan(&borrower, &25_000, &17280, &None, &None, &None, &None);
+}
+
+#[test]
+fn test_roles_reflects_current_configuration() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, _pool_address, _token_id, admin) = setup_test(&env);
+    let roles = manager.roles();
+    assert_eq!(roles.admin, admin);
+    assert_eq!(roles.pending_admin, None);
+    assert_eq!(roles.keepers.len(), 0);
+    // The NFT admin is a minter from initialize; setup adds the manager.
+    let initial_minters = soroban_sdk::vec![&env, admin.clone(), manager.address.clone()];
+    assert_eq!(roles.minters, initial_minters);
+
+    let keeper_a = Address::generate(&env);
+    let keeper_b = Address::generate(&env);
+    manager.add_keeper(&keeper_a);
+    manager.add_keeper(&keeper_b);
+    manager.add_keeper(&keeper_a);
+    manager.remove_keeper(&keeper_a);
+
+    let minter = Address::generate(&env);
+    nft_client.authorize_minter(&minter);
+    let new_admin = Address::generate(&env);
+    manager.propose_admin(&new_admin);
+
+    let roles = manager.roles();
+    assert_eq!(roles.pending_admin, Some(new_admin));
+    assert_eq!(roles.keepers, soroban_sdk::vec![&env, keeper_b]);
+    let mut minters = initial_minters.clone();
+    minters.push_back(minter.clone());
+    assert_eq!(roles.minters, minters);
+
+    nft_client.revoke_minter(&minter);
+    assert_eq!(manager.roles().minters, initial_minters);
+}
+
+#[test]
+fn test_backfill_keepers_lists_pre_upgrade_keepers() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, _nft_client, _pool_address, _token_id, _admin) = setup_test(&env);
+    let listed = Address::generate(&env);
+    let legacy = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    manager.add_keeper(&listed);
+
+    // A keeper granted before the list existed has only its per-address key.
+    env.as_contract(&manager.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Keeper(legacy.clone()), &true);
+    });
+    assert!(manager.is_keeper(&legacy));
+    assert_eq!(
+        manager.roles().keepers,
+        soroban_sdk::vec![&env, listed.clone()]
+    );
+
+    let candidates = soroban_sdk::vec![&env, listed.clone(), legacy.clone(), stranger];
+    assert_eq!(manager.backfill_keepers(&candidates), 1);
+    assert_eq!(manager.backfill_keepers(&candidates), 0);
+    assert_eq!(
+        manager.roles().keepers,
+        soroban_sdk::vec![&env, listed, legacy]
+    );
+}
+
+#[test]
+fn test_partial_repayment_settles_late_fee_then_interest() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _token_admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &10_000);
+    stellar_token.mint(&borrower, &10_000);
+
+    manager.set_late_fee_rate(&500);
+    manager.set_grace_period_ledgers(&0);
+    env.ledger().set_sequence_number(1);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    let due_date = manager.get_loan(&loan_id).due_date;
+    env.ledger().set_sequence_number(due_date + 8_640);
+    let before = manager.loan_summary(&loan_id);
+    assert_eq!(before.late_fee_due, 25);
+    assert_eq!(before.interest_due, 180);
+
+    // Covers the whole late fee and 75 of the interest; no principal.
+    manager.repay(&borrower, &loan_id, &100, &None);
+
+    let summary = manager.loan_summary(&loan_id);
+    assert_eq!(summary.late_fee_paid, 25);
+    assert_eq!(summary.late_fee_due, 0);
+    assert_eq!(summary.interest_paid, 75);
+    assert_eq!(summary.interest_due, 105);
+    assert_eq!(summary.principal_paid, 0);
+    assert_eq!(summary.principal_remaining, 1_000);
+    assert_eq!(summary.total_paid, 100);
+}
+
+#[test]
+fn test_borrow_blackout_blocks_borrowing_inside_the_window() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+    let borrower = Address::generate(&env);
+    stellar_token.mint(&borrower, &10_000);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    let pending_id = manager
+        .request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+
+    assert_eq!(
+        manager.try_set_borrow_blackout(&3_000, &2_000),
+        Err(Ok(LoanError::InvalidConfiguration))
+    );
+    manager.set_borrow_blackout(&2_000, &3_000);
+    assert!(!manager.is_borrow_blackout());
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(manager.is_borrow_blackout());
+    assert_eq!(
+        manager.try_request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None),
+        Err(Ok(LoanError::BorrowBlackout))
+    );
+    assert_eq!(
+        manager.try_approve_loan(&pending_id),
+        Err(Ok(LoanError::BorrowBlackout))
+    );
+    // Repayments are unaffected.
+    manager.repay(&borrower, &loan_id, &500, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    assert!(!manager.is_borrow_blackout());
+    manager.approve_loan(&pending_id);
+    manager.request_loan(&borrower, &1_000, &17280, &None, &None, &None, &None);
+}
+
+#[test]
+fn test_get_repay_token_follows_the_loan_token() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    assert_eq!(manager.get_repay_token(&None), token_id);
+    assert_eq!(
+        manager.try_get_repay_token(&Some(999)),
+        Err(Ok(LoanError::LoanNotFound))
+    );
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    pool_client.set_supported_token(&other_token, &true);
+    let default_loan = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    let other_loan = manager
+        .request_loan(
+            &borrower,
+            &1_000,
+            &17_280,
+            &None,
+            &Some(other_token.clone()),
+            &None,
+            &None,
+        )
+        .loan_id;
+
+    assert_eq!(manager.get_repay_token(&Some(default_loan)), token_id);
+    assert_eq!(manager.get_repay_token(&Some(other_loan)), other_token);
+}
+
+#[test]
+fn test_loan_repaid_event_carries_score_delta() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_address, &100_000);
+    let borrower = Address::generate(&env);
+    stellar_token.mint(&borrower, &10_000);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    let loan_id = manager
+        .request_loan(&borrower, &40_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+
+    manager.repay(&borrower, &loan_id, &20_000, &None);
+    let events = env.events().all();
+    let repaid = events.get(events.len() - 1).unwrap();
+    let new_score = nft_client.get_score(&borrower);
+    assert!(new_score > 600);
+
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&env, &repaid.1.get(0).unwrap()),
+        soroban_sdk::Symbol::new(&env, "LoanRepaid")
+    );
+    let (amount, score_delta, score) = <(i128, i32, u32)>::from_val(&env, &repaid.2);
+    assert_eq!(amount, 20_000);
+    assert_eq!(score_delta, new_score as i32 - 600);
+    assert_eq!(score, new_score);
+}
+
+#[test]
+fn test_strict_collateral_lock_blocks_provider_withdrawals() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_address, token_id, _admin) = setup_test(&env);
+    let pool_client = LendingPoolClient::new(&env, &pool_address);
+    pool_client.set_withdrawal_cooldown(&0);
+    pool_client.set_loan_manager(&manager.address);
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+
+    // The provider is also a borrower with part of their balance posted as
+    // collateral.
+    let provider = Address::generate(&env);
+    stellar_token.mint(&provider, &10_000);
+    pool_client.deposit(&provider, &token_id, &5_000);
+    nft_client.mint(
+        &provider,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let loan_id = manager
+        .request_loan(&provider, &1_000, &17280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    assert!(!manager.has_locked_collateral(&provider));
+    manager.deposit_collateral(&loan_id, &500);
+    assert!(manager.has_locked_collateral(&provider));
+
+    // Off by default: withdrawals proceed.
+    assert!(!pool_client.get_strict_collateral_lock());
+    pool_client.withdraw(&provider, &token_id, &1_000);
+
+    pool_client.set_strict_collateral_lock(&true);
+    assert_eq!(
+        pool_client.try_withdraw(&provider, &token_id, &1_000),
+        Err(Ok(lending_pool::PoolError::CollateralLocked))
+    );
+
+    // Other providers are unaffected.
+    let other = Address::generate(&env);
+    stellar_token.mint(&other, &1_000);
+    pool_client.deposit(&other, &token_id, &1_000);
+    pool_client.withdraw(&other, &token_id, &1_000);
+}
+
+#[test]
+fn test_amount_owed_at_projects_future_debt() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+    env.ledger().set_sequence_number(100);
+    env.ledger().set_timestamp(10_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &10_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&loan_id);
+    let owed_now = manager.get_amount_owed(&loan_id);
+    assert_eq!(manager.amount_owed_at(&loan_id, &10_000), owed_now);
+
+    // Half a term ahead, then a full term ahead.
+    let half = manager.amount_owed_at(&loan_id, &(10_000 + 8_640 * 5));
+    let full = manager.amount_owed_at(&loan_id, &(10_000 + 17_280 * 5));
+    assert!(half > owed_now);
+    assert!(full > half);
+
+    // Projections leave the stored loan untouched.
+    assert_eq!(manager.get_amount_owed(&loan_id), owed_now);
+
+    // The projection matches what is owed once the ledger gets there.
+    env.ledger().set_sequence_number(100 + 8_640);
+    env.ledger().set_timestamp(10_000 + 8_640 * 5);
+    assert_eq!(manager.get_amount_owed(&loan_id), half);
+
+    assert_eq!(
+        manager.try_amount_owed_at(&loan_id, &10_000),
+        Err(Ok(LoanError::TimestampInPast))
+    );
+}
+
+#[test]
+fn test_tranches_release_only_after_their_time() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+    let token = TokenClient::new(&env, &token_id);
+    env.ledger().set_timestamp(1_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &10_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(manager.add_tranche(&loan_id, &3_000, &2_000), 0);
+    assert_eq!(manager.add_tranche(&loan_id, &4_000, &3_000), 1);
+    // Tranches may not add up to more than the principal.
+    assert_eq!(
+        manager.try_add_tranche(&loan_id, &3_001, &4_000),
+        Err(Ok(LoanError::InvalidAmount))
+    );
+    assert_eq!(
+        manager.try_add_tranche(&loan_id, &1_000, &999),
+        Err(Ok(LoanError::TimestampInPast))
+    );
+
+    // Approval pays out only the unscheduled 3_000, and only that is owed.
+    manager.approve_loan(&loan_id);
+    assert_eq!(token.balance(&borrower), 3_000);
+    assert_eq!(manager.get_loan(&loan_id).amount, 3_000);
+    assert_eq!(manager.get_borrower_outstanding(&borrower), 3_000);
+    assert_eq!(manager.get_total_outstanding(&token_id), 3_000);
+    assert_eq!(
+        manager.try_add_tranche(&loan_id, &1_000, &4_000),
+        Err(Ok(LoanError::LoanNotPending))
+    );
+
+    assert_eq!(
+        manager.try_release_tranche(&loan_id, &0),
+        Err(Ok(LoanError::DisbursementLocked))
+    );
+    env.ledger().set_timestamp(2_000);
+    manager.release_tranche(&loan_id, &0);
+    assert_eq!(token.balance(&borrower), 6_000);
+    assert_eq!(manager.get_loan(&loan_id).amount, 6_000);
+    assert_eq!(manager.get_borrower_outstanding(&borrower), 6_000);
+    assert_eq!(
+        manager.try_release_tranche(&loan_id, &0),
+        Err(Ok(LoanError::TrancheAlreadyReleased))
+    );
+    assert_eq!(
+        manager.try_release_tranche(&loan_id, &1),
+        Err(Ok(LoanError::DisbursementLocked))
+    );
+
+    env.ledger().set_timestamp(3_000);
+    manager.release_tranche(&loan_id, &1);
+    assert_eq!(token.balance(&borrower), 10_000);
+    assert_eq!(
+        manager.try_release_tranche(&loan_id, &2),
+        Err(Ok(LoanError::TrancheNotFound))
+    );
+
+    let tranches = manager.get_tranches(&loan_id);
+    assert_eq!(tranches.len(), 2);
+    assert!(tranches.iter().all(|tranche| tranche.released));
+    assert_eq!(manager.get_loan(&loan_id).amount, 10_000);
+    assert_eq!(manager.get_total_outstanding(&token_id), 10_000);
+}
+
+#[test]
+fn test_repaying_early_cancels_unreleased_tranches() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    StellarAssetClient::new(&env, &token_id).mint(&pool_client, &100_000);
+    StellarAssetClient::new(&env, &token_id).mint(&borrower, &1_000);
+    let token = TokenClient::new(&env, &token_id);
+    env.ledger().set_timestamp(1_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &10_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.add_tranche(&loan_id, &7_000, &5_000);
+    manager.approve_loan(&loan_id);
+
+    // Only the 3_000 paid out is owed; repaying it closes the loan.
+    let paid = manager.repay_full(&borrower, &loan_id);
+    assert!((3_000..3_100).contains(&paid));
+    assert_eq!(manager.get_loan(&loan_id).status, LoanStatus::Repaid);
+    assert_eq!(manager.get_borrower_outstanding(&borrower), 0);
+    assert_eq!(manager.get_total_outstanding(&token_id), 0);
+
+    let tranche = manager.get_tranches(&loan_id).get(0).unwrap();
+    assert!(tranche.cancelled && !tranche.released);
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(
+        manager.try_release_tranche(&loan_id, &0),
+        Err(Ok(LoanError::LoanNotActive))
+    );
+    assert_eq!(token.balance(&borrower), 4_000 - paid);
+
+    // The reserve held for the tranche is free again for a new loan.
+    let other = Address::generate(&env);
+    nft_client.mint(
+        &other,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let other_loan = manager
+        .request_loan(&other, &10_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.approve_loan(&other_loan);
+    assert_eq!(token.balance(&other), 10_000);
+}
+
+fn status_changes(env: &Env) -> soroban_sdk::Vec<(u32, LoanStatus)> {
+    let mut changes = soroban_sdk::Vec::new(env);
+    for event in env.events().all().iter() {
+        let name = soroban_sdk::Symbol::from_val(env, &event.1.get(0).unwrap());
+        if name == soroban_sdk::Symbol::new(env, "LoanStatusChanged") {
+            changes.push_back((
+                u32::from_val(env, &event.1.get(1).unwrap()),
+                LoanStatus::from_val(env, &event.2),
+            ));
+        }
+    }
+    changes
+}
+
+#[test]
+fn test_each_status_transition_emits_one_status_event() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (manager, nft_client, pool_client, token_id, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+    let stellar_token = StellarAssetClient::new(&env, &token_id);
+    stellar_token.mint(&pool_client, &100_000);
+    stellar_token.mint(&borrower, &1_000);
+
+    let loan_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    assert_eq!(
+        status_changes(&env),
+        soroban_sdk::vec![&env, (loan_id, LoanStatus::Pending)]
+    );
+    manager.approve_loan(&loan_id);
+    assert_eq!(
+        status_changes(&env),
+        soroban_sdk::vec![&env, (loan_id, LoanStatus::Approved)]
+    );
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 100);
+    manager.repay_full(&borrower, &loan_id);
+    assert_eq!(
+        status_changes(&env),
+        soroban_sdk::vec![&env, (loan_id, LoanStatus::Repaid)]
+    );
+
+    let cancelled_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.cancel_loan(&borrower, &cancelled_id);
+    assert_eq!(
+        status_changes(&env),
+        soroban_sdk::vec![&env, (cancelled_id, LoanStatus::Cancelled)]
+    );
+
+    let rejected_id = manager
+        .request_loan(&borrower, &1_000, &17_280, &None, &None, &None, &None)
+        .loan_id;
+    manager.reject_loan(&rejected_id, &String::from_str(&env, "incomplete"));
+    assert_eq!(
+        status_changes(&env),
+        soroban_sdk::vec![&env, (rejected_id, LoanStatus::Rejected)]
+    );
+
+    // Reads emit nothing.
+    manager.get_loan(&rejected_id);
+    assert!(status_changes(&env).is_empty());
+}
+
+#[test]
+fn test_repay_rejects_a_loan_that_does_not_exist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (manager, nft_client, _pool, _token, _admin) = setup_test(&env);
+    let borrower = Address::generate(&env);
+    nft_client.mint(
+        &borrower,
+        &600,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://QmTest"),
+        &None,
+    );
+
+    assert_eq!(
+        manager.try_repay(&borrower, &999, &1_000, &None),
+        Err(Ok(LoanError::LoanNotFound))
+    );
+    assert_eq!(nft_client.get_score(&borrower), 600);
+}
@@ -22,6 +22,7 @@ const PROPOSAL_TTL_SECONDS: u64 = 604_800;
 
 const KEY_ADMIN: Symbol = symbol_short!("ADMIN");
 const KEY_VERSION: Symbol = symbol_short!("VERSION");
+const KEY_STORAGE_VERSION: Symbol = symbol_short!("STORE_VER");
 const KEY_PENDING: Symbol = symbol_short!("PENDING");
 const KEY_TARGET: Symbol = symbol_short!("TARGET");
 const KEY_LAST_CANCELLED_AT: Symbol = symbol_short!("CANCEL_AT");
@@ -29,6 +30,7 @@ const KEY_PROPOSAL_COUNT: Symbol = symbol_short!("COUNT");
 
 const REPROPOSAL_COOLDOWN_SECONDS: u64 = 3600; // 1 hour
 const CURRENT_VERSION: u32 = 1;
+const STORAGE_VERSION: u32 = 1;
 
 // ─── Types ────────────────────────────────────────────────────────────────────
 
@@ -170,6 +172,9 @@ impl GovernanceContract {
         env.storage().instance().set(&KEY_ADMIN, &admin);
         env.storage().instance().set(&KEY_TARGET, &target_contract);
         env.storage().instance().set(&KEY_VERSION, &CURRENT_VERSION);
+        env.storage()
+            .instance()
+            .set(&KEY_STORAGE_VERSION, &STORAGE_VERSION);
         env.storage().instance().set(&KEY_PROPOSAL_COUNT, &0u32);
         Ok(())
     }
@@ -178,6 +183,15 @@ impl GovernanceContract {
         env.storage().instance().get(&KEY_VERSION).unwrap_or(0)
     }
 
+    /// Storage layout version, advanced by future migrations. Contracts
+    /// initialized before the marker existed report 0.
+    pub fn storage_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&KEY_STORAGE_VERSION)
+            .unwrap_or(0)
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), GovernanceError> {
         let admin = Self::read_admin(&env)?;
         admin.require_auth();
@@ -228,8 +242,7 @@ impl GovernanceContract {
             .instance()
             .get::<Symbol, u64>(&KEY_LAST_CANCELLED_AT)
         {
-            let now = env.ledger().timestamp();
-            if now < last_cancelled_at.saturating_add(REPROPOSAL_COOLDOWN_SECONDS) {
+            if Self::elapsed_since(&env, last_cancelled_at) < REPROPOSAL_COOLDOWN_SECONDS {
                 return Err(GovernanceError::ReproposalCooldownActive);
             }
         }
@@ -390,8 +403,7 @@ impl GovernanceContract {
         }
 
         // INV-3: proposal must not have expired
-        let expiry_time = pending.proposed_at.saturating_add(PROPOSAL_TTL_SECONDS);
-        if now >= expiry_time {
+        if Self::elapsed_since(&env, pending.proposed_at) >= PROPOSAL_TTL_SECONDS {
             return Err(GovernanceError::ProposalExpired);
         }
 
@@ -529,12 +541,10 @@ impl GovernanceContract {
             return Err(GovernanceError::ProposalNotActive);
         }
 
-        let now = env.ledger().timestamp();
-        let expiry_time = pending.proposed_at.saturating_add(PROPOSAL_TTL_SECONDS);
-
-        if now < expiry_time {
+        if Self::elapsed_since(&env, pending.proposed_at) < PROPOSAL_TTL_SECONDS {
             return Err(GovernanceError::ProposalNotExpired);
         }
+        let now = env.ledger().timestamp();
 
         env.storage().instance().remove(&KEY_PENDING);
 
@@ -654,6 +664,12 @@ impl GovernanceContract {
 
     // ── Private helpers ───────────────────────────────────────────────────────
 
+    /// Seconds elapsed since `past`, or 0 if `past` is ahead of the ledger
+    /// clock (e.g. a timestamp carried across an upgrade or migration).
+    fn elapsed_since(env: &Env, past: u64) -> u64 {
+        env.ledger().timestamp().saturating_sub(past)
+    }
+
     fn read_admin(env: &Env) -> Result<Address, GovernanceError> {
         env.storage()
             .instance()
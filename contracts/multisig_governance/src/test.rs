@@ -66,6 +66,7 @@ fn create_upgrade_hash(env: &Env) -> BytesN<32> {
 fn version_is_initialized() {
     let (_env, client, _admin, _) = setup();
     assert_eq!(client.version(), 1);
+    assert_eq!(client.storage_version(), 1);
 }
 
 #[test]
@@ -712,3 +713,31 @@ fn has_approved_tracks_approvals() {
     assert!(client.has_approved(&s1));
     assert!(client.has_approved(&s2));
 }
+
+#[test]
+fn elapsed_since_clamps_future_timestamps_to_zero() {
+    let env = Env::default();
+    set_ts(&env, 1_000);
+    assert_eq!(GovernanceContract::elapsed_since(&env, 400), 600);
+    assert_eq!(GovernanceContract::elapsed_since(&env, 1_000), 0);
+    assert_eq!(GovernanceContract::elapsed_since(&env, 5_000), 0);
+}
+
+#[test]
+fn proposal_stamped_in_the_future_is_not_expired() {
+    let (env, client, _, _) = setup();
+    set_ts(&env, 10_000);
+    let s = Address::generate(&env);
+    let signers = Vec::from_slice(&env, &[s]);
+    client.propose_admin_transfer(
+        &Address::generate(&env),
+        &signers,
+        &1,
+        &MIN_TIMELOCK_SECONDS,
+    );
+
+    // Ledger clock reports a time before the proposal was recorded.
+    set_ts(&env, 5_000);
+    let result = client.try_expire_proposal(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(GovernanceError::ProposalNotExpired)));
+}
@@ -26,6 +26,16 @@ pub enum NftError {
     BelowMinimum = 17,
     InvalidMetadataUri = 18,
     MinterLimitReached = 19,
+    InvalidDecayConfig = 20,
+    EmptyMinterList = 21,
+    BatchTooLarge = 22,
+    InvalidOutstanding = 23,
+    SoulboundToken = 24,
+    LastMinter = 25,
+    NotLoanManager = 26,
+    InvalidPointTable = 27,
+    InvalidGradeBands = 28,
+    DecayTooSoon = 29,
 }
 
 #[contracttype]
@@ -36,6 +46,36 @@ pub struct RemittanceMetadata {
     pub metadata_uri: String,
 }
 
+/// Inactivity decay: `points_per_period` are deducted for every full
+/// `period_ledgers` since the user's last score change, never below `floor`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreDecayConfig {
+    pub points_per_period: u32,
+    pub period_ledgers: u32,
+    pub floor: u32,
+}
+
+/// Default penalty still being restored in temporary penalty mode: `points`
+/// deducted as of `ledger`, restored linearly over the configured window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultPenalty {
+    pub points: u32,
+    pub ledger: u32,
+}
+
+/// Everything a lender gates a borrow on, returned by `eligibility`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Eligibility {
+    pub has_nft: bool,
+    /// Decay-adjusted, as from `current_score`.
+    pub score: u32,
+    pub seized: bool,
+    pub default_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ScoreHistoryEntry {
@@ -45,6 +85,30 @@ pub struct ScoreHistoryEntry {
     pub reason: Symbol,
 }
 
+/// How integer divisions in borrower-facing figures are rounded.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RoundingMode {
+    /// Truncate toward zero (the default).
+    Floor,
+    /// Round half up.
+    Round,
+}
+
+/// How `update_score` turns a repayment amount into score points.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScoringMode {
+    /// One point per 100 units repaid (the default).
+    Linear,
+    /// Points from the band of the configured point table the repayment
+    /// falls into.
+    Table,
+}
+
+/// Storage keys. Admin and contract-wide settings are instance storage;
+/// per-user records and per-minter authorizations are persistent entries
+/// with their own TTL, bumped whenever they are read or written.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -54,6 +118,7 @@ pub enum DataKey {
     AuthorizedMinters,
     Seized(Address),
     Version,
+    StorageVersion,
     ScoreHistory(Address),
     DefaultCount(Address),
     Burned(Address),
@@ -62,6 +127,38 @@ pub enum DataKey {
     Paused,
     ProposedAdmin,
     MinRepaymentAmount,
+    LegacyDefaultHash,
+    ScoreDecay,
+    LastScoreActivity(Address),
+    NftCount,
+    Outstanding(Address),
+    Soulbound,
+    RoundingMode,
+    RequireMinMinters,
+    AutoMigrateLegacy,
+    LoanManager,
+    StrictScoreUpdates,
+    /// Restore window (ledgers) for default penalties; unset = permanent.
+    DefaultPenaltyTemporary,
+    DefaultPenalty(Address),
+    ScoringMode,
+    /// Ascending `(threshold, points)` bands used by `ScoringMode::Table`.
+    PointTable,
+    /// Ledger timestamp of the user's last score change.
+    ScoreUpdatedAt(Address),
+    /// Lowest score the user has held, including decay observed at a change.
+    MinScoreEver(Address),
+    /// Highest score the user has held.
+    PeakScore(Address),
+    /// Minimum scores for grades `AAA`, `AA`, `A`, `B` and `C`, descending.
+    GradeBands,
+    /// Most points a single `update_score` call can award (0 = uncapped).
+    MaxPointsPerLoan,
+    /// Lowest `initial_score` a mint or remint may set (0 = no floor).
+    MinMintScore,
+    /// Ledgers that must pass after a user's last score change or applied
+    /// decay before `decay_score` will run for them again (0 = no limit).
+    MinDecayInterval,
 }
 
 #[contract]
@@ -74,6 +171,7 @@ impl RemittanceNFT {
     const PERSISTENT_TTL_THRESHOLD: u32 = 17280;
     const PERSISTENT_TTL_BUMP: u32 = 518400;
     const CURRENT_VERSION: u32 = 2;
+    const STORAGE_VERSION: u32 = 1;
     const DEFAULT_BURN_THRESHOLD: u32 = 3;
     pub const MAX_SCORE_HISTORY_ENTRIES: u32 = 50;
     const TRANSFER_COOLDOWN_LEDGERS: u32 = 17280;
@@ -81,6 +179,7 @@ impl RemittanceNFT {
     pub const MAX_SCORE: u32 = 850;
     pub const MAX_ALLOWED_BURN_THRESHOLD: u32 = 1000; // Set as appropriate for your business logic
     pub const MAX_AUTHORIZED_MINTERS: u32 = 32;
+    pub const MAX_SCORE_BATCH: u32 = 50;
     const DEFAULT_MIN_REPAYMENT_AMOUNT: i128 = 0;
     /// Minimum repayment amount accepted by update_score() (1/10 XLM in stroops).
     /// Dust repayments below this threshold award 0 score points due to integer
@@ -88,6 +187,8 @@ impl RemittanceNFT {
     /// events, enabling spam attacks. This floor rejects such calls early with
     /// InvalidRepaymentAmount (error 7).
     pub const MIN_SCORE_UPDATE_REPAYMENT: i128 = 100;
+    /// Default minimum scores for `AAA`, `AA`, `A`, `B` and `C`.
+    const DEFAULT_GRADE_BANDS: [u32; 5] = [800, 740, 670, 580, 500];
 
     fn admin_key() -> soroban_sdk::Symbol {
         symbol_short!("ADMIN")
@@ -111,12 +212,73 @@ impl RemittanceNFT {
         );
     }
 
+    fn add_minter(env: &Env, minter: Address) -> Result<(), NftError> {
+        let key = DataKey::AuthorizedMinter(minter.clone());
+        if env.storage().persistent().has(&key) {
+            Self::bump_persistent_ttl(env, &key);
+            return Ok(());
+        }
+
+        let mut minters = Self::get_authorized_minters_list(env);
+        if minters.len() >= Self::MAX_AUTHORIZED_MINTERS {
+            return Err(NftError::MinterLimitReached);
+        }
+
+        minters.push_back(minter.clone());
+        Self::write_authorized_minters_list(env, &minters);
+
+        env.storage().persistent().set(&key, &true);
+        Self::bump_persistent_ttl(env, &key);
+
+        env.events().publish((symbol_short!("MntAuth"), minter), ());
+        Ok(())
+    }
+
+    fn remove_minter(env: &Env, minter: Address) -> Result<(), NftError> {
+        let require_min_minters: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::RequireMinMinters)
+            .unwrap_or(false);
+        if require_min_minters
+            && env
+                .storage()
+                .persistent()
+                .has(&DataKey::AuthorizedMinter(minter.clone()))
+            && Self::get_authorized_minters_list(env).len() <= 1
+        {
+            return Err(NftError::LastMinter);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AuthorizedMinter(minter.clone()));
+
+        let minters = Self::get_authorized_minters_list(env);
+        let mut updated = Vec::new(env);
+        for existing in minters.iter() {
+            if existing != minter {
+                updated.push_back(existing);
+            }
+        }
+        Self::write_authorized_minters_list(env, &updated);
+
+        env.events().publish((symbol_short!("MntRev"), minter), ());
+        Ok(())
+    }
+
+    /// Panic with the uniform "not initialized" message if `initialize`
+    /// has not run. Admin reads and the pause guard go through this.
+    fn require_initialized(env: &Env) {
+        if !env.storage().instance().has(&Self::admin_key()) {
+            panic!("not initialized");
+        }
+    }
+
     fn admin(env: &Env) -> Address {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
-        env.storage()
-            .instance()
-            .get(&Self::admin_key())
-            .expect("not initialized")
+        env.storage().instance().get(&Self::admin_key()).unwrap()
     }
 
     fn require_admin_or_authorized_minter(
@@ -140,8 +302,14 @@ impl RemittanceNFT {
         Ok(())
     }
 
+    /// History hash assigned to legacy `Score`-only entries when they are
+    /// lazily migrated to `Metadata`. Zero unless the admin configured one.
     fn default_history_hash(env: &Env) -> BytesN<32> {
-        BytesN::from_array(env, &[0u8; 32])
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::LegacyDefaultHash)
+            .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32]))
     }
 
     fn get_authorized_minters_list(env: &Env) -> Vec<Address> {
@@ -173,6 +341,13 @@ impl RemittanceNFT {
         Self::bump_persistent_ttl(env, &list_key);
     }
 
+    fn check_min_mint_score(env: &Env, initial_score: u32) -> Result<(), NftError> {
+        if initial_score < Self::get_min_mint_score(env.clone()) {
+            return Err(NftError::BelowMinimum);
+        }
+        Ok(())
+    }
+
     fn validate_metadata_uri(env: &Env, uri: &String) -> Result<(), NftError> {
         // Check if URI starts with "ipfs://" or "https://"
         let _ipfs_prefix = String::from_str(env, "ipfs://");
@@ -202,22 +377,71 @@ impl RemittanceNFT {
 
         let score_key = DataKey::Score(user.clone());
         if let Some(score) = env.storage().persistent().get::<DataKey, u32>(&score_key) {
-            let migrated_metadata = RemittanceMetadata {
-                score,
-                history_hash: Self::default_history_hash(env),
-                metadata_uri: Self::default_metadata_uri(env),
-            };
+            let migrated_metadata = Self::legacy_metadata(env, score);
             env.storage()
                 .persistent()
                 .set(&metadata_key, &migrated_metadata);
             Self::bump_persistent_ttl(env, &metadata_key);
             env.storage().persistent().remove(&score_key);
+            Self::record_score_low(env, user, score);
+            Self::record_score_peak(env, user, score);
             return Some(migrated_metadata);
         }
 
         None
     }
 
+    fn legacy_metadata(env: &Env, score: u32) -> RemittanceMetadata {
+        RemittanceMetadata {
+            score,
+            history_hash: Self::default_history_hash(env),
+            metadata_uri: Self::default_metadata_uri(env),
+        }
+    }
+
+    /// Authorize a score update, holding it to the loan manager in strict
+    /// mode.
+    fn require_score_updater(env: &Env, minter: Option<Address>) -> Result<(), NftError> {
+        if Self::strict_score_updates(env) && minter != Self::get_loan_manager(env.clone()) {
+            return Err(NftError::NotLoanManager);
+        }
+        Self::require_admin_or_authorized_minter(env, minter)
+    }
+
+    fn strict_score_updates(env: &Env) -> bool {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::StrictScoreUpdates)
+            .unwrap_or(false)
+    }
+
+    fn auto_migrate_legacy(env: &Env) -> bool {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoMigrateLegacy)
+            .unwrap_or(true)
+    }
+
+    /// Metadata for read-only entry points. Migrates a legacy `Score` entry
+    /// like `get_or_migrate_metadata` unless auto-migration is off, in which
+    /// case the legacy score is returned as metadata without writing.
+    fn read_metadata(env: &Env, user: &Address) -> Option<RemittanceMetadata> {
+        if Self::auto_migrate_legacy(env) {
+            return Self::get_or_migrate_metadata(env, user);
+        }
+        let metadata_key = DataKey::Metadata(user.clone());
+        if let Some(metadata) = env.storage().persistent().get(&metadata_key) {
+            Self::bump_persistent_ttl(env, &metadata_key);
+            return Some(metadata);
+        }
+        env.storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::Score(user.clone()))
+            .map(|score| Self::legacy_metadata(env, score))
+    }
+
     fn get_score_history_or_default(env: &Env, user: &Address) -> Vec<ScoreHistoryEntry> {
         let key = DataKey::ScoreHistory(user.clone());
         if let Some(history) = env
@@ -276,6 +500,187 @@ impl RemittanceNFT {
             reason,
         });
         Self::write_score_history(env, user, next_history);
+        // Decay is applied lazily, so the lowest point before this change is
+        // the old score less whatever decay had accrued on it.
+        let decayed_old = old_score - Self::pending_decay(env, user, old_score);
+        Self::record_score_low(env, user, new_score.min(decayed_old));
+        Self::record_score_peak(env, user, new_score);
+        Self::touch_score_activity(env, user);
+    }
+
+    /// Lower `user`'s recorded all-time low to `score` if it is below it.
+    fn record_score_low(env: &Env, user: &Address, score: u32) {
+        let key = DataKey::MinScoreEver(user.clone());
+        let low: Option<u32> = env.storage().persistent().get(&key);
+        if low.is_none_or(|low| score < low) {
+            env.storage().persistent().set(&key, &score);
+        }
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    /// Raise `user`'s recorded peak to `score` if it is above it.
+    fn record_score_peak(env: &Env, user: &Address, score: u32) {
+        let key = DataKey::PeakScore(user.clone());
+        let peak: Option<u32> = env.storage().persistent().get(&key);
+        if peak.is_none_or(|peak| score > peak) {
+            env.storage().persistent().set(&key, &score);
+        }
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    /// Lower `user`'s score by `penalty_points`, floored at the minimum
+    /// score, and record it in their history. Returns `(old, new)` when the
+    /// score changed.
+    fn apply_penalty(
+        env: &Env,
+        user: &Address,
+        penalty_points: u32,
+        reason: Symbol,
+    ) -> Option<(u32, u32)> {
+        let metadata_key = DataKey::Metadata(user.clone());
+        let mut metadata = Self::get_or_migrate_metadata(env, user)
+            .unwrap_or_else(|| panic!("user does not have an NFT"));
+
+        let old_score = metadata.score;
+        let new_score = old_score
+            .saturating_sub(penalty_points)
+            .max(Self::MIN_CREDIT_SCORE);
+        if new_score == old_score {
+            return None;
+        }
+
+        metadata.score = new_score;
+        env.storage().persistent().set(&metadata_key, &metadata);
+        Self::bump_persistent_ttl(env, &metadata_key);
+        Self::append_score_history(env, user, old_score, new_score, reason);
+        Some((old_score, new_score))
+    }
+
+    fn touch_score_activity(env: &Env, user: &Address) {
+        let key = DataKey::LastScoreActivity(user.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &env.ledger().sequence());
+        Self::bump_persistent_ttl(env, &key);
+        let updated_key = DataKey::ScoreUpdatedAt(user.clone());
+        env.storage()
+            .persistent()
+            .set(&updated_key, &env.ledger().timestamp());
+        Self::bump_persistent_ttl(env, &updated_key);
+    }
+
+    /// Points of decay accrued on `score` since the user's last score change.
+    /// Users without a recorded activity ledger (legacy NFTs) do not decay.
+    fn pending_decay(env: &Env, user: &Address, score: u32) -> u32 {
+        let config: ScoreDecayConfig = match env.storage().instance().get(&DataKey::ScoreDecay) {
+            Some(config) => config,
+            None => return 0,
+        };
+        if config.points_per_period == 0 || score <= config.floor {
+            return 0;
+        }
+        let last_activity: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastScoreActivity(user.clone()))
+        {
+            Some(ledger) => ledger,
+            None => return 0,
+        };
+
+        let periods = env.ledger().sequence().saturating_sub(last_activity) / config.period_ledgers;
+        periods
+            .saturating_mul(config.points_per_period)
+            .min(score - config.floor)
+    }
+
+    /// Points of `user`'s default penalty restored so far. Always 0 when
+    /// default penalties are permanent.
+    fn pending_restore(env: &Env, user: &Address) -> u32 {
+        let window: u32 = match env
+            .storage()
+            .instance()
+            .get(&DataKey::DefaultPenaltyTemporary)
+        {
+            Some(window) => window,
+            None => return 0,
+        };
+        let penalty: DefaultPenalty = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::DefaultPenalty(user.clone()))
+        {
+            Some(penalty) => penalty,
+            None => return 0,
+        };
+
+        let elapsed = env.ledger().sequence().saturating_sub(penalty.ledger);
+        if elapsed >= window {
+            return penalty.points;
+        }
+        (penalty.points as u64 * elapsed as u64 / window as u64) as u32
+    }
+
+    /// Write the default penalty points restored so far into `user`'s stored
+    /// score and restart the remaining penalty from the current ledger, so a
+    /// new default does not discard restoration already earned.
+    fn settle_default_penalty(env: &Env, user: &Address) {
+        let key = DataKey::DefaultPenalty(user.clone());
+        let penalty: DefaultPenalty = match env.storage().persistent().get(&key) {
+            Some(penalty) => penalty,
+            None => return,
+        };
+        let restored = Self::pending_restore(env, user);
+        if restored == 0 {
+            return;
+        }
+
+        if let Some(mut metadata) = Self::get_or_migrate_metadata(env, user) {
+            let old_score = metadata.score;
+            metadata.score = old_score.saturating_add(restored).min(Self::MAX_SCORE);
+            let metadata_key = DataKey::Metadata(user.clone());
+            env.storage().persistent().set(&metadata_key, &metadata);
+            Self::bump_persistent_ttl(env, &metadata_key);
+            Self::append_score_history(
+                env,
+                user,
+                old_score,
+                metadata.score,
+                symbol_short!("RESTORE"),
+            );
+        }
+
+        let remaining = DefaultPenalty {
+            points: penalty.points - restored,
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &remaining);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    /// Track `points` just deducted for a default so they can be restored,
+    /// folding in whatever is still unrestored from earlier defaults.
+    fn record_default_penalty(env: &Env, user: &Address, points: u32) {
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::DefaultPenaltyTemporary)
+        {
+            return;
+        }
+        let key = DataKey::DefaultPenalty(user.clone());
+        let unrestored = env
+            .storage()
+            .persistent()
+            .get::<DataKey, DefaultPenalty>(&key)
+            .map(|penalty| penalty.points - Self::pending_restore(env, user))
+            .unwrap_or(0);
+        let penalty = DefaultPenalty {
+            points: unrestored.saturating_add(points),
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &penalty);
+        Self::bump_persistent_ttl(env, &key);
     }
 
     fn has_active_nft(env: &Env, user: &Address) -> bool {
@@ -312,7 +717,139 @@ impl RemittanceNFT {
                 .has(&DataKey::Score(user.clone()))
     }
 
+    /// Move a user's NFT and all identity state (history, default count,
+    /// seized flag) from `from` to `to`. Callers are responsible for auth and
+    /// for checking that `to` is unoccupied.
+    fn move_nft_state(env: &Env, from: &Address, to: &Address, metadata: &RemittanceMetadata) {
+        let from_metadata_key = DataKey::Metadata(from.clone());
+        let to_metadata_key = DataKey::Metadata(to.clone());
+        env.storage().persistent().set(&to_metadata_key, metadata);
+        Self::bump_persistent_ttl(env, &to_metadata_key);
+        env.storage().persistent().remove(&from_metadata_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Score(from.clone()));
+
+        let from_history_key = DataKey::ScoreHistory(from.clone());
+        if let Some(history) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Vec<ScoreHistoryEntry>>(&from_history_key)
+        {
+            let to_history_key = DataKey::ScoreHistory(to.clone());
+            env.storage().persistent().set(&to_history_key, &history);
+            Self::bump_persistent_ttl(env, &to_history_key);
+            env.storage().persistent().remove(&from_history_key);
+        }
+
+        let from_default_key = DataKey::DefaultCount(from.clone());
+        if let Some(default_count) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&from_default_key)
+        {
+            let to_default_key = DataKey::DefaultCount(to.clone());
+            env.storage()
+                .persistent()
+                .set(&to_default_key, &default_count);
+            Self::bump_persistent_ttl(env, &to_default_key);
+            env.storage().persistent().remove(&from_default_key);
+        }
+
+        let from_seized_key = DataKey::Seized(from.clone());
+        if env.storage().persistent().has(&from_seized_key) {
+            let to_seized_key = DataKey::Seized(to.clone());
+            env.storage().persistent().set(&to_seized_key, &true);
+            Self::bump_persistent_ttl(env, &to_seized_key);
+            env.storage().persistent().remove(&from_seized_key);
+        }
+
+        let from_activity_key = DataKey::LastScoreActivity(from.clone());
+        if let Some(ledger) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&from_activity_key)
+        {
+            let to_activity_key = DataKey::LastScoreActivity(to.clone());
+            env.storage().persistent().set(&to_activity_key, &ledger);
+            Self::bump_persistent_ttl(env, &to_activity_key);
+            env.storage().persistent().remove(&from_activity_key);
+        }
+
+        let from_updated_key = DataKey::ScoreUpdatedAt(from.clone());
+        if let Some(timestamp) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u64>(&from_updated_key)
+        {
+            let to_updated_key = DataKey::ScoreUpdatedAt(to.clone());
+            env.storage().persistent().set(&to_updated_key, &timestamp);
+            Self::bump_persistent_ttl(env, &to_updated_key);
+            env.storage().persistent().remove(&from_updated_key);
+        }
+
+        let from_low_key = DataKey::MinScoreEver(from.clone());
+        if let Some(low) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&from_low_key)
+        {
+            let to_low_key = DataKey::MinScoreEver(to.clone());
+            env.storage().persistent().set(&to_low_key, &low);
+            Self::bump_persistent_ttl(env, &to_low_key);
+            env.storage().persistent().remove(&from_low_key);
+        }
+
+        let from_peak_key = DataKey::PeakScore(from.clone());
+        if let Some(peak) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&from_peak_key)
+        {
+            let to_peak_key = DataKey::PeakScore(to.clone());
+            env.storage().persistent().set(&to_peak_key, &peak);
+            Self::bump_persistent_ttl(env, &to_peak_key);
+            env.storage().persistent().remove(&from_peak_key);
+        }
+
+        let from_penalty_key = DataKey::DefaultPenalty(from.clone());
+        if let Some(penalty) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, DefaultPenalty>(&from_penalty_key)
+        {
+            let to_penalty_key = DataKey::DefaultPenalty(to.clone());
+            env.storage().persistent().set(&to_penalty_key, &penalty);
+            Self::bump_persistent_ttl(env, &to_penalty_key);
+            env.storage().persistent().remove(&from_penalty_key);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RemintApproval(from.clone()));
+    }
+
+    /// Adjust the live NFT count. NFTs minted before the counter existed are
+    /// not included.
+    fn adjust_nft_count(env: &Env, increment: bool) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NftCount)
+            .unwrap_or(0);
+        let next = if increment {
+            count.checked_add(1).expect("nft count overflow")
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage().instance().set(&DataKey::NftCount, &next);
+        Self::bump_instance_ttl(env);
+    }
+
     fn burn_internal(env: &Env, user: &Address) {
+        if Self::has_any_remittance_state(env, user) {
+            Self::adjust_nft_count(env, false);
+        }
         env.storage()
             .persistent()
             .remove(&DataKey::Metadata(user.clone()));
@@ -331,6 +868,21 @@ impl RemittanceNFT {
         env.storage()
             .persistent()
             .remove(&DataKey::TransferCooldown(user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LastScoreActivity(user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DefaultPenalty(user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ScoreUpdatedAt(user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MinScoreEver(user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PeakScore(user.clone()));
 
         let burned_key = DataKey::Burned(user.clone());
         env.storage().persistent().set(&burned_key, &true);
@@ -351,6 +903,9 @@ impl RemittanceNFT {
         env.storage()
             .instance()
             .set(&DataKey::Version, &Self::CURRENT_VERSION);
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &Self::STORAGE_VERSION);
         env.storage().instance().set(&DataKey::Paused, &false);
         Self::bump_instance_ttl(&env);
         // Admin is automatically authorized to mint
@@ -365,6 +920,7 @@ impl RemittanceNFT {
     }
 
     fn assert_not_paused(env: &Env) -> Result<(), NftError> {
+        Self::require_initialized(env);
         Self::bump_instance_ttl(env);
         let paused: bool = env
             .storage()
@@ -382,6 +938,16 @@ impl RemittanceNFT {
         env.storage().instance().get(&DataKey::Version).unwrap_or(0)
     }
 
+    /// Storage layout version, advanced only by `migrate`. Contracts
+    /// initialized before the marker existed report 0.
+    pub fn storage_version(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(0)
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
         Self::admin(&env).require_auth();
         let old_version = Self::version(env.clone());
@@ -396,9 +962,19 @@ impl RemittanceNFT {
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
+    /// Bring storage up to `STORAGE_VERSION`. A no-op when already current;
+    /// panics on a layout newer than this code understands.
     pub fn migrate(env: Env) {
         Self::admin(&env).require_auth();
 
+        let storage_version = Self::storage_version(env.clone());
+        if storage_version == Self::STORAGE_VERSION {
+            return;
+        }
+        if storage_version > Self::STORAGE_VERSION {
+            panic!("unexpected storage version");
+        }
+
         if !env.storage().instance().has(&Self::burn_threshold_key()) {
             env.storage()
                 .instance()
@@ -407,52 +983,47 @@ impl RemittanceNFT {
         env.storage()
             .instance()
             .set(&DataKey::Version, &Self::CURRENT_VERSION);
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &Self::STORAGE_VERSION);
         Self::bump_instance_ttl(&env);
     }
 
     /// Authorize a contract or account to mint NFTs
     pub fn authorize_minter(env: Env, minter: Address) -> Result<(), NftError> {
         Self::admin(&env).require_auth();
+        Self::add_minter(&env, minter)
+    }
 
-        let key = DataKey::AuthorizedMinter(minter.clone());
-        if env.storage().persistent().has(&key) {
-            Self::bump_persistent_ttl(&env, &key);
-            return Ok(());
+    /// Authorize several minters under a single admin auth. Already-authorized
+    /// entries are skipped; the whole call fails if the minter cap is exceeded.
+    pub fn authorize_minters(env: Env, minters: Vec<Address>) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if minters.is_empty() {
+            return Err(NftError::EmptyMinterList);
         }
-
-        let mut minters = Self::get_authorized_minters_list(&env);
-        if minters.len() >= Self::MAX_AUTHORIZED_MINTERS {
-            return Err(NftError::MinterLimitReached);
+        for minter in minters.iter() {
+            Self::add_minter(&env, minter)?;
         }
-
-        minters.push_back(minter.clone());
-        Self::write_authorized_minters_list(&env, &minters);
-
-        env.storage().persistent().set(&key, &true);
-        Self::bump_persistent_ttl(&env, &key);
-
-        env.events().publish((symbol_short!("MntAuth"), minter), ());
         Ok(())
     }
 
     /// Revoke authorization for a contract or account to mint NFTs
-    pub fn revoke_minter(env: Env, minter: Address) {
+    pub fn revoke_minter(env: Env, minter: Address) -> Result<(), NftError> {
         Self::admin(&env).require_auth();
+        Self::remove_minter(&env, minter)
+    }
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::AuthorizedMinter(minter.clone()));
-
-        let minters = Self::get_authorized_minters_list(&env);
-        let mut updated = Vec::new(&env);
-        for existing in minters.iter() {
-            if existing != minter {
-                updated.push_back(existing);
-            }
+    /// Revoke several minters under a single admin auth.
+    pub fn revoke_minters(env: Env, minters: Vec<Address>) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if minters.is_empty() {
+            return Err(NftError::EmptyMinterList);
         }
-        Self::write_authorized_minters_list(&env, &updated);
-
-        env.events().publish((symbol_short!("MntRev"), minter), ());
+        for minter in minters.iter() {
+            Self::remove_minter(&env, minter)?;
+        }
+        Ok(())
     }
 
     /// Check if an address is authorized to mint
@@ -465,10 +1036,39 @@ impl RemittanceNFT {
         is_authorized
     }
 
+    /// Whether `addr` may mint: the admin always can (by minting with no
+    /// `minter`), as can any authorized minter.
+    pub fn can_mint(env: Env, addr: Address) -> bool {
+        addr == Self::admin(&env) || Self::is_authorized_minter(env, addr)
+    }
+
     pub fn get_authorized_minters(env: Env) -> Vec<Address> {
         Self::get_authorized_minters_list(&env)
     }
 
+    /// Number of authorized minters, for health checks.
+    pub fn minter_count(env: Env) -> u32 {
+        Self::get_authorized_minters_list(&env).len()
+    }
+
+    /// When enabled, revoking the last remaining minter is rejected with
+    /// `LastMinter` so minting can't be locked out entirely.
+    pub fn set_require_min_minters(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::RequireMinMinters, &enabled);
+        Self::bump_instance_ttl(&env);
+    }
+
+    pub fn get_require_min_minters(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::RequireMinMinters)
+            .unwrap_or(false)
+    }
+
     /// Mint an NFT representing a user's remittance history and reputation score.
     /// If minter is provided, it must be authorized and must sign the call.
     /// If minter is None, admin must sign the call.
@@ -485,6 +1085,7 @@ impl RemittanceNFT {
 
         // Validate metadata URI format
         Self::validate_metadata_uri(&env, &metadata_uri)?;
+        Self::check_min_mint_score(&env, initial_score)?;
 
         let metadata_key = DataKey::Metadata(user.clone());
         let score_key = DataKey::Score(user.clone());
@@ -508,6 +1109,10 @@ impl RemittanceNFT {
 
         env.storage().persistent().set(&metadata_key, &metadata);
         Self::bump_persistent_ttl(&env, &metadata_key);
+        Self::record_score_low(&env, &user, metadata.score);
+        Self::record_score_peak(&env, &user, metadata.score);
+        Self::touch_score_activity(&env, &user);
+        Self::adjust_nft_count(&env, true);
         env.events()
             .publish((symbol_short!("Mint"), user), initial_score);
 
@@ -538,6 +1143,7 @@ impl RemittanceNFT {
 
         // Validate metadata URI format
         Self::validate_metadata_uri(&env, &metadata_uri)?;
+        Self::check_min_mint_score(&env, initial_score)?;
 
         // Must be a previously burned account — not a first-time mint.
         let burned_key = DataKey::Burned(user.clone());
@@ -583,6 +1189,10 @@ impl RemittanceNFT {
         };
         env.storage().persistent().set(&metadata_key, &metadata);
         Self::bump_persistent_ttl(&env, &metadata_key);
+        Self::record_score_low(&env, &user, metadata.score);
+        Self::record_score_peak(&env, &user, metadata.score);
+        Self::touch_score_activity(&env, &user);
+        Self::adjust_nft_count(&env, true);
 
         // Emit a distinct AdminRemint event — auditably separate from Mint events.
         env.events()
@@ -593,12 +1203,12 @@ impl RemittanceNFT {
 
     /// Get the metadata (score and history hash) for a user's NFT
     pub fn get_metadata(env: Env, user: Address) -> Option<RemittanceMetadata> {
-        Self::get_or_migrate_metadata(&env, &user)
+        Self::read_metadata(&env, &user)
     }
 
     /// Get the metadata URI for a user's NFT
     pub fn get_metadata_uri(env: Env, user: Address) -> Option<String> {
-        Self::get_or_migrate_metadata(&env, &user).map(|metadata| metadata.metadata_uri)
+        Self::read_metadata(&env, &user).map(|metadata| metadata.metadata_uri)
     }
 
     /// Update the metadata URI for a user's NFT (admin function)
@@ -627,15 +1237,321 @@ impl RemittanceNFT {
         Ok(())
     }
 
-    /// Get the score for a user.
-    /// Returns 0 if the user has no NFT.
-    pub fn get_score(env: Env, user: Address) -> u32 {
-        Self::get_or_migrate_metadata(&env, &user)
-            .map(|metadata| metadata.score)
-            .unwrap_or(0)
+    /// Get the score for a user.
+    /// Returns 0 if the user has no NFT.
+    pub fn get_score(env: Env, user: Address) -> u32 {
+        Self::read_metadata(&env, &user)
+            .map(|metadata| metadata.score)
+            .unwrap_or(0)
+    }
+
+    /// Seconds since `user`'s score last changed, for staleness checks.
+    /// Returns `u64::MAX` for users without an NFT, and for NFTs whose
+    /// score has not changed since before this was tracked.
+    pub fn score_age(env: Env, user: Address) -> u64 {
+        let key = DataKey::ScoreUpdatedAt(user);
+        match env.storage().persistent().get::<DataKey, u64>(&key) {
+            Some(updated_at) => {
+                Self::bump_persistent_ttl(&env, &key);
+                env.ledger().timestamp().saturating_sub(updated_at)
+            }
+            None => u64::MAX,
+        }
+    }
+
+    /// Scores for `users`, positionally; 0 for users without an NFT. At most
+    /// `MAX_SCORE_BATCH` users per call.
+    pub fn get_scores(env: Env, users: Vec<Address>) -> Result<Vec<u32>, NftError> {
+        if users.len() > Self::MAX_SCORE_BATCH {
+            return Err(NftError::BatchTooLarge);
+        }
+        let mut scores = Vec::new(&env);
+        for user in users.iter() {
+            scores.push_back(Self::get_score(env.clone(), user));
+        }
+        Ok(scores)
+    }
+
+    /// Get the score for a user with pending inactivity decay applied and,
+    /// in temporary penalty mode, default penalty points restored so far.
+    /// Read-only: the stored score is left untouched. Returns 0 if the user
+    /// has no NFT.
+    pub fn current_score(env: Env, user: Address) -> u32 {
+        let stored = env
+            .storage()
+            .persistent()
+            .get::<DataKey, RemittanceMetadata>(&DataKey::Metadata(user.clone()))
+            .map(|metadata| metadata.score)
+            .or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get::<DataKey, u32>(&DataKey::Score(user.clone()))
+            });
+        match stored {
+            Some(score) => (score - Self::pending_decay(&env, &user, score)
+                + Self::pending_restore(&env, &user))
+            .min(Self::MAX_SCORE),
+            None => 0,
+        }
+    }
+
+    /// Lowest score `user` has ever held, counting penalties and any
+    /// inactivity decay accrued up to now. Never rises on recovery. NFTs
+    /// from before this was tracked start from their score at the time.
+    /// Returns 0 if the user has no NFT.
+    pub fn get_min_score_ever(env: Env, user: Address) -> u32 {
+        let current = Self::current_score(env.clone(), user.clone());
+        let key = DataKey::MinScoreEver(user);
+        match env.storage().persistent().get::<DataKey, u32>(&key) {
+            Some(low) => {
+                Self::bump_persistent_ttl(&env, &key);
+                low.min(current)
+            }
+            None => current,
+        }
+    }
+
+    /// Highest score `user` has ever held. Never falls with penalties or
+    /// decay. NFTs from before this was tracked start from their score at
+    /// the time. Returns 0 if the user has no NFT.
+    pub fn get_peak_score(env: Env, user: Address) -> u32 {
+        let current = Self::current_score(env.clone(), user.clone());
+        let key = DataKey::PeakScore(user);
+        match env.storage().persistent().get::<DataKey, u32>(&key) {
+            Some(peak) => {
+                Self::bump_persistent_ttl(&env, &key);
+                peak.max(current)
+            }
+            None => current,
+        }
+    }
+
+    /// Current score rescaled from `0..=MAX_SCORE` to `0..=scale`, rounding
+    /// down. A score at `MAX_SCORE` maps to exactly `scale`.
+    pub fn normalized_score(env: Env, user: Address, scale: u32) -> u32 {
+        let score = Self::current_score(env, user);
+        let normalized = score as u64 * scale as u64 / Self::MAX_SCORE as u64;
+        normalized.min(scale as u64) as u32
+    }
+
+    /// Configure inactivity decay. Set `points_per_period` to 0 to disable.
+    pub fn set_score_decay(
+        env: Env,
+        points_per_period: u32,
+        period_ledgers: u32,
+        floor: u32,
+    ) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if period_ledgers == 0 || floor > Self::MAX_SCORE {
+            return Err(NftError::InvalidDecayConfig);
+        }
+        let config = ScoreDecayConfig {
+            points_per_period,
+            period_ledgers,
+            floor,
+        };
+        env.storage().instance().set(&DataKey::ScoreDecay, &config);
+        Self::bump_instance_ttl(&env);
+        env.events().publish(
+            (symbol_short!("DecayCfg"),),
+            (points_per_period, period_ledgers, floor),
+        );
+        Ok(())
+    }
+
+    pub fn get_score_decay(env: Env) -> Option<ScoreDecayConfig> {
+        Self::bump_instance_ttl(&env);
+        env.storage().instance().get(&DataKey::ScoreDecay)
+    }
+
+    /// Write `user`'s accrued inactivity decay into their stored score.
+    /// Callable by anyone, e.g. keepers. Decay is derived only from the full
+    /// periods elapsed since the last change, and the activity clock moves
+    /// forward by exactly those periods, so repeated calls never compound:
+    /// a second call in the same ledger changes nothing. Returns the score.
+    pub fn decay_score(env: Env, user: Address) -> Result<u32, NftError> {
+        Self::assert_not_paused(&env)?;
+        let mut metadata =
+            Self::get_or_migrate_metadata(&env, &user).ok_or(NftError::NftNotFound)?;
+
+        let activity_key = DataKey::LastScoreActivity(user.clone());
+        let now = env.ledger().sequence();
+        let min_interval = Self::get_min_decay_interval(env.clone());
+        if let Some(last_activity) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&activity_key)
+        {
+            if now.saturating_sub(last_activity) < min_interval {
+                return Err(NftError::DecayTooSoon);
+            }
+        }
+
+        let decay = Self::pending_decay(&env, &user, metadata.score);
+        if decay == 0 {
+            return Ok(metadata.score);
+        }
+        let config: ScoreDecayConfig = env.storage().instance().get(&DataKey::ScoreDecay).unwrap();
+        let last_activity: u32 = env.storage().persistent().get(&activity_key).unwrap();
+        let consumed = (now - last_activity) / config.period_ledgers * config.period_ledgers;
+
+        let old_score = metadata.score;
+        metadata.score = old_score - decay;
+        let metadata_key = DataKey::Metadata(user.clone());
+        env.storage().persistent().set(&metadata_key, &metadata);
+        Self::bump_persistent_ttl(&env, &metadata_key);
+        Self::append_score_history(
+            &env,
+            &user,
+            old_score,
+            metadata.score,
+            symbol_short!("DECAY"),
+        );
+        // Keep the part of a period already elapsed rather than resetting
+        // the clock to now.
+        env.storage()
+            .persistent()
+            .set(&activity_key, &(last_activity + consumed));
+        env.events().publish(
+            (symbol_short!("Decayed"), user),
+            (old_score, metadata.score),
+        );
+        Ok(metadata.score)
+    }
+
+    /// Make `decay_score` refuse to run for a user until `ledgers` have
+    /// passed since their last score change or applied decay. 0 disables.
+    pub fn set_min_decay_interval(env: Env, ledgers: u32) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MinDecayInterval, &ledgers);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("DecayIntv"),), ledgers);
+    }
+
+    pub fn get_min_decay_interval(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::MinDecayInterval)
+            .unwrap_or(0)
+    }
+
+    /// Make default penalties permanent, or temporary: restored linearly in
+    /// `current_score` over `restore_ledgers` after each default.
+    pub fn set_default_penalty_mode(
+        env: Env,
+        temporary: bool,
+        restore_ledgers: u32,
+    ) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if temporary {
+            if restore_ledgers == 0 {
+                return Err(NftError::InvalidDecayConfig);
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::DefaultPenaltyTemporary, &restore_ledgers);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::DefaultPenaltyTemporary);
+        }
+        Self::bump_instance_ttl(&env);
+        env.events()
+            .publish((symbol_short!("PenMode"),), (temporary, restore_ledgers));
+        Ok(())
+    }
+
+    /// Restore window in ledgers, or `None` when default penalties are
+    /// permanent.
+    pub fn get_default_penalty_window(env: Env) -> Option<u32> {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::DefaultPenaltyTemporary)
+    }
+
+    /// Fold a stray legacy `Score` entry for `user` into their `Metadata`,
+    /// keeping the higher of the two scores, and delete the legacy key.
+    /// A user with only a legacy entry is migrated; one with only
+    /// `Metadata` is left unchanged.
+    pub fn reconcile(env: Env, user: Address, minter: Option<Address>) -> Result<(), NftError> {
+        Self::require_admin_or_authorized_minter(&env, minter)?;
+
+        let score_key = DataKey::Score(user.clone());
+        let metadata_key = DataKey::Metadata(user.clone());
+        let Some(legacy_score) = env.storage().persistent().get::<DataKey, u32>(&score_key) else {
+            return if env.storage().persistent().has(&metadata_key) {
+                Ok(())
+            } else {
+                Err(NftError::NftNotFound)
+            };
+        };
+
+        let mut metadata: RemittanceMetadata = match env.storage().persistent().get(&metadata_key) {
+            Some(metadata) => metadata,
+            None => Self::legacy_metadata(&env, legacy_score),
+        };
+        let old_score = metadata.score;
+        metadata.score = old_score.max(legacy_score).min(Self::MAX_SCORE);
+
+        env.storage().persistent().set(&metadata_key, &metadata);
+        Self::bump_persistent_ttl(&env, &metadata_key);
+        env.storage().persistent().remove(&score_key);
+        if metadata.score != old_score {
+            Self::append_score_history(
+                &env,
+                &user,
+                old_score,
+                metadata.score,
+                symbol_short!("RECON"),
+            );
+        }
+        env.events().publish(
+            (symbol_short!("Reconcile"), user),
+            (legacy_score, metadata.score),
+        );
+        Ok(())
+    }
+
+    /// Register the loan manager allowed to call `update_score` in strict
+    /// mode.
+    pub fn set_loan_manager(env: Env, loan_manager: Address) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::LoanManager, &loan_manager);
+        Self::bump_instance_ttl(&env);
+        env.events()
+            .publish((symbol_short!("LoanMgr"),), loan_manager);
+    }
+
+    pub fn get_loan_manager(env: Env) -> Option<Address> {
+        Self::bump_instance_ttl(&env);
+        env.storage().instance().get(&DataKey::LoanManager)
+    }
+
+    /// When enabled, `update_score` and `apply_score_delta` only accept the
+    /// registered loan manager as `minter`; admin and other minters are
+    /// rejected with `NotLoanManager`. Off by default.
+    pub fn set_strict_score_updates(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::StrictScoreUpdates, &enabled);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("StrictScr"),), enabled);
+    }
+
+    pub fn get_strict_score_updates(env: Env) -> bool {
+        Self::strict_score_updates(&env)
     }
 
     /// Update the score for a user's NFT based on a repayment amount.
+    ///
+    /// In strict mode only the registered loan manager may call this.
     pub fn update_score(
         env: Env,
         user: Address,
@@ -656,22 +1572,29 @@ impl RemittanceNFT {
         if repayment_amount < Self::MIN_SCORE_UPDATE_REPAYMENT {
             return Err(NftError::InvalidRepaymentAmount);
         }
-        Self::require_admin_or_authorized_minter(&env, minter)?;
+        Self::require_score_updater(&env, minter)?;
 
         let metadata_key = DataKey::Metadata(user.clone());
         let mut metadata =
             Self::get_or_migrate_metadata(&env, &user).ok_or(NftError::NftNotFound)?;
 
-        // Simple logic: 1 point per 100 units of repayment.
-        let points_i128 = repayment_amount / 100;
+        let points_i128 = match Self::scoring_mode(&env) {
+            // Simple logic: 1 point per 100 units of repayment.
+            ScoringMode::Linear => Self::div_rounded(&env, repayment_amount, 100),
+            ScoringMode::Table => Self::table_points(&env, repayment_amount) as i128,
+        };
         if points_i128 == 0 {
             return Ok(());
         }
-        let points = if points_i128 > (Self::MAX_SCORE as i128) {
+        let mut points = if points_i128 > (Self::MAX_SCORE as i128) {
             Self::MAX_SCORE
         } else {
             points_i128 as u32
         };
+        let max_points = Self::get_max_points_per_loan(env.clone());
+        if max_points > 0 {
+            points = points.min(max_points);
+        }
         let old_score = metadata.score;
         metadata.score = old_score.saturating_add(points).min(Self::MAX_SCORE);
 
@@ -690,6 +1613,158 @@ impl RemittanceNFT {
         Ok(())
     }
 
+    /// Choose how repayment points are rounded: `Floor` (default) or
+    /// round-half-up.
+    pub fn set_rounding_mode(env: Env, mode: RoundingMode) {
+        Self::admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::RoundingMode, &mode);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("RoundMode"),), mode);
+    }
+
+    pub fn get_rounding_mode(env: Env) -> RoundingMode {
+        Self::rounding_mode(&env)
+    }
+
+    /// Switch how repayments are converted to points. `Table` requires a
+    /// point table to be set first.
+    pub fn set_scoring_mode(env: Env, mode: ScoringMode) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if mode == ScoringMode::Table && !env.storage().instance().has(&DataKey::PointTable) {
+            return Err(NftError::InvalidPointTable);
+        }
+        env.storage().instance().set(&DataKey::ScoringMode, &mode);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("ScoreMode"),), mode);
+        Ok(())
+    }
+
+    pub fn get_scoring_mode(env: Env) -> ScoringMode {
+        Self::scoring_mode(&env)
+    }
+
+    /// Set the `(threshold, points)` bands for `ScoringMode::Table`. A
+    /// repayment earns the points of the highest threshold it reaches.
+    /// Thresholds must be positive and strictly ascending.
+    pub fn set_point_table(env: Env, table: Vec<(i128, u32)>) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if table.is_empty() {
+            return Err(NftError::InvalidPointTable);
+        }
+        let mut previous = 0i128;
+        for (threshold, points) in table.iter() {
+            if threshold <= previous || points > Self::MAX_SCORE {
+                return Err(NftError::InvalidPointTable);
+            }
+            previous = threshold;
+        }
+        env.storage().instance().set(&DataKey::PointTable, &table);
+        Self::bump_instance_ttl(&env);
+        env.events()
+            .publish((symbol_short!("PointTbl"),), table.len());
+        Ok(())
+    }
+
+    pub fn get_point_table(env: Env) -> Vec<(i128, u32)> {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::PointTable)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set the minimum scores for grades `AAA`, `AA`, `A`, `B` and `C`, in
+    /// that order. Bands must be strictly descending and within the score
+    /// range; scores below the last band grade `D`.
+    pub fn set_grade_bands(env: Env, bands: Vec<u32>) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if bands.len() != Self::DEFAULT_GRADE_BANDS.len() as u32 {
+            return Err(NftError::InvalidGradeBands);
+        }
+        let mut previous = Self::MAX_SCORE + 1;
+        for band in bands.iter() {
+            if band == 0 || band >= previous {
+                return Err(NftError::InvalidGradeBands);
+            }
+            previous = band;
+        }
+        env.storage().instance().set(&DataKey::GradeBands, &bands);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("GradeBnds"),), bands);
+        Ok(())
+    }
+
+    pub fn get_grade_bands(env: Env) -> Vec<u32> {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::GradeBands)
+            .unwrap_or_else(|| Vec::from_array(&env, Self::DEFAULT_GRADE_BANDS))
+    }
+
+    /// Letter grade for `user`'s current score under the configured bands:
+    /// `AAA`, `AA`, `A`, `B`, `C` or `D`. Users without an NFT grade `D`.
+    pub fn credit_grade(env: Env, user: Address) -> Symbol {
+        let score = Self::current_score(env.clone(), user);
+        let grades = [
+            symbol_short!("AAA"),
+            symbol_short!("AA"),
+            symbol_short!("A"),
+            symbol_short!("B"),
+            symbol_short!("C"),
+        ];
+        for (band, grade) in Self::get_grade_bands(env).iter().zip(grades) {
+            if score >= band {
+                return grade;
+            }
+        }
+        symbol_short!("D")
+    }
+
+    /// Reject mints and remints whose `initial_score` is below `min_score`.
+    /// 0 (the default) allows any starting score.
+    pub fn set_min_mint_score(env: Env, min_score: u32) -> Result<(), NftError> {
+        Self::admin(&env).require_auth();
+        if min_score > Self::MAX_SCORE {
+            return Err(NftError::InvalidThreshold);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MinMintScore, &min_score);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("MinMint"),), min_score);
+        Ok(())
+    }
+
+    pub fn get_min_mint_score(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::MinMintScore)
+            .unwrap_or(0)
+    }
+
+    /// Cap the points one `update_score` or positive `apply_score_delta` call
+    /// can award, so a single large repayment cannot max out a score. Points
+    /// beyond the cap are dropped. 0 removes the cap.
+    pub fn set_max_points_per_loan(env: Env, max_points: u32) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPointsPerLoan, &max_points);
+        Self::bump_instance_ttl(&env);
+        env.events()
+            .publish((symbol_short!("MaxPoints"),), max_points);
+    }
+
+    pub fn get_max_points_per_loan(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxPointsPerLoan)
+            .unwrap_or(0)
+    }
+
     pub fn set_min_repayment_amount(env: Env, amount: i128) {
         Self::admin(&env).require_auth();
         if amount < 0 {
@@ -701,10 +1776,83 @@ impl RemittanceNFT {
         Self::bump_instance_ttl(&env);
     }
 
+    /// Set the placeholder history hash used when migrating legacy
+    /// `Score`-only users. Only affects users migrated after the call.
+    pub fn set_legacy_default_hash(env: Env, hash: BytesN<32>) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::LegacyDefaultHash, &hash);
+        Self::bump_instance_ttl(&env);
+    }
+
+    pub fn get_legacy_default_hash(env: Env) -> BytesN<32> {
+        Self::default_history_hash(&env)
+    }
+
+    /// Whether read-only calls (`get_metadata`, `get_metadata_uri`,
+    /// `get_score`) migrate legacy `Score` entries in place. Defaults to
+    /// `true`; turn off for read-only auditing. Mutators always migrate.
+    pub fn set_auto_migrate_legacy(env: Env, enabled: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::AutoMigrateLegacy, &enabled);
+        Self::bump_instance_ttl(&env);
+        env.events().publish((symbol_short!("AutoMig"),), enabled);
+    }
+
+    pub fn get_auto_migrate_legacy(env: Env) -> bool {
+        Self::auto_migrate_legacy(&env)
+    }
+
     pub fn get_min_repayment_amount(env: Env) -> i128 {
         Self::min_repayment_amount(&env)
     }
 
+    fn scoring_mode(env: &Env) -> ScoringMode {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ScoringMode)
+            .unwrap_or(ScoringMode::Linear)
+    }
+
+    /// Points of the highest band whose threshold `repayment_amount`
+    /// reaches; 0 below the first band.
+    fn table_points(env: &Env, repayment_amount: i128) -> u32 {
+        let table: Vec<(i128, u32)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PointTable)
+            .unwrap_or(Vec::new(env));
+        let mut points = 0;
+        for (threshold, band_points) in table.iter() {
+            if repayment_amount < threshold {
+                break;
+            }
+            points = band_points;
+        }
+        points
+    }
+
+    fn rounding_mode(env: &Env) -> RoundingMode {
+        Self::bump_instance_ttl(env);
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundingMode)
+            .unwrap_or(RoundingMode::Floor)
+    }
+
+    /// `numerator / denominator` for non-negative operands, rounded per the
+    /// configured rounding mode.
+    fn div_rounded(env: &Env, numerator: i128, denominator: i128) -> i128 {
+        match Self::rounding_mode(env) {
+            RoundingMode::Floor => numerator / denominator,
+            RoundingMode::Round => (numerator + denominator / 2) / denominator,
+        }
+    }
+
     fn min_repayment_amount(env: &Env) -> i128 {
         env.storage()
             .instance()
@@ -716,44 +1864,72 @@ impl RemittanceNFT {
         Self::require_admin_or_authorized_minter(&env, minter)
             .unwrap_or_else(|_| panic!("unauthorized minter"));
 
-        if !Self::has_active_nft(&env, &user) {
-            return;
+        if let Some((old_score, new_score)) =
+            Self::apply_penalty(&env, &user, penalty_points, symbol_short!("DEC"))
+        {
+            env.events().publish(
+                (symbol_short!("ScoreDecr"), user),
+                (old_score, new_score, symbol_short!("PEN")),
+            );
         }
+    }
 
-        let metadata_key = DataKey::Metadata(user.clone());
-        let mut metadata = Self::get_or_migrate_metadata(&env, &user)
-            .unwrap_or_else(|| panic!("user does not have an NFT"));
-
-        let old_score = metadata.score;
-        let decreased = old_score.saturating_sub(penalty_points);
-        let new_score = decreased.max(Self::MIN_CREDIT_SCORE);
-        if new_score == old_score {
-            return;
+    /// Penalize every listed user by `penalty` points (floored at the
+    /// minimum score), e.g. when a fraud ring is identified. Users without
+    /// an active NFT are skipped. Each change is recorded in the user's
+    /// score history under `reason` and emits a `ScorePen` event. Returns
+    /// the number of users whose score changed.
+    pub fn batch_penalize(
+        env: Env,
+        users: Vec<Address>,
+        penalty: u32,
+        reason: Symbol,
+    ) -> Result<u32, NftError> {
+        Self::admin(&env).require_auth();
+        Self::assert_not_paused(&env)?;
+        if users.len() > Self::MAX_SCORE_BATCH {
+            return Err(NftError::BatchTooLarge);
         }
 
-        metadata.score = new_score;
-        env.storage().persistent().set(&metadata_key, &metadata);
-        Self::bump_persistent_ttl(&env, &metadata_key);
-        Self::append_score_history(&env, &user, old_score, new_score, symbol_short!("DEC"));
-        env.events().publish(
-            (symbol_short!("ScoreDecr"), user),
-            (old_score, new_score, symbol_short!("PEN")),
-        );
+        let mut penalized = 0u32;
+        for user in users.iter() {
+            if !Self::has_active_nft(&env, &user) {
+                continue;
+            }
+            if let Some((old_score, new_score)) =
+                Self::apply_penalty(&env, &user, penalty, reason.clone())
+            {
+                penalized += 1;
+                env.events().publish(
+                    (symbol_short!("ScorePen"), user),
+                    (old_score, new_score, reason.clone()),
+                );
+            }
+        }
+        Ok(penalized)
     }
 
     /// Update the history hash for a user's NFT.
+    ///
+    /// In strict mode only the registered loan manager may call this.
     pub fn apply_score_delta(
         env: Env,
         user: Address,
         delta: i32,
         minter: Option<Address>,
     ) -> Result<(), NftError> {
-        Self::require_admin_or_authorized_minter(&env, minter)?;
+        Self::require_score_updater(&env, minter)?;
 
         let metadata_key = DataKey::Metadata(user.clone());
         let mut metadata =
             Self::get_or_migrate_metadata(&env, &user).ok_or(NftError::NftNotFound)?;
 
+        let mut delta = delta;
+        let max_points = Self::get_max_points_per_loan(env.clone());
+        if max_points > 0 && delta > 0 {
+            delta = delta.min(i32::try_from(max_points).unwrap_or(i32::MAX));
+        }
+
         let old_score = metadata.score as i64;
         let next_score = old_score + delta as i64;
         let bounded_score = next_score.clamp(0, Self::MAX_SCORE as i64);
@@ -850,9 +2026,13 @@ impl RemittanceNFT {
         Ok(())
     }
 
+    /// Record a default for `user`: deduct `penalty_points` (floored at the
+    /// minimum score), bump their default count and flag them as seized,
+    /// burning the NFT once the burn threshold is reached.
     pub fn record_default(
         env: Env,
         user: Address,
+        penalty_points: u32,
         minter: Option<Address>,
     ) -> Result<(), NftError> {
         Self::require_admin_or_authorized_minter(&env, minter)?;
@@ -861,6 +2041,17 @@ impl RemittanceNFT {
             return Ok(());
         }
 
+        Self::settle_default_penalty(&env, &user);
+        if let Some((old_score, new_score)) =
+            Self::apply_penalty(&env, &user, penalty_points, symbol_short!("DEC"))
+        {
+            Self::record_default_penalty(&env, &user, old_score - new_score);
+            env.events().publish(
+                (symbol_short!("ScoreDecr"), user.clone()),
+                (old_score, new_score, symbol_short!("PEN")),
+            );
+        }
+
         let default_key = DataKey::DefaultCount(user.clone());
         let updated_count = env
             .storage()
@@ -899,6 +2090,26 @@ impl RemittanceNFT {
         Ok(())
     }
 
+    /// Make NFTs soulbound (`transfer` is rejected) or transferable. Admin
+    /// recovery via `migrate_nft` is allowed either way.
+    pub fn set_soulbound(env: Env, soulbound: bool) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::Soulbound, &soulbound);
+        Self::bump_instance_ttl(&env);
+        env.events()
+            .publish((symbol_short!("Soulbound"),), soulbound);
+    }
+
+    pub fn is_soulbound(env: Env) -> bool {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::Soulbound)
+            .unwrap_or(false)
+    }
+
     pub fn transfer(
         env: Env,
         from: Address,
@@ -908,6 +2119,9 @@ impl RemittanceNFT {
         if from == to {
             return Err(NftError::SelfTransfer);
         }
+        if Self::is_soulbound(env.clone()) {
+            return Err(NftError::SoulboundToken);
+        }
 
         from.require_auth();
         Self::require_admin_or_authorized_minter(&env, minter)?;
@@ -930,52 +2144,7 @@ impl RemittanceNFT {
             return Err(NftError::DestinationOccupied);
         }
 
-        let from_metadata_key = DataKey::Metadata(from.clone());
-        let to_metadata_key = DataKey::Metadata(to.clone());
-        env.storage().persistent().set(&to_metadata_key, &metadata);
-        Self::bump_persistent_ttl(&env, &to_metadata_key);
-        env.storage().persistent().remove(&from_metadata_key);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Score(from.clone()));
-
-        let from_history_key = DataKey::ScoreHistory(from.clone());
-        if let Some(history) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<ScoreHistoryEntry>>(&from_history_key)
-        {
-            let to_history_key = DataKey::ScoreHistory(to.clone());
-            env.storage().persistent().set(&to_history_key, &history);
-            Self::bump_persistent_ttl(&env, &to_history_key);
-            env.storage().persistent().remove(&from_history_key);
-        }
-
-        let from_default_key = DataKey::DefaultCount(from.clone());
-        if let Some(default_count) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, u32>(&from_default_key)
-        {
-            let to_default_key = DataKey::DefaultCount(to.clone());
-            env.storage()
-                .persistent()
-                .set(&to_default_key, &default_count);
-            Self::bump_persistent_ttl(&env, &to_default_key);
-            env.storage().persistent().remove(&from_default_key);
-        }
-
-        let from_seized_key = DataKey::Seized(from.clone());
-        if env.storage().persistent().has(&from_seized_key) {
-            let to_seized_key = DataKey::Seized(to.clone());
-            env.storage().persistent().set(&to_seized_key, &true);
-            Self::bump_persistent_ttl(&env, &to_seized_key);
-            env.storage().persistent().remove(&from_seized_key);
-        }
-
-        env.storage()
-            .persistent()
-            .remove(&DataKey::RemintApproval(from.clone()));
+        Self::move_nft_state(&env, &from, &to, &metadata);
 
         env.storage().persistent().remove(&transfer_cooldown_key);
         let to_cooldown_key = DataKey::TransferCooldown(to.clone());
@@ -994,6 +2163,53 @@ impl RemittanceNFT {
         Ok(())
     }
 
+    /// Move a user's NFT to a new wallet without the old wallet's signature.
+    ///
+    /// Intended for users who have lost access to `old_user`; requires admin
+    /// or authorized-minter auth instead. Score, history, default count and
+    /// seized state all move to `new_user`. No transfer cooldown is applied.
+    pub fn migrate_nft(
+        env: Env,
+        old_user: Address,
+        new_user: Address,
+        minter: Option<Address>,
+    ) -> Result<(), NftError> {
+        // Source and destination identical: moving state onto itself would
+        // delete the NFT after copying it.
+        if old_user == new_user {
+            return Err(NftError::SelfTransfer);
+        }
+        Self::require_admin_or_authorized_minter(&env, minter)?;
+
+        let metadata =
+            Self::get_or_migrate_metadata(&env, &old_user).ok_or(NftError::NftNotFound)?;
+
+        if Self::has_any_remittance_state(&env, &new_user) {
+            return Err(NftError::DestinationOccupied);
+        }
+
+        Self::move_nft_state(&env, &old_user, &new_user, &metadata);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TransferCooldown(old_user.clone()));
+
+        env.events()
+            .publish((symbol_short!("Migrate"), old_user, new_user), ());
+
+        Ok(())
+    }
+
+    /// `user`'s NFT state in one read: whether they hold one, their current
+    /// score, whether their collateral is seized, and their default count.
+    pub fn eligibility(env: Env, user: Address) -> Eligibility {
+        Eligibility {
+            has_nft: Self::has_active_nft(&env, &user),
+            score: Self::current_score(env.clone(), user.clone()),
+            seized: Self::is_seized(env.clone(), user.clone()),
+            default_count: Self::get_default_count(env, user),
+        }
+    }
+
     /// Returns true if the borrower's collateral has been seized.
     /// See `seize_collateral` for the full list of operations this flag blocks.
     pub fn is_seized(env: Env, user: Address) -> bool {
@@ -1005,6 +2221,40 @@ impl RemittanceNFT {
         is_seized
     }
 
+    /// Cache `user`'s open loan principal, as reported by the loan manager.
+    /// Kept alongside the NFT rather than in its metadata so existing
+    /// metadata records keep their stored shape.
+    pub fn set_outstanding(
+        env: Env,
+        user: Address,
+        amount: i128,
+        minter: Option<Address>,
+    ) -> Result<(), NftError> {
+        Self::require_admin_or_authorized_minter(&env, minter)?;
+        if amount < 0 {
+            return Err(NftError::InvalidOutstanding);
+        }
+
+        let key = DataKey::Outstanding(user);
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+            Self::bump_persistent_ttl(&env, &key);
+        }
+        Ok(())
+    }
+
+    /// Open loan principal last reported for `user`; 0 if none.
+    pub fn get_outstanding(env: Env, user: Address) -> i128 {
+        let key = DataKey::Outstanding(user);
+        let amount = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount > 0 {
+            Self::bump_persistent_ttl(&env, &key);
+        }
+        amount
+    }
+
     pub fn get_default_count(env: Env, user: Address) -> u32 {
         let key = DataKey::DefaultCount(user);
         let count = env.storage().persistent().get(&key).unwrap_or(0);
@@ -1051,6 +2301,16 @@ impl RemittanceNFT {
         Self::default_burn_threshold(&env)
     }
 
+    /// Number of live NFTs: mints and re-mints less burns. Transfers and
+    /// migrations move an NFT without changing the count.
+    pub fn total_nfts(env: Env) -> u32 {
+        Self::bump_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::NftCount)
+            .unwrap_or(0)
+    }
+
     pub fn get_score_history(
         env: Env,
         user: Address,
@@ -1103,17 +2363,21 @@ impl RemittanceNFT {
     }
 
     pub fn pause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         env.storage().instance().set(&DataKey::Paused, &true);
         Self::bump_instance_ttl(&env);
-        env.events().publish((symbol_short!("Paused"),), ());
+        env.events()
+            .publish((symbol_short!("Paused"), admin), env.ledger().timestamp());
     }
 
     pub fn unpause(env: Env) {
-        Self::admin(&env).require_auth();
+        let admin = Self::admin(&env);
+        admin.require_auth();
         env.storage().instance().set(&DataKey::Paused, &false);
         Self::bump_instance_ttl(&env);
-        env.events().publish((symbol_short!("Unpaused"),), ());
+        env.events()
+            .publish((symbol_short!("Unpaused"), admin), env.ledger().timestamp());
     }
 
     pub fn get_admin(env: Env) -> Address {
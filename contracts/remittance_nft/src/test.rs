@@ -818,12 +818,12 @@ fn test_record_default_auto_burns_after_threshold() {
         &None,
     );
 
-    client.record_default(&user, &None);
+    client.record_default(&user, &0, &None);
     assert_eq!(client.get_default_count(&user), 1);
     assert!(client.is_seized(&user));
     assert!(client.get_metadata(&user).is_some());
 
-    client.record_default(&user, &None);
+    client.record_default(&user, &0, &None);
     assert_eq!(client.get_default_count(&user), 2);
     assert!(client.get_metadata(&user).is_none());
     assert_eq!(client.get_score(&user), 0);
@@ -851,7 +851,7 @@ fn test_transfer_moves_identity_state_to_new_wallet() {
         &None,
     );
     client.update_score(&old_wallet, &300, &None);
-    client.record_default(&old_wallet, &None);
+    client.record_default(&old_wallet, &0, &None);
 
     client.transfer(&old_wallet, &new_wallet, &None);
 
@@ -1887,3 +1887,1333 @@ fn test_score_history_max_50_entries() {
         .unwrap();
     assert_eq!(last_entry.ledger, 60);
 }
+
+#[test]
+fn test_migrate_nft_moves_populated_nft_to_fresh_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let old_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.mint(
+        &old_wallet,
+        &500,
+        &create_test_hash(&env, 31),
+        &create_test_uri(&env),
+        &None,
+    );
+    client.update_score(&old_wallet, &300, &None);
+    client.record_default(&old_wallet, &0, &None);
+
+    client.migrate_nft(&old_wallet, &new_wallet, &None);
+
+    let events = env.events().all();
+    let event = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        Symbol::from_val(&env, &event.1.get(0).unwrap()),
+        symbol_short!("Migrate")
+    );
+    assert_eq!(
+        Address::from_val(&env, &event.1.get(1).unwrap()),
+        old_wallet
+    );
+    assert_eq!(
+        Address::from_val(&env, &event.1.get(2).unwrap()),
+        new_wallet
+    );
+
+    assert!(client.get_metadata(&old_wallet).is_none());
+    assert_eq!(client.get_default_count(&old_wallet), 0);
+    assert!(!client.is_seized(&old_wallet));
+
+    let metadata = client.get_metadata(&new_wallet).unwrap();
+    assert_eq!(metadata.score, 503);
+    assert_eq!(metadata.history_hash, create_test_hash(&env, 31));
+    assert_eq!(client.get_default_count(&new_wallet), 1);
+    assert!(client.is_seized(&new_wallet));
+    assert_eq!(client.get_score_history(&new_wallet, &0, &10).len(), 1);
+    // Recovery does not start a transfer cooldown on the new wallet.
+    assert_eq!(client.get_transfer_cooldown_remaining(&new_wallet), 0);
+}
+
+#[test]
+fn test_migrate_nft_rejects_destination_with_existing_nft() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let old_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.mint(
+        &old_wallet,
+        &500,
+        &create_test_hash(&env, 32),
+        &create_test_uri(&env),
+        &None,
+    );
+    client.mint(
+        &new_wallet,
+        &400,
+        &create_test_hash(&env, 33),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    let result = client.try_migrate_nft(&old_wallet, &new_wallet, &None);
+    assert_eq!(result, Err(Ok(NftError::DestinationOccupied)));
+    assert_eq!(client.get_score(&old_wallet), 500);
+    assert_eq!(client.get_score(&new_wallet), 400);
+}
+
+#[test]
+fn test_migrate_nft_rejects_self_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let wallet = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.mint(
+        &wallet,
+        &500,
+        &create_test_hash(&env, 35),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    let result = client.try_migrate_nft(&wallet, &wallet, &None);
+    assert_eq!(result, Err(Ok(NftError::SelfTransfer)));
+    assert_eq!(client.get_score(&wallet), 500);
+}
+
+#[test]
+fn test_migrate_nft_rejects_unauthorized_minter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let old_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let rogue = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.mint(
+        &old_wallet,
+        &500,
+        &create_test_hash(&env, 34),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    let result = client.try_migrate_nft(&old_wallet, &new_wallet, &Some(rogue));
+    assert_eq!(result, Err(Ok(NftError::UnauthorizedMinter)));
+    assert_eq!(client.get_score(&old_wallet), 500);
+}
+
+#[test]
+fn test_legacy_migration_uses_configured_default_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let read_user = Address::generate(&env);
+    let update_user = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    assert_eq!(
+        client.get_legacy_default_hash(),
+        BytesN::from_array(&env, &[0u8; 32])
+    );
+
+    let placeholder = create_test_hash(&env, 77);
+    client.set_legacy_default_hash(&placeholder);
+    assert_eq!(client.get_legacy_default_hash(), placeholder);
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Score(read_user.clone()), &700u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Score(update_user.clone()), &650u32);
+    });
+
+    // Migration on read.
+    let metadata = client.get_metadata(&read_user).unwrap();
+    assert_eq!(metadata.score, 700);
+    assert_eq!(metadata.history_hash, placeholder);
+
+    // Migration on write.
+    client.update_score(&update_user, &500, &None);
+    let metadata = client.get_metadata(&update_user).unwrap();
+    assert_eq!(metadata.score, 655);
+    assert_eq!(metadata.history_hash, placeholder);
+}
+
+#[test]
+fn test_current_score_applies_decay_without_mutating() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &700,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(client.current_score(&user), 700);
+
+    client.set_score_decay(&10, &100, &500);
+    let start = env.ledger().sequence();
+
+    env.ledger().set_sequence_number(start + 1_050);
+    assert_eq!(client.current_score(&user), 600);
+    assert_eq!(client.get_score(&user), 700);
+
+    // Decay never takes the score below the configured floor.
+    env.ledger().set_sequence_number(start + 100_000);
+    assert_eq!(client.current_score(&user), 500);
+    assert_eq!(client.get_score(&user), 700);
+
+    // A score change resets the inactivity clock.
+    client.update_score(&user, &1_000, &None);
+    assert_eq!(client.current_score(&user), 710);
+
+    assert_eq!(
+        client.try_set_score_decay(&10, &0, &500),
+        Err(Ok(NftError::InvalidDecayConfig))
+    );
+}
+
+#[test]
+fn test_total_nfts_tracks_mint_burn_and_migrate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let recovered = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    assert_eq!(client.total_nfts(), 0);
+
+    for (i, user) in [&alice, &bob, &carol].into_iter().enumerate() {
+        client.mint(
+            user,
+            &500,
+            &create_test_hash(&env, 40 + i as u8),
+            &create_test_uri(&env),
+            &None,
+        );
+    }
+    assert_eq!(client.total_nfts(), 3);
+
+    client.burn(&bob, &None);
+    assert_eq!(client.total_nfts(), 2);
+    // Burning a missing NFT fails and leaves the count alone.
+    assert_eq!(client.try_burn(&bob, &None), Err(Ok(NftError::NftNotFound)));
+    assert_eq!(client.total_nfts(), 2);
+
+    client.migrate_nft(&alice, &recovered, &None);
+    assert_eq!(client.total_nfts(), 2);
+
+    client.approve_remint(&bob);
+    client.admin_remint(
+        &bob,
+        &400,
+        &create_test_hash(&env, 50),
+        &create_test_uri(&env),
+    );
+    assert_eq!(client.total_nfts(), 3);
+}
+
+#[test]
+fn test_bulk_authorize_and_revoke_minters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let batch = Vec::from_array(&env, [a.clone(), b.clone(), c.clone()]);
+
+    client.authorize_minters(&batch);
+    assert_eq!(env.events().all().len(), 3);
+    assert!(client.is_authorized_minter(&a));
+    assert!(client.is_authorized_minter(&b));
+    assert!(client.is_authorized_minter(&c));
+    assert_eq!(client.get_authorized_minters().len(), 4);
+
+    client.revoke_minters(&Vec::from_array(&env, [a.clone(), c.clone()]));
+    assert!(!client.is_authorized_minter(&a));
+    assert!(client.is_authorized_minter(&b));
+    assert!(!client.is_authorized_minter(&c));
+    assert_eq!(client.get_authorized_minters().len(), 2);
+
+    let empty = Vec::new(&env);
+    assert_eq!(
+        client.try_authorize_minters(&empty),
+        Err(Ok(NftError::EmptyMinterList))
+    );
+    assert_eq!(
+        client.try_revoke_minters(&empty),
+        Err(Ok(NftError::EmptyMinterList))
+    );
+}
+
+#[test]
+fn test_normalized_score_scales() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let top = Address::generate(&env);
+    let mid = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://QmTest");
+    client.mint(
+        &top,
+        &RemittanceNFT::MAX_SCORE,
+        &create_test_hash(&env, 1),
+        &uri,
+        &None,
+    );
+    client.mint(&mid, &425, &create_test_hash(&env, 2), &uri, &None);
+
+    assert_eq!(client.normalized_score(&top, &100), 100);
+    assert_eq!(client.normalized_score(&top, &1000), 1000);
+    assert_eq!(client.normalized_score(&top, &u32::MAX), u32::MAX);
+    assert_eq!(client.normalized_score(&mid, &100), 50);
+    assert_eq!(client.normalized_score(&mid, &1000), 500);
+    assert_eq!(client.normalized_score(&mid, &0), 0);
+
+    let nobody = Address::generate(&env);
+    assert_eq!(client.normalized_score(&nobody, &100), 0);
+}
+
+#[test]
+fn test_get_scores_is_positional_and_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let uri = String::from_str(&env, "ipfs://QmTest");
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let nobody = Address::generate(&env);
+    client.mint(&alice, &640, &create_test_hash(&env, 1), &uri, &None);
+    client.mint(&bob, &410, &create_test_hash(&env, 2), &uri, &None);
+
+    let scores = client.get_scores(&Vec::from_array(
+        &env,
+        [alice.clone(), nobody.clone(), bob.clone(), alice.clone()],
+    ));
+    assert_eq!(scores, Vec::from_array(&env, [640u32, 0, 410, 640]));
+    assert_eq!(client.get_scores(&Vec::new(&env)), Vec::new(&env));
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=RemittanceNFT::MAX_SCORE_BATCH {
+        too_many.push_back(nobody.clone());
+    }
+    assert_eq!(
+        client.try_get_scores(&too_many),
+        Err(Ok(NftError::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_set_outstanding_requires_minter_and_non_negative() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    assert_eq!(client.get_outstanding(&user), 0);
+
+    client.set_outstanding(&user, &2_500, &None);
+    assert_eq!(client.get_outstanding(&user), 2_500);
+    assert_eq!(
+        client.try_set_outstanding(&user, &-1, &None),
+        Err(Ok(NftError::InvalidOutstanding))
+    );
+    assert_eq!(
+        client.try_set_outstanding(&user, &0, &Some(stranger)),
+        Err(Ok(NftError::UnauthorizedMinter))
+    );
+
+    client.set_outstanding(&user, &0, &None);
+    assert_eq!(client.get_outstanding(&user), 0);
+}
+
+#[test]
+fn test_soulbound_mode_blocks_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &owner,
+        &500,
+        &create_test_hash(&env, 31),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    assert!(!client.is_soulbound());
+    client.set_soulbound(&true);
+    assert!(client.is_soulbound());
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &None),
+        Err(Ok(NftError::SoulboundToken))
+    );
+    assert_eq!(client.get_score(&owner), 500);
+
+    // Admin recovery still works while soulbound.
+    client.migrate_nft(&owner, &recipient, &None);
+    assert_eq!(client.get_score(&recipient), 500);
+
+    client.set_soulbound(&false);
+    client.transfer(&recipient, &owner, &None);
+    assert_eq!(client.get_score(&owner), 500);
+    assert_eq!(client.get_score(&recipient), 0);
+}
+
+#[test]
+fn test_dormant_minter_authorization_survives_ledger_advancement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.authorize_minter(&minter);
+
+    // Leave the minter unused until its entry is close to expiring; reading it
+    // then extends its own TTL independently of the instance.
+    env.ledger().set_sequence_number(505_000);
+    assert!(client.is_authorized_minter(&minter));
+    client.version();
+
+    // Past the TTL granted at authorization time.
+    env.ledger().set_sequence_number(1_000_000);
+    assert!(client.is_authorized_minter(&minter));
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &Some(minter),
+    );
+    assert_eq!(client.get_score(&user), 500);
+}
+
+#[test]
+fn test_batch_penalize_skips_users_without_nft() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let uri = String::from_str(&env, "ipfs://QmTest");
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let nobody = Address::generate(&env);
+    client.mint(&alice, &640, &create_test_hash(&env, 1), &uri, &None);
+    client.mint(&bob, &320, &create_test_hash(&env, 2), &uri, &None);
+
+    let reason = Symbol::new(&env, "fraud_ring");
+    let penalized = client.batch_penalize(
+        &Vec::from_array(&env, [alice.clone(), nobody.clone(), bob.clone()]),
+        &100,
+        &reason,
+    );
+    assert_eq!(penalized, 2);
+    assert_eq!(env.events().all().len(), 2);
+    assert_eq!(client.get_score(&alice), 540);
+    // Floored at the minimum credit score.
+    assert_eq!(client.get_score(&bob), 300);
+    assert_eq!(client.get_score(&nobody), 0);
+
+    let history = client.get_score_history(&alice, &0, &10);
+    assert_eq!(history.last().unwrap().reason, reason);
+}
+
+#[test]
+fn test_rounding_mode_splits_repayment_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.set_min_repayment_amount(&0);
+
+    let uri = String::from_str(&env, "ipfs://QmTest");
+    let floor_user = Address::generate(&env);
+    let round_user = Address::generate(&env);
+    client.mint(&floor_user, &500, &create_test_hash(&env, 1), &uri, &None);
+    client.mint(&round_user, &500, &create_test_hash(&env, 2), &uri, &None);
+
+    assert_eq!(client.get_rounding_mode(), RoundingMode::Floor);
+    client.update_score(&floor_user, &149, &None);
+    assert_eq!(client.get_score(&floor_user), 501);
+    client.update_score(&floor_user, &150, &None);
+    assert_eq!(client.get_score(&floor_user), 502);
+
+    client.set_rounding_mode(&RoundingMode::Round);
+    client.update_score(&round_user, &149, &None);
+    assert_eq!(client.get_score(&round_user), 501);
+    client.update_score(&round_user, &150, &None);
+    assert_eq!(client.get_score(&round_user), 503);
+}
+
+#[test]
+fn test_migrate_advances_storage_version_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    assert_eq!(client.storage_version(), 1);
+
+    // Simulate a deployment initialized before the marker and burn threshold.
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::StorageVersion);
+        env.storage()
+            .instance()
+            .remove(&soroban_sdk::symbol_short!("BURNTHR"));
+    });
+    assert_eq!(client.storage_version(), 0);
+
+    client.migrate();
+    assert_eq!(client.storage_version(), 1);
+    assert_eq!(client.get_default_burn_threshold(), 3);
+
+    // A second run is a no-op: it no longer backfills missing keys.
+    client.set_default_burn_threshold(&5);
+    client.migrate();
+    assert_eq!(client.storage_version(), 1);
+    assert_eq!(client.get_default_burn_threshold(), 5);
+}
+
+#[test]
+#[should_panic(expected = "unexpected storage version")]
+fn test_migrate_refuses_newer_storage_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &99u32);
+    });
+    client.migrate();
+}
+
+#[test]
+fn test_minter_count_and_last_minter_guard() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    assert_eq!(client.minter_count(), 1);
+
+    client.authorize_minter(&minter);
+    assert_eq!(client.minter_count(), 2);
+    client.revoke_minter(&minter);
+    assert_eq!(client.minter_count(), 1);
+
+    client.set_require_min_minters(&true);
+    assert_eq!(
+        client.try_revoke_minter(&admin),
+        Err(Ok(NftError::LastMinter))
+    );
+    assert_eq!(
+        client.try_revoke_minters(&Vec::from_array(&env, [admin.clone()])),
+        Err(Ok(NftError::LastMinter))
+    );
+    assert!(client.is_authorized_minter(&admin));
+
+    client.set_require_min_minters(&false);
+    client.revoke_minter(&admin);
+    assert_eq!(client.minter_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "not initialized")]
+fn test_mint_before_initialize_panics_uniformly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.mint(
+        &user,
+        &500,
+        &hash,
+        &String::from_str(&env, "ipfs://x"),
+        &None,
+    );
+}
+
+#[test]
+fn test_pause_and_unpause_events_carry_admin_and_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_234);
+
+    for (paused, name) in [(true, "Paused"), (false, "Unpaused")] {
+        if paused {
+            client.pause();
+        } else {
+            client.unpause();
+        }
+        let events = env.events().all();
+        let event = events.get(events.len() - 1).unwrap();
+        assert_eq!(
+            Symbol::from_val(&env, &event.1.get(0).unwrap()),
+            Symbol::new(&env, name)
+        );
+        assert_eq!(Address::from_val(&env, &event.1.get(1).unwrap()), admin);
+        assert_eq!(u64::from_val(&env, &event.2), 1_234);
+    }
+}
+
+#[test]
+fn test_reads_do_not_migrate_legacy_data_when_auto_migrate_is_off() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    assert!(client.get_auto_migrate_legacy());
+    client.set_auto_migrate_legacy(&false);
+
+    let score_key = DataKey::Score(user.clone());
+    let metadata_key = DataKey::Metadata(user.clone());
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&score_key, &600u32);
+    });
+
+    let metadata = client.get_metadata(&user).unwrap();
+    assert_eq!(metadata.score, 600);
+    assert_eq!(metadata.history_hash, client.get_legacy_default_hash());
+    assert_eq!(client.get_score(&user), 600);
+    assert_eq!(
+        client.get_metadata_uri(&user),
+        Some(String::from_str(&env, ""))
+    );
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().has(&score_key));
+        assert!(!env.storage().persistent().has(&metadata_key));
+    });
+
+    // Mutators still migrate.
+    client.update_score(&user, &200, &None);
+    assert_eq!(client.get_score(&user), 602);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&score_key));
+        assert!(env.storage().persistent().has(&metadata_key));
+    });
+}
+
+#[test]
+fn test_strict_score_updates_only_accept_loan_manager() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other_minter = Address::generate(&env);
+    let loan_manager = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.authorize_minter(&other_minter);
+    client.authorize_minter(&loan_manager);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    // Permissive by default: admin and any authorized minter may bump scores.
+    assert!(!client.get_strict_score_updates());
+    client.update_score(&user, &100, &None);
+    client.update_score(&user, &100, &Some(other_minter.clone()));
+    assert_eq!(client.get_score(&user), 502);
+
+    client.set_loan_manager(&loan_manager);
+    client.set_strict_score_updates(&true);
+    assert_eq!(
+        client.try_update_score(&user, &100, &None),
+        Err(Ok(NftError::NotLoanManager))
+    );
+    assert_eq!(
+        client.try_update_score(&user, &100, &Some(other_minter)),
+        Err(Ok(NftError::NotLoanManager))
+    );
+
+    client.update_score(&user, &100, &Some(loan_manager));
+    assert_eq!(client.get_score(&user), 503);
+}
+
+#[test]
+fn test_strict_score_updates_also_gate_score_deltas() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other_minter = Address::generate(&env);
+    let loan_manager = Address::generate(&env);
+
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.authorize_minter(&other_minter);
+    client.authorize_minter(&loan_manager);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &None,
+    );
+    client.set_loan_manager(&loan_manager);
+    client.set_strict_score_updates(&true);
+
+    assert_eq!(
+        client.try_apply_score_delta(&user, &10, &None),
+        Err(Ok(NftError::NotLoanManager))
+    );
+    assert_eq!(
+        client.try_apply_score_delta(&user, &10, &Some(other_minter)),
+        Err(Ok(NftError::NotLoanManager))
+    );
+    assert_eq!(client.get_score(&user), 500);
+
+    client.apply_score_delta(&user, &10, &Some(loan_manager));
+    assert_eq!(client.get_score(&user), 510);
+}
+
+#[test]
+fn test_reconcile_merges_stray_legacy_score_into_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 7),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    // Seed a leftover legacy entry alongside the metadata, as a partial
+    // migration would.
+    let score_key = DataKey::Score(user.clone());
+    let metadata_key = DataKey::Metadata(user.clone());
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&score_key, &640u32);
+    });
+
+    client.reconcile(&user, &None);
+
+    let metadata = client.get_metadata(&user).unwrap();
+    assert_eq!(metadata.score, 640);
+    assert_eq!(metadata.history_hash, create_test_hash(&env, 7));
+    assert_eq!(metadata.metadata_uri, create_test_uri(&env));
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&score_key));
+        assert!(env.storage().persistent().has(&metadata_key));
+    });
+
+    // A lower legacy score never lowers the current one.
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&score_key, &300u32);
+    });
+    client.reconcile(&user, &None);
+    assert_eq!(client.get_score(&user), 640);
+
+    // Nothing to reconcile: a no-op, and an error for unknown users.
+    client.reconcile(&user, &None);
+    assert_eq!(
+        client.try_reconcile(&Address::generate(&env), &None),
+        Err(Ok(NftError::NftNotFound))
+    );
+}
+
+#[test]
+fn test_temporary_default_penalty_is_restored_over_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &600,
+        &create_test_hash(&env, 3),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    client.set_default_penalty_mode(&true, &1_000);
+    assert_eq!(client.get_default_penalty_window(), Some(1_000));
+    let start = env.ledger().sequence();
+    client.record_default(&user, &100, &None);
+    assert_eq!(client.get_score(&user), 500);
+    assert_eq!(client.current_score(&user), 500);
+
+    env.ledger().set_sequence_number(start + 500);
+    assert_eq!(client.current_score(&user), 550);
+
+    env.ledger().set_sequence_number(start + 1_000);
+    assert_eq!(client.current_score(&user), 600);
+    env.ledger().set_sequence_number(start + 5_000);
+    assert_eq!(client.current_score(&user), 600);
+}
+
+#[test]
+fn test_second_default_keeps_points_restored_from_the_first() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &600,
+        &create_test_hash(&env, 3),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    client.set_default_penalty_mode(&true, &1_000);
+    let start = env.ledger().sequence();
+    client.record_default(&user, &100, &None);
+    env.ledger().set_sequence_number(start + 1_000);
+    assert_eq!(client.current_score(&user), 600);
+
+    // The 100 points already restored stay restored; only the new 50 count.
+    client.record_default(&user, &50, &None);
+    assert_eq!(client.get_score(&user), 550);
+    assert_eq!(client.current_score(&user), 550);
+
+    env.ledger().set_sequence_number(start + 2_000);
+    assert_eq!(client.current_score(&user), 600);
+}
+
+#[test]
+fn test_permanent_default_penalty_is_never_restored() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &600,
+        &create_test_hash(&env, 4),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    assert_eq!(client.get_default_penalty_window(), None);
+    let start = env.ledger().sequence();
+    client.record_default(&user, &100, &None);
+    env.ledger().set_sequence_number(start + 100_000);
+    assert_eq!(client.current_score(&user), 500);
+
+    assert_eq!(
+        client.try_set_default_penalty_mode(&true, &0),
+        Err(Ok(NftError::InvalidDecayConfig))
+    );
+}
+
+#[test]
+fn test_point_table_awards_points_per_band() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 5),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    assert_eq!(
+        client.try_set_scoring_mode(&ScoringMode::Table),
+        Err(Ok(NftError::InvalidPointTable))
+    );
+    assert_eq!(
+        client.try_set_point_table(&soroban_sdk::vec![&env, (1_000i128, 5u32), (500, 2)]),
+        Err(Ok(NftError::InvalidPointTable))
+    );
+
+    client.set_point_table(&soroban_sdk::vec![
+        &env,
+        (500i128, 2u32),
+        (1_000, 5),
+        (10_000, 20)
+    ]);
+    client.set_scoring_mode(&ScoringMode::Table);
+    assert_eq!(client.get_scoring_mode(), ScoringMode::Table);
+
+    // Below the first band: no points.
+    client.update_score(&user, &499, &None);
+    assert_eq!(client.get_score(&user), 500);
+
+    client.update_score(&user, &500, &None);
+    assert_eq!(client.get_score(&user), 502);
+    client.update_score(&user, &9_999, &None);
+    assert_eq!(client.get_score(&user), 507);
+    client.update_score(&user, &50_000, &None);
+    assert_eq!(client.get_score(&user), 527);
+}
+
+#[test]
+fn test_score_age_grows_until_next_update() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(client.score_age(&user), u64::MAX);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 6),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(client.score_age(&user), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_600);
+    assert_eq!(client.score_age(&user), 600);
+    env.ledger().with_mut(|li| li.timestamp = 4_000);
+    assert_eq!(client.score_age(&user), 3_000);
+
+    client.update_score(&user, &1_000, &None);
+    assert_eq!(client.score_age(&user), 0);
+    env.ledger().with_mut(|li| li.timestamp = 4_050);
+    assert_eq!(client.score_age(&user), 50);
+}
+
+#[test]
+fn test_min_score_ever_keeps_the_low_after_recovery() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(client.get_min_score_ever(&user), 0);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 7),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(client.get_min_score_ever(&user), 500);
+
+    client.record_default(&user, &100, &None);
+    assert_eq!(client.get_score(&user), 400);
+    assert_eq!(client.get_min_score_ever(&user), 400);
+
+    client.update_score(&user, &20_000, &None);
+    assert_eq!(client.get_score(&user), 600);
+    assert_eq!(client.get_min_score_ever(&user), 400);
+}
+
+#[test]
+fn test_credit_grade_band_boundaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_set_grade_bands(&soroban_sdk::vec![&env, 800u32, 700, 700, 600, 500]),
+        Err(Ok(NftError::InvalidGradeBands))
+    );
+    assert_eq!(
+        client.try_set_grade_bands(&soroban_sdk::vec![&env, 800u32, 700, 600]),
+        Err(Ok(NftError::InvalidGradeBands))
+    );
+    client.set_grade_bands(&soroban_sdk::vec![&env, 800u32, 700, 600, 500, 400]);
+
+    let cases = [
+        (850u32, "AAA"),
+        (800, "AAA"),
+        (799, "AA"),
+        (700, "AA"),
+        (699, "A"),
+        (600, "A"),
+        (599, "B"),
+        (500, "B"),
+        (499, "C"),
+        (400, "C"),
+        (399, "D"),
+    ];
+    for (i, (score, grade)) in cases.iter().enumerate() {
+        let user = Address::generate(&env);
+        client.mint(
+            &user,
+            score,
+            &create_test_hash(&env, i as u8),
+            &create_test_uri(&env),
+            &None,
+        );
+        assert_eq!(client.credit_grade(&user), Symbol::new(&env, grade));
+    }
+
+    assert_eq!(
+        client.credit_grade(&Address::generate(&env)),
+        Symbol::new(&env, "D")
+    );
+}
+
+#[test]
+fn test_max_points_per_loan_caps_large_repayments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 8),
+        &create_test_uri(&env),
+        &None,
+    );
+
+    client.set_max_points_per_loan(&25);
+    assert_eq!(client.get_max_points_per_loan(), 25);
+
+    // 1 point per 100 units: 10_000 would be 100 points, capped at 25.
+    client.update_score(&user, &10_000, &None);
+    assert_eq!(client.get_score(&user), 525);
+
+    // Under the cap: unaffected.
+    client.update_score(&user, &1_000, &None);
+    assert_eq!(client.get_score(&user), 535);
+
+    client.set_max_points_per_loan(&0);
+    client.update_score(&user, &10_000, &None);
+    assert_eq!(client.get_score(&user), 635);
+}
+
+#[test]
+fn test_min_mint_score_rejects_mints_below_the_floor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    assert_eq!(client.get_min_mint_score(), 0);
+
+    assert_eq!(
+        client.try_set_min_mint_score(&851),
+        Err(Ok(NftError::InvalidThreshold))
+    );
+    client.set_min_mint_score(&300);
+
+    let low = Address::generate(&env);
+    assert_eq!(
+        client.try_mint(
+            &low,
+            &299,
+            &create_test_hash(&env, 9),
+            &create_test_uri(&env),
+            &None,
+        ),
+        Err(Ok(NftError::BelowMinimum))
+    );
+    assert_eq!(client.get_score(&low), 0);
+
+    let at_floor = Address::generate(&env);
+    client.mint(
+        &at_floor,
+        &300,
+        &create_test_hash(&env, 10),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(client.get_score(&at_floor), 300);
+}
+
+#[test]
+fn test_can_mint_covers_admin_and_minters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.authorize_minter(&minter);
+
+    // The admin can still mint after dropping out of the minter list.
+    client.revoke_minter(&admin);
+    assert!(!client.is_authorized_minter(&admin));
+
+    assert!(client.can_mint(&admin));
+    assert!(client.can_mint(&minter));
+    assert!(!client.can_mint(&stranger));
+}
+
+#[test]
+fn test_eligibility_reports_user_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.eligibility(&user),
+        Eligibility {
+            has_nft: false,
+            score: 0,
+            seized: false,
+            default_count: 0,
+        }
+    );
+
+    client.mint(
+        &user,
+        &600,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(
+        client.eligibility(&user),
+        Eligibility {
+            has_nft: true,
+            score: 600,
+            seized: false,
+            default_count: 0,
+        }
+    );
+
+    // A default also seizes the user's collateral.
+    client.record_default(&user, &50, &None);
+    let eligibility = client.eligibility(&user);
+    assert!(eligibility.has_nft);
+    assert!(eligibility.seized);
+    assert_eq!(eligibility.default_count, 1);
+    assert_eq!(eligibility.score, client.current_score(&user));
+}
+
+#[test]
+fn test_peak_score_survives_decay_and_penalties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(client.get_peak_score(&user), 0);
+    client.mint(
+        &user,
+        &500,
+        &create_test_hash(&env, 7),
+        &create_test_uri(&env),
+        &None,
+    );
+    assert_eq!(client.get_peak_score(&user), 500);
+
+    client.update_score(&user, &20_000, &None);
+    let peak = client.get_score(&user);
+    assert!(peak > 500);
+    assert_eq!(client.get_peak_score(&user), peak);
+
+    client.set_score_decay(&10, &100, &300);
+    let start = env.ledger().sequence();
+    env.ledger().set_sequence_number(start + 1_050);
+    assert_eq!(client.current_score(&user), peak - 100);
+    assert_eq!(client.get_peak_score(&user), peak);
+
+    client.record_default(&user, &100, &None);
+    assert!(client.get_score(&user) < peak);
+    assert_eq!(client.get_peak_score(&user), peak);
+}
+
+#[test]
+fn test_decay_score_is_idempotent_within_a_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(RemittanceNFT, ());
+    let client = RemittanceNFTClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.mint(
+        &user,
+        &700,
+        &create_test_hash(&env, 1),
+        &create_test_uri(&env),
+        &None,
+    );
+    client.set_score_decay(&10, &100, &500);
+    let start = env.ledger().sequence();
+
+    env.ledger().set_sequence_number(start + 250);
+    assert_eq!(client.decay_score(&user), 680);
+    assert_eq!(client.get_score(&user), 680);
+    // Same ledger again: nothing more to apply.
+    assert_eq!(client.decay_score(&user), 680);
+    assert_eq!(client.current_score(&user), 680);
+
+    // The half period already elapsed still counts towards the next one.
+    env.ledger().set_sequence_number(start + 300);
+    assert_eq!(client.decay_score(&user), 670);
+
+    // With a minimum interval, calls inside it are refused.
+    client.set_min_decay_interval(&500);
+    env.ledger().set_sequence_number(start + 450);
+    assert_eq!(
+        client.try_decay_score(&user),
+        Err(Ok(NftError::DecayTooSoon))
+    );
+    env.ledger().set_sequence_number(start + 800);
+    assert_eq!(client.decay_score(&user), 620);
+    assert_eq!(client.current_score(&user), 620);
+}
@@ -55,7 +55,16 @@ export async function buildUnsignedLoanRequestXdr({
           new xdr.InvokeContractArgs({
             contractAddress: Address.fromString(contractId).toScAddress(),
             functionName: "request_loan",
-            args: [borrowerScVal, amountScVal, termScVal],
+            // memo, token, grace_ledgers and cosigner are left unset.
+            args: [
+              borrowerScVal,
+              amountScVal,
+              termScVal,
+              xdr.ScVal.scvVoid(),
+              xdr.ScVal.scvVoid(),
+              xdr.ScVal.scvVoid(),
+              xdr.ScVal.scvVoid(),
+            ],
           }),
         ),
         auth: [],
@@ -93,7 +102,7 @@ export async function buildUnsignedRepaymentXdr({
           new xdr.InvokeContractArgs({
             contractAddress: Address.fromString(contractId).toScAddress(),
             functionName: "repay",
-            args: [borrowerScVal, loanIdScVal, amountScVal],
+            args: [borrowerScVal, loanIdScVal, amountScVal, xdr.ScVal.scvVoid()],
           }),
         ),
         auth: [],
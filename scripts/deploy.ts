@@ -232,8 +232,8 @@ async function main() {
     //   b. authorize_minter(LoanManager) must run BEFORE LoanManager.initialize,
     //      because LoanManager.initialize asserts it is already an authorized minter.
     //   c. LendingPool has no dependency on NFT or LoanManager at init time.
-    //   d. LoanManager.initialize takes (nft, pool, token, admin) so both NFT and
-    //      Pool addresses must be known first.
+    //   d. LoanManager.initialize takes (nft, pool, token, admin, event_namespace,
+    //      loan_id_base) so both NFT and Pool addresses must be known first.
     //   e. Governance.initialize takes (admin, target_contract). We point it at
     //      LoanManager as the primary governed contract.
     //
@@ -257,11 +257,24 @@ async function main() {
         server,
         managerContractId,
         'initialize',
-        [nftContractId, poolContractId, config.token, adminAddr],
+        [
+            nftContractId,
+            poolContractId,
+            config.token,
+            adminAddr,
+            // Optional per-deployment leading topic on every LoanManager event.
+            config.eventNamespace ? nativeToScVal(config.eventNamespace, { type: 'symbol' }) : null,
+            // Optional offset for loan ids, so deployments sharing an indexer don't clash.
+            config.loanIdBase ? nativeToScVal(config.loanIdBase, { type: 'u32' }) : null,
+        ],
         account,
         passphrase,
     );
 
+    // Let the LoanManager report repaid interest to the pool's lifetime total.
+    console.log('  LendingPool.set_loan_manager(LoanManager)');
+    await invoke(server, poolContractId, 'set_loan_manager', [managerContractId], account, passphrase);
+
     // Governance — target is LoanManager (the core protocol contract).
     console.log('  Governance.initialize(target=LoanManager)');
     await invoke(server, govContractId, 'initialize', [adminAddr, managerContractId], account, passphrase);